@@ -4,13 +4,14 @@ import uuid
 from typing import TYPE_CHECKING
 
 from tach.cache.setup import resolve_dot_tach
+from tach.extension import resolve_cache_dir
 
 if TYPE_CHECKING:
     from pathlib import Path
 
 
 def get_uid(project_root: Path) -> uuid.UUID | None:
-    info_path = project_root / ".tach" / "tach.info"
+    info_path = resolve_cache_dir(project_root) / "tach.info"
     if not info_path.exists():
         resolve_dot_tach(project_root)
     contents = info_path.read_text().strip()
@@ -19,7 +20,7 @@ def get_uid(project_root: Path) -> uuid.UUID | None:
 
 
 def get_latest_version(project_root: Path) -> str | None:
-    latest_version_path = project_root / ".tach" / ".latest-version"
+    latest_version_path = resolve_cache_dir(project_root) / ".latest-version"
     if not latest_version_path.exists():
         return
     version = latest_version_path.read_text().strip()
@@ -4,6 +4,7 @@ import uuid
 from pathlib import Path
 
 from tach import __version__
+from tach.extension import prepare_cache_dir
 
 
 def resolve_dot_tach(project_root: Path) -> Path | None:
@@ -14,21 +15,11 @@ def resolve_dot_tach(project_root: Path) -> Path | None:
             else:
                 path.mkdir()
 
-    # Create .tach
-    tach_path = project_root / ".tach"
-    _create(tach_path)
+    # Create .tach, along with its .gitignore
+    tach_path = prepare_cache_dir(project_root)
     # Create info
     info_path = tach_path / "tach.info"
     _create(info_path, is_file=True, file_content=str(uuid.uuid4()))
-    # Create .gitignore
-    gitignore_content = """
-# This folder is for tach. Do not edit.
-
-# gitignore all content, including this .gitignore
-*
-    """
-    gitignore_path = tach_path / ".gitignore"
-    _create(gitignore_path, is_file=True, file_content=gitignore_content)
     # Create version
     version_path = tach_path / ".latest-version"
     _create(version_path, is_file=True, file_content=__version__)
@@ -37,7 +37,7 @@ from tach.show import (
 from tach.test import run_affected_tests
 
 if TYPE_CHECKING:
-    from tach.extension import UnusedDependencies
+    from tach.extension import Diagnostic, UnusedDependencies
 
 
 import signal
@@ -124,23 +124,23 @@ def print_generated_module_graph_file(
 
 
 def print_circular_dependency_error(
-    module_paths: list[str], output_format: str = "text"
+    cycles: list[list[str]], output_format: str = "text"
 ) -> None:
     if output_format == "json":
-        json.dump(
-            {"error": "Circular dependency", "dependencies": module_paths}, sys.stdout
-        )
+        json.dump({"error": "Circular dependency", "cycles": cycles}, sys.stdout)
     else:
         console_err.print(
             "\n".join(
                 [
-                    f"{icons.FAIL} [red]Circular dependency detected for module [/]'{module_path}'"
-                    for module_path in module_paths
+                    f"{icons.FAIL} [red]Circular dependency detected:[/] "
+                    + " -> ".join(f"'{module_path}'" for module_path in cycle)
+                    for cycle in cycles
                 ]
             )
             + f"\n\n[yellow]Resolve circular dependencies.\n"
-            f"Remove or unset 'forbid_circular_dependencies' from "
-            f"'{CONFIG_FILE_NAME}.toml' to allow circular dependencies.[/]",
+            f"Set 'circular_dependencies' to 'warn' in the 'rules' of "
+            f"'{CONFIG_FILE_NAME}.toml' to report them without failing, "
+            f"or to 'off' to allow them.[/]",
         )
 
 
@@ -229,6 +229,11 @@ def build_parser() -> argparse.ArgumentParser:
         default="text",
         help="Output format (default: text)",
     )
+    check_parser.add_argument(
+        "--rule-exit-codes",
+        action="store_true",
+        help="Encode which rules failed in the exit code, as a bitmask over the configured rules.",
+    )
     add_base_arguments(check_parser)
 
     ## tach check-external
@@ -238,6 +243,17 @@ def build_parser() -> argparse.ArgumentParser:
         help="Perform checks related to third-party dependencies",
         description="Perform checks related to third-party dependencies",
     )
+    check_parser_external.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    check_parser_external.add_argument(
+        "--rule-exit-codes",
+        action="store_true",
+        help="Encode which rules failed in the exit code, as a bitmask over the configured rules.",
+    )
     add_base_arguments(check_parser_external)
 
     ## tach sync
@@ -508,15 +524,21 @@ def check_cache_for_action(
         source_roots=[
             project_root / source_root for source_root in project_config.source_roots
         ],
+        source_extensions=project_config.source_extensions,
         action=action,
-        py_interpreter_version=f"{sys.version_info.major}.{sys.version_info.minor}.{sys.version_info.micro}",
+        interpreter_sensitive=project_config.cache.interpreter_sensitive,
+        interpreter_distributions=project_config.cache.interpreter_distributions,
         file_dependencies=project_config.cache.file_dependencies,
         env_dependencies=project_config.cache.env_dependencies,
         backend=project_config.cache.backend,
         respect_gitignore=project_config.respect_gitignore,
+        rules=project_config.resolved_rules(),
+        irrelevant_rules=project_config.cache.irrelevant_rules,
     )
     cache_result = extension.check_computation_cache(
-        project_root=project_root, cache_key=cache_key
+        project_root=project_root,
+        cache_key=cache_key,
+        cache_config=project_config.cache,
     )
     if cache_result:
         return CachedOutput(
@@ -527,6 +549,19 @@ def check_cache_for_action(
     return CachedOutput(key=cache_key)
 
 
+# With --rule-exit-codes, errors which no rule governs set the bit after every rule's bit
+CIRCULAR_DEPENDENCIES_EXIT_CODE = 1 << 5
+UNGOVERNED_ERROR_EXIT_CODE = 1 << 7
+
+
+def diagnostics_exit_code(
+    diagnostics: list[Diagnostic], rule_exit_codes: bool = False
+) -> int:
+    if rule_exit_codes:
+        return extension.rule_exit_code(diagnostics)
+    return 1 if any(diagnostic.is_error() for diagnostic in diagnostics) else 0
+
+
 def tach_check(
     project_config: ProjectConfig,
     project_root: Path,
@@ -534,6 +569,7 @@ def tach_check(
     dependencies: bool = True,
     interfaces: bool = True,
     output_format: str = "text",
+    rule_exit_codes: bool = False,
 ):
     logger.info(
         "tach check called",
@@ -553,7 +589,7 @@ def tach_check(
             dependencies=dependencies,
             interfaces=interfaces,
         )
-        has_errors = any(diagnostic.is_error() for diagnostic in diagnostics)
+        exit_code = diagnostics_exit_code(diagnostics, rule_exit_codes)
 
         if output_format == "json":
             try:
@@ -562,14 +598,13 @@ def tach_check(
                 )
             except ValueError as e:
                 json.dump({"error": str(e)}, sys.stdout)
-            sys.exit(1 if has_errors else 0)
+            sys.exit(exit_code)
 
         if diagnostics:
             print(
                 extension.format_diagnostics(diagnostics=diagnostics),
                 file=sys.stderr,
             )
-        exit_code = 1 if has_errors else 0
 
         # If we're checking in exact mode, we want to verify that there are no unused dependencies
         if dependencies and exact:
@@ -579,14 +614,16 @@ def tach_check(
             )
             if unused_dependencies:
                 print_unused_dependencies(unused_dependencies)
-                exit_code = 1
+                exit_code = (
+                    exit_code | UNGOVERNED_ERROR_EXIT_CODE if rule_exit_codes else 1
+                )
 
     except TachCircularDependencyError as e:
-        print_circular_dependency_error(e.dependencies, output_format)
-        sys.exit(1)
+        print_circular_dependency_error(e.cycles, output_format)
+        sys.exit(CIRCULAR_DEPENDENCIES_EXIT_CODE if rule_exit_codes else 1)
     except TachVisibilityError as e:
         print_visibility_errors(e.visibility_errors, output_format)
-        sys.exit(1)
+        sys.exit(UNGOVERNED_ERROR_EXIT_CODE if rule_exit_codes else 1)
     except Exception as e:
         if output_format == "json":
             json.dump({"error": str(e)}, sys.stdout)
@@ -602,12 +639,15 @@ def tach_check(
 def tach_check_external(
     project_config: ProjectConfig,
     project_root: Path,
+    output_format: str = "text",
+    rule_exit_codes: bool = False,
 ):
     logger.info(
         "tach check-external called",
         extra={
             "data": CallInfo(
                 function="tach_check_external",
+                parameters={"output_format": output_format},
             ),
         },
     )
@@ -616,15 +656,22 @@ def tach_check_external(
             project_root=project_root,
             project_config=project_config,
         )
+        exit_code = diagnostics_exit_code(diagnostics, rule_exit_codes)
+
+        if output_format == "json":
+            print(
+                extension.serialize_check_output_json(diagnostics, pretty_print=True)
+            )
+            sys.exit(exit_code)
+
         if diagnostics:
             print(
                 extension.format_diagnostics(diagnostics=diagnostics),
                 file=sys.stderr,
             )
 
-        has_errors = any(diagnostic.is_error() for diagnostic in diagnostics)
-        if has_errors:
-            sys.exit(1)
+        if exit_code:
+            sys.exit(exit_code)
         else:
             console_err.print(
                 f"{icons.SUCCESS} All external dependencies validated!", style="green"
@@ -982,6 +1029,7 @@ def tach_test(
                     ],
                     results.exit_code,
                 ),
+                cache_config=project_config.cache,
             )
         sys.exit(results.exit_code)
     except TachError as e:
@@ -1255,6 +1303,7 @@ def main(argv: list[str] = sys.argv[1:]) -> None:
                 interfaces=args.interfaces,
                 exact=args.exact,
                 output_format=args.output,
+                rule_exit_codes=args.rule_exit_codes,
             )
         else:
             tach_check(
@@ -1262,11 +1311,14 @@ def main(argv: list[str] = sys.argv[1:]) -> None:
                 project_root=project_root,
                 exact=args.exact,
                 output_format=args.output,
+                rule_exit_codes=args.rule_exit_codes,
             )
     elif args.command == "check-external":
         tach_check_external(
             project_config=project_config,
             project_root=project_root,
+            output_format=args.output,
+            rule_exit_codes=args.rule_exit_codes,
         )
     elif args.command == "report":
         include_dependency_modules = (
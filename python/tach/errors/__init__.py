@@ -14,8 +14,8 @@ class TachClosedBetaError(TachError): ...
 
 
 class TachCircularDependencyError(TachError):
-    def __init__(self, dependencies: list[str]):
-        self.dependencies = dependencies
+    def __init__(self, cycles: list[list[str]]):
+        self.cycles = cycles
         super().__init__("Circular dependency error")
 
 
@@ -31,18 +31,27 @@ def create_dependency_report(
 def create_computation_cache_key(
     project_root: Path,
     source_roots: list[Path],
+    source_extensions: list[str],
     action: str,
-    py_interpreter_version: str,
+    interpreter_sensitive: bool,
+    interpreter_distributions: bool,
     file_dependencies: list[str],
     env_dependencies: list[str],
     backend: str,
     respect_gitignore: RespectGitIgnore,
+    rules: RulesConfig,
+    irrelevant_rules: list[str],
 ) -> str: ...
+def resolve_cache_dir(project_root: Path) -> Path: ...
+def prepare_cache_dir(project_root: Path) -> Path: ...
 def check_computation_cache(
-    project_root: Path, cache_key: str
+    project_root: Path, cache_key: str, cache_config: CacheConfig
 ) -> tuple[list[tuple[int, str]], int] | None: ...
 def update_computation_cache(
-    project_root: Path, cache_key: str, value: tuple[list[tuple[int, str]], int]
+    project_root: Path,
+    cache_key: str,
+    value: tuple[list[tuple[int, str]], int],
+    cache_config: CacheConfig,
 ) -> None: ...
 def parse_project_config(filepath: Path) -> tuple[ProjectConfig, bool]: ...
 def parse_project_config_from_pyproject(filepath: Path) -> ProjectConfig: ...
@@ -76,6 +85,7 @@ class Diagnostic:
     def is_dependency_error(self) -> bool: ...
     def is_interface_error(self) -> bool: ...
     def is_warning(self) -> bool: ...
+    def is_info(self) -> bool: ...
     def is_error(self) -> bool: ...
     def is_deprecated(self) -> bool: ...
     def usage_module(self) -> str | None: ...
@@ -87,6 +97,14 @@ class Diagnostic:
 def serialize_diagnostics_json(
     diagnostics: list[Diagnostic], pretty_print: bool
 ) -> str: ...
+def serialize_check_output_json(
+    diagnostics: list[Diagnostic], pretty_print: bool = False
+) -> str: ...
+def serialize_rule_summary_json(
+    diagnostics: list[Diagnostic], rules: RulesConfig, pretty_print: bool = False
+) -> str: ...
+def rule_exit_code(diagnostics: list[Diagnostic]) -> int: ...
+def config_json_schema(pretty_print: bool = False) -> str: ...
 
 class DependencyConfig:
     path: str
@@ -101,6 +119,7 @@ class ModuleConfig:
     visibility: list[str]
     strict: bool
     unchecked: bool
+    deprecated: bool
     layer: str | None
 
     def __new__(cls, path: str, strict: bool) -> ModuleConfig: ...
@@ -115,22 +134,73 @@ class InterfaceConfig:
     visibility: list[str] | None
     data_types: InterfaceDataTypes
 
-CacheBackend = Literal["disk"]
+CacheBackend = Literal["disk", "memory", "http", "s3"]
 
 class CacheConfig:
     backend: CacheBackend
     file_dependencies: list[str]
     env_dependencies: list[str]
+    irrelevant_rules: list[str]
+    ttl_seconds: int | None
+    max_size_bytes: int | None
+    compression: int | None
+    interpreter_sensitive: bool
+    interpreter_distributions: bool
 
 class ExternalDependencyConfig:
     exclude: list[str]
     rename: list[str]
+    renames: dict[str, str]
+    rename_files: list[Path]
+    first_party_prefixes: list[str]
+    namespace_packages: list[str]
+    detect_namespace_packages: bool
+    include_dependency_groups: list[str]
+    single_dependency_source: bool
+    conda_lock: bool
+    pipfile_include_dev: bool
+    allow_transitive_imports: bool
+    validate_renames: bool
+    case_insensitive_paths: bool | None
+    unused_check_exempt_groups: list[str]
+    missing_group_severities: dict[str, RuleSetting]
+    collapse_unused_groups: bool
+    tool_plugins: bool
+    uv_support: bool
+    use_installed_distributions: bool
+    validate_installed_entries: bool
+    pip_install_files: list[Path]
+    allow_external_source_paths: bool
+    strict_source_paths: bool
+    suggestion_max_distance: int
+    suggestion_min_length: int
+    name_normalization: Literal["import", "pep503"]
+    max_dependency_group_depth: int
+    target_environment: MarkerEnvironment
+    preserve_unknown_chars: bool
+    source_layout_template: str | None
+    strict_specifiers: bool
+
+class MarkerEnvironment:
+    python_version: str | None
+    sys_platform: str | None
+    os_name: str | None
+
+class ProjectInfo:
+    name: str | None
+    dependencies: set[str]
+    dependency_order: list[str]
+    source_paths: list[Path]
+
+    def __new__(
+        cls, name: str | None, dependencies: list[str], source_paths: list[Path]
+    ) -> ProjectInfo: ...
 
 class UnusedDependencies:
     path: str
     dependencies: list[DependencyConfig]
 
-RuleSetting = Literal["error", "warn", "off"]
+RuleSetting = Literal["error", "warn", "info", "off"]
 
 RootModuleTreatment = Literal["allow", "ignore", "dependenciesonly", "forbid"]
 
@@ -140,18 +210,23 @@ RespectGitIgnore: TypeAlias = bool | Literal["if_git_repo"]
 class RulesConfig:
     unused_ignore_directives: RuleSetting
     require_ignore_directive_reasons: RuleSetting
+    circular_dependencies: RuleSetting | None
+    deprecated_module_usage: RuleSetting
+    ignore_directive_reason_pattern: str | None
 
 class ProjectConfig:
     cache: CacheConfig
     external: ExternalDependencyConfig
     exclude: list[str]
     source_roots: list[Path]
+    source_extensions: list[str]
     exact: bool
     ignore_type_checking_imports: bool
     include_string_imports: bool
     forbid_circular_dependencies: bool
     layers_explicit_depends_on: bool
     respect_gitignore: RespectGitIgnore
+    relative_paths: bool
     # [DEPRECATED] Just reading this to print a warning
     use_regex_matching: bool
     rules: RulesConfig
@@ -178,6 +253,8 @@ class ProjectConfig:
     def all_modules(self) -> list[ModuleConfig]: ...
     def all_interfaces(self) -> list[InterfaceConfig]: ...
     def filtered_modules(self, included_paths: list[Path]) -> list[ModuleConfig]: ...
+    def resolved_rules(self) -> RulesConfig: ...
+    def effective_rules(self, path: str) -> RulesConfig: ...
 
 class TachPytestPluginHandler:
     removed_test_paths: set[str]
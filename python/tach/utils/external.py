@@ -35,6 +35,14 @@ def get_module_mappings():
     return packages_distributions()
 
 
+def get_installed_distributions() -> list[str]:
+    from importlib.metadata import distributions
+
+    return sorted(
+        {dist.metadata["Name"] for dist in distributions() if dist.metadata["Name"]}
+    )
+
+
 PYPI_PACKAGE_REGEX = re.compile(r"[-_.]+")
 
 
@@ -53,6 +61,7 @@ def normalize_package_name(import_module_path: str) -> str:
 __all__ = [
     "is_stdlib_module",
     "get_module_mappings",
+    "get_installed_distributions",
     "get_package_name",
     "normalize_package_name",
 ]
@@ -105,7 +105,7 @@ def test_check_circular_dependency_text(example_dir, capfd, mocker):
 
     mocker.patch(
         "tach.extension.check",
-        side_effect=TachCircularDependencyError(["mod1", "mod2", "mod1"]),
+        side_effect=TachCircularDependencyError([["mod1", "mod2", "mod1"]]),
     )
 
     with pytest.raises(SystemExit) as exc_info:
@@ -117,8 +117,7 @@ def test_check_circular_dependency_text(example_dir, capfd, mocker):
 
     captured = capfd.readouterr()
     assert "Circular dependency detected" in captured.err
-    assert "'mod1'" in captured.err
-    assert "'mod2'" in captured.err
+    assert "'mod1' -> 'mod2' -> 'mod1'" in captured.err
 
 
 def test_check_circular_dependency_json(example_dir, capfd, mocker):
@@ -128,7 +127,7 @@ def test_check_circular_dependency_json(example_dir, capfd, mocker):
 
     mocker.patch(
         "tach.extension.check",
-        side_effect=TachCircularDependencyError(["mod1", "mod2", "mod1"]),
+        side_effect=TachCircularDependencyError([["mod1", "mod2", "mod1"]]),
     )
 
     with pytest.raises(SystemExit) as exc_info:
@@ -698,3 +697,35 @@ def test_layers_explicit_depends_on_disabled(
     assert exc_info.value.code == 0
     captured = capfd.readouterr()
     assert SUCCESS in captured.err
+
+
+@pytest.mark.parametrize("use_installed_distributions", [True, False])
+def test_check_external_installed_distributions(
+    tmp_path: Path, use_installed_distributions: bool
+):
+    from tach.extension import check_external_dependencies
+
+    project_root = tmp_path / "installed_distributions"
+    (project_root / "src").mkdir(parents=True)
+    (project_root / "src" / "main.py").write_text("import pytest\n")
+    (project_root / "pyproject.toml").write_text(
+        '[project]\nname = "installed-distributions"\ndependencies = []\n'
+    )
+    (project_root / "tach.toml").write_text(
+        'source_roots = ["src"]\n\n'
+        "[external]\n"
+        f"use_installed_distributions = {str(use_installed_distributions).lower()}\n"
+    )
+    project_config = parse_project_config(root=project_root)
+    assert project_config is not None
+
+    diagnostics = check_external_dependencies(
+        project_root=project_root, project_config=project_config
+    )
+
+    undeclared = [
+        diagnostic
+        for diagnostic in diagnostics
+        if "pytest" in diagnostic.to_string() and "not declared" in diagnostic.to_string()
+    ]
+    assert bool(undeclared) is not use_installed_distributions
@@ -43,6 +43,18 @@ def test_execute_with_config(capfd, mock_check, mock_project_config):
     assert "All modules validated!" in captured.err
 
 
+def test_clean_check_with_rule_exit_codes(capfd, mock_check, mock_project_config):
+    args, _ = cli.parse_arguments(["check", "--rule-exit-codes"])
+    assert args.rule_exit_codes
+    with pytest.raises(SystemExit) as sys_exit:
+        cli.tach_check(
+            project_root=Path(),
+            project_config=mock_project_config,
+            rule_exit_codes=args.rule_exit_codes,
+        )
+    assert sys_exit.value.code == 0
+
+
 def test_invalid_command(capfd):
     with pytest.raises(SystemExit) as sys_exit:
         # Test with an invalid command
@@ -1,7 +1,10 @@
 from __future__ import annotations
 
+from pathlib import Path
+
 import pytest
 
+from tach.extension import ProjectInfo
 from tach.parsing import parse_project_config
 
 
@@ -13,3 +16,10 @@ def test_invalid_project_config(example_dir):
 def test_empty_project_config(example_dir):
     with pytest.raises(ValueError):
         parse_project_config(example_dir / "invalid" / "empty")
+
+
+def test_project_info_constructor():
+    project_info = ProjectInfo("my-pkg", ["Requests", "pytest-cov"], [Path("src")])
+    assert project_info.name == "my-pkg"
+    assert project_info.dependencies == {"requests", "pytest_cov"}
+    assert [str(path) for path in project_info.source_paths] == ["src"]
@@ -0,0 +1,395 @@
+use cached::{DiskCache, IOCached};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{ComputationCacheValue, Result, legacy_cache_dir, prepare_cache_dir};
+use crate::config::{CacheConfig, CompressionLevel};
+
+/// Marks an entry as zstd-compressed JSON. Uncompressed entries are plain JSON,
+/// which can never start with these bytes.
+const ZSTD_MAGIC: &[u8] = b"\0TACH-ZSTD\0";
+
+fn encode_entry(
+    value: &ComputationCacheValue,
+    compression: Option<CompressionLevel>,
+) -> Result<Vec<u8>> {
+    let content = serde_json::to_vec(value)?;
+    let Some(compression) = compression else {
+        return Ok(content);
+    };
+    let mut entry = ZSTD_MAGIC.to_vec();
+    entry.extend(zstd::bulk::compress(&content, compression.level())?);
+    Ok(entry)
+}
+
+fn decode_entry(entry: &[u8]) -> Result<ComputationCacheValue> {
+    match entry.strip_prefix(ZSTD_MAGIC) {
+        Some(compressed) => Ok(serde_json::from_slice(&zstd::decode_all(compressed)?)?),
+        None => Ok(serde_json::from_slice(entry)?),
+    }
+}
+
+/// Bookkeeping for a cache entry, stored separately from its value so that entries written
+/// before the metadata existed can still be read.
+#[derive(Debug, Serialize, Deserialize)]
+struct EntryMetadata {
+    /// When the entry was written, in whole seconds since the Unix epoch
+    created_at: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct AccessRecord {
+    /// Position in the order of accesses, where the most recently used entry is the highest
+    last_access: u64,
+    /// The approximate size of the entry
+    size_bytes: u64,
+}
+
+/// The sizes and access order of cache entries, which decide what to evict
+/// when the cache grows past its size limit
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AccessIndex {
+    next_access: u64,
+    entries: BTreeMap<String, AccessRecord>,
+}
+
+impl AccessIndex {
+    /// Read the index, starting afresh if it is missing or unreadable
+    fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Best-effort, a lost update only makes eviction less accurate
+    fn save(&self, path: &Path) {
+        let temp_path = path.with_extension("json.tmp");
+        if let Ok(content) = serde_json::to_vec(self) {
+            let _ = fs::write(&temp_path, content).and_then(|_| fs::rename(&temp_path, path));
+        }
+    }
+
+    fn touch(&mut self, cache_key: &str, size_bytes: Option<u64>) {
+        let last_access = self.next_access;
+        self.next_access += 1;
+        let size_bytes = size_bytes
+            .or_else(|| self.entries.get(cache_key).map(|record| record.size_bytes))
+            .unwrap_or_default();
+        self.entries.insert(
+            cache_key.to_string(),
+            AccessRecord {
+                last_access,
+                size_bytes,
+            },
+        );
+    }
+
+    /// The least recently used entries to remove so that the rest fit within `max_size_bytes`
+    fn evictions(&self, max_size_bytes: u64) -> Vec<String> {
+        let mut total_size: u64 = self.entries.values().map(|record| record.size_bytes).sum();
+        let mut by_access: Vec<(&String, &AccessRecord)> = self.entries.iter().collect();
+        by_access.sort_by_key(|(_, record)| record.last_access);
+        by_access
+            .into_iter()
+            .take_while(|(_, record)| {
+                let over_budget = total_size > max_size_bytes;
+                total_size = total_size.saturating_sub(record.size_bytes);
+                over_budget
+            })
+            .map(|(cache_key, _)| cache_key.clone())
+            .collect()
+    }
+}
+
+/// The computation cache stored beneath the `.tach` directory
+pub struct DiskComputationCache {
+    /// Encoded entries, which may be compressed
+    entries: DiskCache<String, Vec<u8>>,
+    /// Entries written before they were encoded, which are still read but no longer written.
+    /// Older releases kept them in the project root, so they are only opened where they exist.
+    legacy_entries: Option<DiskCache<String, ComputationCacheValue>>,
+    metadata: DiskCache<String, EntryMetadata>,
+    index_path: PathBuf,
+    /// Entries older than this are misses. With a TTL, entries without metadata are misses too.
+    ttl: Option<Duration>,
+    max_size_bytes: Option<u64>,
+    compression: Option<CompressionLevel>,
+}
+
+fn build_disk_cache<V: Serialize + DeserializeOwned>(
+    cache_dir: &Path,
+    name: &str,
+) -> Result<DiskCache<String, V>> {
+    Ok(DiskCache::<String, V>::new(name)
+        .set_disk_directory(cache_dir.join(name))
+        .build()?)
+}
+
+impl DiskComputationCache {
+    pub fn try_new<P: AsRef<Path>>(project_root: P, cache_config: &CacheConfig) -> Result<Self> {
+        let cache_dir = prepare_cache_dir(&project_root);
+        let legacy_dir = legacy_cache_dir(&project_root);
+        let legacy_entries =
+            if legacy_dir == cache_dir || legacy_dir.join("computation-cache").exists() {
+                Some(build_disk_cache(&legacy_dir, "computation-cache")?)
+            } else {
+                None
+            };
+        Ok(Self {
+            entries: build_disk_cache(&cache_dir, "computation-cache-entries")?,
+            legacy_entries,
+            metadata: build_disk_cache(&cache_dir, "computation-cache-metadata")?,
+            index_path: cache_dir.join("computation-cache-index.json"),
+            ttl: cache_config.ttl(),
+            max_size_bytes: cache_config.max_size_bytes,
+            compression: cache_config.compression,
+        })
+    }
+
+    fn is_expired(&self, cache_key: &String) -> bool {
+        let Some(ttl) = self.ttl else {
+            return false;
+        };
+        match self.metadata.cache_get(cache_key) {
+            Ok(Some(metadata)) => now().saturating_sub(metadata.created_at) > ttl.as_secs(),
+            // Missing or unreadable metadata cannot prove the entry is fresh
+            _ => true,
+        }
+    }
+
+    fn remove(&self, cache_key: &String) -> Result<()> {
+        self.entries.cache_remove(cache_key)?;
+        if let Some(legacy_entries) = &self.legacy_entries {
+            legacy_entries.cache_remove(cache_key)?;
+        }
+        self.metadata.cache_remove(cache_key)?;
+        Ok(())
+    }
+
+    pub fn get(&self, cache_key: String) -> Result<Option<ComputationCacheValue>> {
+        if self.is_expired(&cache_key) {
+            self.remove(&cache_key)?;
+            return Ok(None);
+        }
+        let value = match self.entries.cache_get(&cache_key)? {
+            Some(entry) => Some(decode_entry(&entry)?),
+            None => match &self.legacy_entries {
+                Some(legacy_entries) => legacy_entries.cache_get(&cache_key)?,
+                None => None,
+            },
+        };
+        if value.is_some() {
+            let mut index = AccessIndex::load(&self.index_path);
+            index.touch(&cache_key, None);
+            index.save(&self.index_path);
+        }
+        Ok(value)
+    }
+
+    pub fn set(
+        &self,
+        cache_key: String,
+        value: ComputationCacheValue,
+    ) -> Result<Option<ComputationCacheValue>> {
+        let entry = encode_entry(&value, self.compression)?;
+        let size_bytes = entry.len() as u64;
+        self.metadata
+            .cache_set(cache_key.clone(), EntryMetadata { created_at: now() })?;
+        let previous = match self.entries.cache_set(cache_key.clone(), entry)? {
+            Some(previous) => decode_entry(&previous).ok(),
+            None => match &self.legacy_entries {
+                Some(legacy_entries) => legacy_entries.cache_remove(&cache_key)?,
+                None => None,
+            },
+        };
+
+        let mut index = AccessIndex::load(&self.index_path);
+        index.touch(&cache_key, Some(size_bytes));
+        if let Some(max_size_bytes) = self.max_size_bytes {
+            self.evict(&mut index, max_size_bytes);
+        }
+        index.save(&self.index_path);
+        Ok(previous)
+    }
+
+    /// Remove least recently used entries until the cache fits within `max_size_bytes`.
+    /// Best-effort, so that a failed removal never fails the run.
+    fn evict(&self, index: &mut AccessIndex, max_size_bytes: u64) {
+        for cache_key in index.evictions(max_size_bytes) {
+            let _ = self.remove(&cache_key);
+            index.entries.remove(&cache_key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn value() -> ComputationCacheValue {
+        (vec![(1, "1 passed".to_string())], 0)
+    }
+
+    fn value_size() -> u64 {
+        encode_entry(&value(), None).unwrap().len() as u64
+    }
+
+    fn ttl_config() -> CacheConfig {
+        CacheConfig {
+            ttl_seconds: Some(60),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_entries_without_ttl_never_expire() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let cache =
+            DiskComputationCache::try_new(temp_dir.path(), &CacheConfig::default()).unwrap();
+        cache.set("key".to_string(), value()).unwrap();
+        cache
+            .metadata
+            .cache_set("key".to_string(), EntryMetadata { created_at: 0 })
+            .unwrap();
+
+        assert_eq!(cache.get("key".to_string()).unwrap(), Some(value()));
+    }
+
+    #[test]
+    fn test_expired_entries_are_removed() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let cache = DiskComputationCache::try_new(temp_dir.path(), &ttl_config()).unwrap();
+        cache.set("fresh".to_string(), value()).unwrap();
+        cache.set("stale".to_string(), value()).unwrap();
+        cache
+            .metadata
+            .cache_set("stale".to_string(), EntryMetadata { created_at: 0 })
+            .unwrap();
+
+        assert_eq!(cache.get("fresh".to_string()).unwrap(), Some(value()));
+        assert_eq!(cache.get("stale".to_string()).unwrap(), None);
+        assert_eq!(cache.entries.cache_get(&"stale".to_string()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_entries_without_metadata_expire_with_ttl() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let cache = DiskComputationCache::try_new(temp_dir.path(), &ttl_config()).unwrap();
+        // As written before entry metadata was recorded
+        cache
+            .legacy_entries
+            .as_ref()
+            .unwrap()
+            .cache_set("legacy".to_string(), value())
+            .unwrap();
+
+        assert_eq!(cache.get("legacy".to_string()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_least_recently_used_entries_are_evicted() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let cache_config = CacheConfig {
+            max_size_bytes: Some(value_size() * 2),
+            ..Default::default()
+        };
+        let cache = DiskComputationCache::try_new(temp_dir.path(), &cache_config).unwrap();
+        cache.set("first".to_string(), value()).unwrap();
+        cache.set("second".to_string(), value()).unwrap();
+        // Using the first entry makes the second the least recently used
+        assert!(cache.get("first".to_string()).unwrap().is_some());
+        cache.set("third".to_string(), value()).unwrap();
+
+        assert!(cache.get("first".to_string()).unwrap().is_some());
+        assert_eq!(cache.get("second".to_string()).unwrap(), None);
+        assert!(cache.get("third".to_string()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_unreadable_index_does_not_fail_writes() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let cache_config = CacheConfig {
+            max_size_bytes: Some(0),
+            ..Default::default()
+        };
+        let cache = DiskComputationCache::try_new(temp_dir.path(), &cache_config).unwrap();
+        fs::write(&cache.index_path, "not json").unwrap();
+
+        assert!(cache.set("key".to_string(), value()).is_ok());
+        assert_eq!(cache.get("key".to_string()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_legacy_entries_are_read() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let cache_config = CacheConfig {
+            compression: Some(CompressionLevel::try_from(3).unwrap()),
+            ..Default::default()
+        };
+        let cache = DiskComputationCache::try_new(temp_dir.path(), &cache_config).unwrap();
+        cache
+            .legacy_entries
+            .as_ref()
+            .unwrap()
+            .cache_set("legacy".to_string(), value())
+            .unwrap();
+
+        assert_eq!(cache.get("legacy".to_string()).unwrap(), Some(value()));
+    }
+
+    #[test]
+    fn test_legacy_entries_are_read_from_project_root() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let repo_root = temp_dir.path();
+        let project_root = repo_root.join("packages/one");
+        fs::create_dir_all(repo_root.join(".git")).unwrap();
+        fs::create_dir_all(&project_root).unwrap();
+        // As written by releases which kept the cache in the project root
+        build_disk_cache::<ComputationCacheValue>(&project_root.join(".tach"), "computation-cache")
+            .unwrap()
+            .cache_set("legacy".to_string(), value())
+            .unwrap();
+
+        let cache = DiskComputationCache::try_new(&project_root, &CacheConfig::default()).unwrap();
+        assert_eq!(cache.get("legacy".to_string()).unwrap(), Some(value()));
+        assert!(repo_root.join(".tach/.gitignore").is_file());
+    }
+
+    #[test]
+    fn test_compressed_entries_round_trip() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let cache_config = CacheConfig {
+            compression: Some(CompressionLevel::try_from(3).unwrap()),
+            ..Default::default()
+        };
+        let cache = DiskComputationCache::try_new(temp_dir.path(), &cache_config).unwrap();
+        cache.set("key".to_string(), value()).unwrap();
+
+        let entry = cache
+            .entries
+            .cache_get(&"key".to_string())
+            .unwrap()
+            .unwrap();
+        assert!(entry.starts_with(ZSTD_MAGIC));
+        assert_eq!(cache.get("key".to_string()).unwrap(), Some(value()));
+        drop(cache);
+
+        // Compressed entries remain readable once compression is turned off
+        let cache =
+            DiskComputationCache::try_new(temp_dir.path(), &CacheConfig::default()).unwrap();
+        assert_eq!(cache.get("key".to_string()).unwrap(), Some(value()));
+    }
+}
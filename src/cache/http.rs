@@ -0,0 +1,124 @@
+use std::env;
+use std::time::Duration;
+
+use super::{CacheError, ComputationCacheValue, Result};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An HTTP client for remote caches, which gives up on slow requests
+pub(super) fn build_agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .build()
+        .into()
+}
+
+/// A remote computation cache, which stores each entry at `<url>/<cache key>` with `PUT`
+/// and retrieves it with `GET`.
+///
+/// Unless `strict` is set, network errors and unreadable entries are reported as cache misses,
+/// so an unavailable cache server only slows a run down.
+pub struct HttpCache {
+    url: String,
+    strict: bool,
+    auth_header: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl HttpCache {
+    pub fn new(url: &str, strict: bool, auth_header_env: Option<&str>) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            strict,
+            auth_header: auth_header_env.and_then(|var| env::var(var).ok()),
+            agent: build_agent(),
+        }
+    }
+
+    fn entry_url(&self, cache_key: &str) -> String {
+        format!("{}/{cache_key}", self.url)
+    }
+
+    fn fetch(&self, cache_key: &str) -> Result<Option<ComputationCacheValue>> {
+        let mut request = self.agent.get(self.entry_url(cache_key));
+        if let Some(auth_header) = &self.auth_header {
+            request = request.header("Authorization", auth_header);
+        }
+        let mut response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::StatusCode(404)) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let body = response.body_mut().read_to_vec()?;
+        Ok(Some(serde_json::from_slice(&body)?))
+    }
+
+    fn store(&self, cache_key: &str, value: &ComputationCacheValue) -> Result<()> {
+        let body = serde_json::to_vec(value)?;
+        let mut request = self
+            .agent
+            .put(self.entry_url(cache_key))
+            .header("Content-Type", "application/json");
+        if let Some(auth_header) = &self.auth_header {
+            request = request.header("Authorization", auth_header);
+        }
+        request.send(&body[..])?;
+        Ok(())
+    }
+
+    /// Treat a failure as a cache miss, unless the cache is strict
+    fn degrade<T: Default>(&self, result: Result<T>) -> Result<T> {
+        match result {
+            Err(err) if !self.strict => {
+                eprintln!("Remote cache unavailable, continuing without it: {err}");
+                Ok(T::default())
+            }
+            result => result,
+        }
+    }
+
+    pub fn get(&self, cache_key: &str) -> Result<Option<ComputationCacheValue>> {
+        self.degrade(self.fetch(cache_key))
+    }
+
+    pub fn set(&self, cache_key: &str, value: &ComputationCacheValue) -> Result<()> {
+        self.degrade(self.store(cache_key, value))
+    }
+}
+
+impl From<ureq::Error> for CacheError {
+    fn from(err: ureq::Error) -> Self {
+        CacheError::Http(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Nothing listens on port 1, so every request fails to connect
+    const UNREACHABLE_URL: &str = "http://127.0.0.1:1/cache/";
+
+    #[test]
+    fn test_entry_url_ignores_trailing_slash() {
+        let cache = HttpCache::new(UNREACHABLE_URL, false, None);
+        assert_eq!(cache.entry_url("ABC123"), "http://127.0.0.1:1/cache/ABC123");
+    }
+
+    #[test]
+    fn test_network_errors_are_cache_misses() {
+        let cache = HttpCache::new(UNREACHABLE_URL, false, None);
+        assert!(cache.get("ABC123").unwrap().is_none());
+        assert!(cache.set("ABC123", &(vec![], 0)).is_ok());
+    }
+
+    #[test]
+    fn test_strict_cache_reports_network_errors() {
+        let cache = HttpCache::new(UNREACHABLE_URL, true, None);
+        assert!(matches!(cache.get("ABC123"), Err(CacheError::Http(_))));
+        assert!(matches!(
+            cache.set("ABC123", &(vec![], 0)),
+            Err(CacheError::Http(_))
+        ));
+    }
+}
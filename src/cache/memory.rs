@@ -0,0 +1,47 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use super::{ComputationCacheValue, Result};
+
+static MEMORY_CACHE: Lazy<Mutex<HashMap<String, ComputationCacheValue>>> =
+    Lazy::new(Default::default);
+
+/// A computation cache which only lasts as long as the current process, for tests
+/// and for long-lived processes which should not write to disk
+pub struct MemoryComputationCache;
+
+impl MemoryComputationCache {
+    fn entries(&self) -> MutexGuard<'static, HashMap<String, ComputationCacheValue>> {
+        // A panic while holding the lock cannot leave the map half-updated
+        MEMORY_CACHE.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn get(&self, cache_key: String) -> Result<Option<ComputationCacheValue>> {
+        Ok(self.entries().get(&cache_key).cloned())
+    }
+
+    pub fn set(
+        &self,
+        cache_key: String,
+        value: ComputationCacheValue,
+    ) -> Result<Option<ComputationCacheValue>> {
+        Ok(self.entries().insert(cache_key, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_cache_hit_and_miss() {
+        let cache = MemoryComputationCache;
+        let key = "memory-cache-test".to_string();
+        let value: ComputationCacheValue = (vec![(1, "1 passed".to_string())], 0);
+
+        assert_eq!(cache.get(key.clone()).unwrap(), None);
+        assert_eq!(cache.set(key.clone(), value.clone()).unwrap(), None);
+        assert_eq!(cache.get(key).unwrap(), Some(value));
+    }
+}
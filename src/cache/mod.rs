@@ -0,0 +1,617 @@
+use cached::DiskCacheError;
+use cached::stores::DiskCacheBuildError;
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use std::{env, fs};
+use thiserror::Error;
+use toml::Value;
+
+use crate::config::{CacheBackend, CacheConfig, RespectGitIgnore, RulesConfig};
+use crate::filesystem;
+
+mod disk;
+mod http;
+mod memory;
+mod s3;
+
+pub use disk::DiskComputationCache;
+pub use http::HttpCache;
+pub use memory::MemoryComputationCache;
+pub use s3::S3Cache;
+
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("Disk cache error: {0}")]
+    DiskCache(#[from] DiskCacheError),
+    #[error("Disk cache build error: {0}")]
+    DiskCacheBuild(#[from] DiskCacheBuildError),
+    #[error("HTTP cache error: {0}")]
+    Http(Box<ureq::Error>),
+    #[error("Missing S3 credentials, set AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY")]
+    MissingS3Credentials,
+    #[error("Cache compression error: {0}")]
+    Compression(#[from] std::io::Error),
+    #[error("Invalid cache entry: {0}")]
+    InvalidEntry(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, CacheError>;
+
+#[derive(Debug)]
+struct CacheKey {
+    hash: String,
+}
+
+impl FromIterator<u8> for CacheKey {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut hasher = DefaultHasher::new();
+        for item in iter {
+            item.hash(&mut hasher);
+        }
+        let hash = format!("{:016X}", hasher.finish());
+        CacheKey { hash }
+    }
+}
+
+static CACHE_DIR: &str = ".tach";
+
+static CACHE_GITIGNORE: &str = "# This folder is for tach. Do not edit.
+
+# gitignore all content, including this .gitignore
+*";
+
+pub type ComputationCacheValue = (Vec<(u8, String)>, u8);
+
+/// Resolve the directory which holds the cache, anchored at the enclosing git repository
+/// so that invocations from anywhere in the repo share a single cache.
+/// Falls back to the project root outside of a git repository.
+pub fn resolve_cache_dir<P: AsRef<Path>>(project_root: P) -> PathBuf {
+    let project_root = project_root.as_ref();
+    project_root
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(project_root)
+        .join(CACHE_DIR)
+}
+
+/// Create the cache directory for `project_root` along with its .gitignore, returning its path.
+/// Best-effort, the cache itself will report any real I/O problems.
+pub fn prepare_cache_dir<P: AsRef<Path>>(project_root: P) -> PathBuf {
+    let cache_dir = resolve_cache_dir(project_root);
+    let gitignore_path = cache_dir.join(".gitignore");
+    if !gitignore_path.exists() {
+        let _ = fs::create_dir_all(&cache_dir)
+            .and_then(|_| fs::write(&gitignore_path, CACHE_GITIGNORE));
+    }
+    cache_dir
+}
+
+/// The cache directory of releases which kept the cache in the project root, even within a git repository
+fn legacy_cache_dir<P: AsRef<Path>>(project_root: P) -> PathBuf {
+    project_root.as_ref().join(CACHE_DIR)
+}
+
+fn parse_project_dependencies<P: AsRef<Path>>(project_root: P) -> impl Iterator<Item = String> {
+    let project_root = project_root.as_ref();
+    let mut dependencies = Vec::new();
+
+    // Check for requirements.txt
+    let requirements_path = project_root.join("requirements.txt");
+    if requirements_path.is_file() {
+        if let Ok(content) = filesystem::read_file_content(&requirements_path) {
+            for line in content.lines() {
+                if !line.trim().is_empty() && !line.trim().starts_with('#') {
+                    dependencies.push(line.trim().to_string());
+                }
+            }
+            return dependencies.into_iter();
+        }
+    }
+
+    // Check for pyproject.toml
+    let pyproject_path = project_root.join("pyproject.toml");
+    if pyproject_path.is_file() {
+        let content = filesystem::read_file_content(&pyproject_path).unwrap_or_default();
+        let toml_value = content.parse::<Value>().unwrap_or(Value::Integer(0));
+        if let Some(dependencies_array) = toml_value
+            .get("project")
+            .and_then(|v| v.get("dependencies"))
+            .and_then(|v| v.as_array())
+        {
+            for dep_str in dependencies_array.iter().filter_map(|dep| dep.as_str()) {
+                dependencies.push(dep_str.to_string());
+            }
+        }
+        // Handle optional dependencies if necessary
+        if let Some(optional_dependencies) = toml_value
+            .get("project")
+            .and_then(|v| v.get("optional-dependencies"))
+            .and_then(|v| v.as_table())
+        {
+            for deps_array in optional_dependencies
+                .values()
+                .filter_map(|deps| deps.as_array())
+            {
+                for dep_str in deps_array.iter().filter_map(|dep| dep.as_str()) {
+                    dependencies.push(dep_str.to_string());
+                }
+            }
+        }
+
+        return dependencies.into_iter();
+    }
+
+    // Didn't find any dependencies
+    println!(
+        "Did not auto-detect dependencies. Is there a 'requirements.txt' or 'pyproject.toml' in your project root?"
+    );
+
+    vec![].into_iter()
+}
+
+/// Expand the `file_dependencies` glob patterns beneath the project root, and describe each matched
+/// file by its relative path and contents. Files are sorted by their path (with `/` separators),
+/// so that the description is the same across platforms. Patterns which match nothing are allowed.
+fn read_file_dependencies(
+    project_root: &Path,
+    file_dependencies: Vec<String>,
+    walker: &filesystem::FSWalker,
+) -> impl Iterator<Item = u8> {
+    let mut files: Vec<(String, PathBuf)> = walker
+        .walk_globbed_files(project_root.to_str().unwrap(), file_dependencies)
+        .filter_map(|path| {
+            let relative_path = filesystem::relative_to(&path, project_root).ok()?;
+            let relative_path = relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            Some((relative_path, path))
+        })
+        .collect();
+    files.sort();
+    files.into_iter().flat_map(|(relative_path, path)| {
+        relative_path
+            .into_bytes()
+            .into_iter()
+            .chain(fs::read(path).unwrap_or_default())
+    })
+}
+
+/// Describe each environment dependency as `NAME=value`, where an unset variable has an empty value.
+/// A dependency ending in `*` matches every variable with the preceding prefix, which are described
+/// in order of their names, so adding or removing a matching variable changes the description.
+fn describe_env_dependencies(
+    env_dependencies: Vec<String>,
+    env: &BTreeMap<String, String>,
+) -> Vec<String> {
+    env_dependencies
+        .into_iter()
+        .flat_map(|var| match var.strip_suffix('*') {
+            Some(prefix) => env
+                .range(prefix.to_string()..)
+                .take_while(|(name, _)| name.starts_with(prefix))
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect(),
+            None => {
+                let value = env.get(&var).map(String::as_str).unwrap_or_default();
+                vec![format!("{var}={value}")]
+            }
+        })
+        .collect()
+}
+
+fn read_env_dependencies(env_dependencies: Vec<String>) -> impl Iterator<Item = String> {
+    // Variables which are not valid unicode cannot be configured, so they are left out
+    let env: BTreeMap<String, String> = env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+        .collect();
+    describe_env_dependencies(env_dependencies, &env).into_iter()
+}
+
+/// Describe the rule settings which affect cached results.
+/// Rules named in `irrelevant_rules` are left out, so changing only their severity
+/// does not invalidate the cache.
+fn rules_cache_dependencies(rules: &RulesConfig, irrelevant_rules: &[String]) -> String {
+    let is_relevant = |name: &str| !irrelevant_rules.iter().any(|rule| rule == name);
+    let mut dependencies: String = rules
+        .settings()
+        .iter()
+        .filter(|(name, _)| is_relevant(name))
+        .map(|(name, setting)| format!("{name}={};", setting.as_str()))
+        .collect();
+    if let Some(pattern) = &rules.ignore_directive_reason_pattern
+        && is_relevant("require_ignore_directive_reasons")
+    {
+        dependencies.push_str(&format!("ignore_directive_reason_pattern={pattern};"));
+    }
+    dependencies
+}
+
+/// Hash everything which affects the result of `action` into a cache key.
+/// `interpreter` describes the active Python interpreter, and is `None` when cached results
+/// should not depend on it. Every file with one of the `source_extensions` is hashed.
+#[allow(clippy::too_many_arguments)]
+pub fn create_computation_cache_key(
+    project_root: &PathBuf,
+    source_roots: &[PathBuf],
+    source_extensions: &[String],
+    action: String,
+    interpreter: Option<String>,
+    file_dependencies: Vec<String>,
+    env_dependencies: Vec<String>,
+    _backend: String,
+    respect_gitignore: RespectGitIgnore,
+    rules: &RulesConfig,
+    irrelevant_rules: &[String],
+) -> String {
+    // Exclusions are not applied when building cache keys (paths are empty here)
+    let walker = filesystem::FSWalker::try_new(project_root, &[], respect_gitignore)
+        .unwrap()
+        .with_source_extensions(source_extensions);
+    let file_dependencies = read_file_dependencies(project_root, file_dependencies, &walker);
+    let source_pyfiles = source_roots.iter().flat_map(|root| {
+        walker
+            .walk_pyfiles(root.to_str().unwrap())
+            .flat_map(move |path| fs::read(root.join(path)).unwrap())
+    });
+    let env_dependencies = read_env_dependencies(env_dependencies).flat_map(|d| d.into_bytes());
+    let project_dependencies =
+        parse_project_dependencies(&project_root).flat_map(|d| d.into_bytes());
+    CacheKey::from_iter(
+        source_pyfiles
+            .chain(env_dependencies)
+            .chain(project_dependencies)
+            .chain(file_dependencies)
+            .chain(action.into_bytes())
+            .chain(interpreter.unwrap_or_default().into_bytes())
+            .chain(respect_gitignore.to_string().into_bytes())
+            .chain(rules_cache_dependencies(rules, irrelevant_rules).into_bytes()),
+    )
+    .hash
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FingerprintMode {
+    /// Hash file paths and modification times (fast, but sensitive to 'touch')
+    Mtime,
+    /// Hash file paths and contents
+    Content,
+}
+
+/// Compute a stable fingerprint of the Python files beneath the given source paths.
+/// If the fingerprint is unchanged, the source tree does not need to be re-analyzed.
+pub fn source_tree_fingerprint(
+    source_paths: &[PathBuf],
+    walker: &filesystem::FSWalker,
+    mode: FingerprintMode,
+) -> String {
+    let mut hasher = DefaultHasher::new();
+    for source_path in source_paths {
+        source_path.hash(&mut hasher);
+        let mut files: Vec<PathBuf> = walker
+            .walk_pyfiles(&source_path.display().to_string())
+            .collect();
+        files.sort();
+        for file in files {
+            file.hash(&mut hasher);
+            let absolute_path = source_path.join(&file);
+            match mode {
+                FingerprintMode::Mtime => fs::metadata(&absolute_path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                    .hash(&mut hasher),
+                FingerprintMode::Content => fs::read(&absolute_path).ok().hash(&mut hasher),
+            }
+        }
+    }
+    format!("{:016X}", hasher.finish())
+}
+
+pub fn check_computation_cache(
+    project_root: &PathBuf,
+    cache_key: String,
+    cache_config: &CacheConfig,
+) -> Result<Option<ComputationCacheValue>> {
+    match &cache_config.backend {
+        CacheBackend::Disk => {
+            DiskComputationCache::try_new(project_root, cache_config)?.get(cache_key)
+        }
+        CacheBackend::Memory => MemoryComputationCache.get(cache_key),
+        CacheBackend::Http {
+            url,
+            strict,
+            auth_header_env,
+        } => HttpCache::new(url, *strict, auth_header_env.as_deref()).get(&cache_key),
+        CacheBackend::S3 {
+            bucket,
+            prefix,
+            region,
+            endpoint,
+        } => Ok(S3Cache::new(bucket, prefix, region, endpoint.as_deref()).get(&cache_key)),
+    }
+}
+
+pub fn update_computation_cache(
+    project_root: &PathBuf,
+    cache_key: String,
+    value: ComputationCacheValue,
+    cache_config: &CacheConfig,
+) -> Result<Option<ComputationCacheValue>> {
+    match &cache_config.backend {
+        CacheBackend::Disk => {
+            DiskComputationCache::try_new(project_root, cache_config)?.set(cache_key, value)
+        }
+        CacheBackend::Memory => MemoryComputationCache.set(cache_key, value),
+        CacheBackend::Http {
+            url,
+            strict,
+            auth_header_env,
+        } => {
+            HttpCache::new(url, *strict, auth_header_env.as_deref()).set(&cache_key, &value)?;
+            Ok(None)
+        }
+        CacheBackend::S3 {
+            bucket,
+            prefix,
+            region,
+            endpoint,
+        } => {
+            S3Cache::new(bucket, prefix, region, endpoint.as_deref()).set(&cache_key, &value);
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::project::default_source_extensions;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cache_dir_shared_from_subdirectory() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let repo_root = temp_dir.path();
+        fs::create_dir_all(repo_root.join(".git")).unwrap();
+        fs::create_dir_all(repo_root.join("packages/one")).unwrap();
+
+        assert_eq!(
+            resolve_cache_dir(repo_root.join("packages/one")),
+            resolve_cache_dir(repo_root)
+        );
+        assert_eq!(resolve_cache_dir(repo_root), repo_root.join(CACHE_DIR));
+    }
+
+    #[test]
+    fn test_cache_dir_falls_back_to_project_root() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let project_root = temp_dir.path().join("project");
+        fs::create_dir_all(&project_root).unwrap();
+
+        assert_eq!(
+            resolve_cache_dir(&project_root),
+            project_root.join(CACHE_DIR)
+        );
+    }
+
+    fn fingerprint(root: &Path, mode: FingerprintMode) -> String {
+        let walker = filesystem::FSWalker::empty(root);
+        source_tree_fingerprint(&[root.to_path_buf()], &walker, mode)
+    }
+
+    #[test]
+    fn test_fingerprint_changes_when_file_added() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("a.py"), "import os\n").unwrap();
+
+        let before = fingerprint(root, FingerprintMode::Content);
+        fs::write(root.join("b.py"), "import sys\n").unwrap();
+        let after = fingerprint(root, FingerprintMode::Content);
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_mtime_in_content_mode() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let root = temp_dir.path();
+        let file_path = root.join("a.py");
+        fs::write(&file_path, "import os\n").unwrap();
+
+        let before_content = fingerprint(root, FingerprintMode::Content);
+        let before_mtime = fingerprint(root, FingerprintMode::Mtime);
+        fs::File::options()
+            .write(true)
+            .open(&file_path)
+            .unwrap()
+            .set_modified(UNIX_EPOCH + std::time::Duration::from_secs(1_000_000))
+            .unwrap();
+
+        assert_eq!(before_content, fingerprint(root, FingerprintMode::Content));
+        assert_ne!(before_mtime, fingerprint(root, FingerprintMode::Mtime));
+    }
+
+    #[test]
+    fn test_cache_key_ignores_irrelevant_rules() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::write(project_root.join("requirements.txt"), "requests\n").unwrap();
+        let irrelevant_rules = vec!["unused_ignore_directives".to_string()];
+        let cache_key = |rules: &RulesConfig| {
+            create_computation_cache_key(
+                &project_root,
+                &[],
+                &default_source_extensions(),
+                "tach-test".to_string(),
+                Some("3.12.0".to_string()),
+                vec![],
+                vec![],
+                "disk".to_string(),
+                RespectGitIgnore::False,
+                rules,
+                &irrelevant_rules,
+            )
+        };
+        let base_key = cache_key(&RulesConfig::default());
+
+        let irrelevant_change = RulesConfig {
+            unused_ignore_directives: crate::config::RuleSetting::Error,
+            ..Default::default()
+        };
+        assert_eq!(cache_key(&irrelevant_change), base_key);
+
+        let relevant_change = RulesConfig {
+            local_imports: crate::config::RuleSetting::Off,
+            ..Default::default()
+        };
+        assert_ne!(cache_key(&relevant_change), base_key);
+    }
+
+    #[test]
+    fn test_cache_key_expands_file_dependency_globs() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::write(project_root.join("requirements.txt"), "requests\n").unwrap();
+        fs::create_dir_all(project_root.join("config/nested")).unwrap();
+        fs::write(project_root.join("config/app.yaml"), "debug: false\n").unwrap();
+        fs::write(project_root.join("config/nested/db.yaml"), "host: db\n").unwrap();
+        let cache_key = |file_dependencies: &[&str]| {
+            create_computation_cache_key(
+                &project_root,
+                &[],
+                &default_source_extensions(),
+                "tach-test".to_string(),
+                Some("3.12.0".to_string()),
+                file_dependencies.iter().map(|s| s.to_string()).collect(),
+                vec![],
+                "disk".to_string(),
+                RespectGitIgnore::False,
+                &RulesConfig::default(),
+                &[],
+            )
+        };
+        let base_key = cache_key(&[]);
+        let globbed_key = cache_key(&["config/**/*.yaml"]);
+        assert_ne!(globbed_key, base_key);
+        // Patterns which match nothing do not affect the key
+        assert_eq!(cache_key(&["schemas/*.json"]), base_key);
+        // The order of the patterns does not affect the key
+        assert_eq!(
+            cache_key(&["config/nested/*.yaml", "config/*.yaml"]),
+            cache_key(&["config/*.yaml", "config/nested/*.yaml"])
+        );
+
+        fs::write(
+            project_root.join("config/nested/db.yaml"),
+            "host: replica\n",
+        )
+        .unwrap();
+        assert_ne!(cache_key(&["config/**/*.yaml"]), globbed_key);
+    }
+
+    #[test]
+    fn test_cache_key_hashes_every_source_extension() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::write(project_root.join("requirements.txt"), "requests\n").unwrap();
+        fs::create_dir(project_root.join("src")).unwrap();
+        fs::write(project_root.join("src/app.py"), "import stubs\n").unwrap();
+        fs::write(
+            project_root.join("src/stubs.pyi"),
+            "def helper() -> int: ...\n",
+        )
+        .unwrap();
+        let source_roots = [project_root.join("src")];
+        let cache_key = |source_extensions: &[String]| {
+            create_computation_cache_key(
+                &project_root,
+                &source_roots,
+                source_extensions,
+                "tach-test".to_string(),
+                None,
+                vec![],
+                vec![],
+                "disk".to_string(),
+                RespectGitIgnore::False,
+                &RulesConfig::default(),
+                &[],
+            )
+        };
+        let with_stubs = ["py".to_string(), "pyi".to_string()];
+        let py_only_key = cache_key(&default_source_extensions());
+        let stubs_key = cache_key(&with_stubs);
+
+        fs::write(
+            project_root.join("src/stubs.pyi"),
+            "def helper() -> str: ...\n",
+        )
+        .unwrap();
+        assert_eq!(cache_key(&default_source_extensions()), py_only_key);
+        assert_ne!(cache_key(&with_stubs), stubs_key);
+    }
+
+    #[test]
+    fn test_describe_env_dependencies_matches_prefixes() {
+        let env = BTreeMap::from_iter(
+            [
+                ("MYAPP_PORT", "8000"),
+                ("DEBUG", "1"),
+                ("MYAPP_HOST", "localhost"),
+                ("MYAPPLICATION", "other"),
+                ("OTHER_MYAPP_HOST", "remote"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+        assert_eq!(
+            describe_env_dependencies(
+                vec![
+                    "DEBUG".to_string(),
+                    "MYAPP_*".to_string(),
+                    "UNSET".to_string(),
+                    "NO_MATCHES_*".to_string(),
+                ],
+                &env
+            ),
+            vec![
+                "DEBUG=1",
+                "MYAPP_HOST=localhost",
+                "MYAPP_PORT=8000",
+                "UNSET=",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cache_key_depends_on_interpreter() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let project_root = temp_dir.path().to_path_buf();
+        fs::write(project_root.join("requirements.txt"), "requests\n").unwrap();
+        let cache_key = |interpreter: Option<&str>| {
+            create_computation_cache_key(
+                &project_root,
+                &[],
+                &default_source_extensions(),
+                "tach-test".to_string(),
+                interpreter.map(str::to_string),
+                vec![],
+                vec![],
+                "disk".to_string(),
+                RespectGitIgnore::False,
+                &RulesConfig::default(),
+                &[],
+            )
+        };
+        assert_ne!(cache_key(Some("3.11.9;")), cache_key(Some("3.12.0;")));
+        assert_ne!(
+            cache_key(Some("3.12.0;requests==2.31.0")),
+            cache_key(Some("3.12.0;requests==2.32.3"))
+        );
+        assert_eq!(cache_key(None), cache_key(None));
+    }
+}
@@ -1,9 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::config::ProjectConfig;
+use crate::config::{ProjectConfig, RuleSetting};
 use crate::dependencies::import::{ExternalImportWithDistributionNames, with_distribution_names};
 use crate::diagnostics::{CodeDiagnostic, Diagnostic, DiagnosticDetails};
 use crate::diagnostics::{FileChecker, Result as DiagnosticResult};
+use crate::external::parsing::{DependencySource, normalize_package_name};
 use crate::processors::file_module::FileModule;
 use crate::resolvers::PackageResolver;
 
@@ -12,15 +13,27 @@ pub struct ExternalDependencyChecker<'a> {
     module_mappings: &'a HashMap<String, Vec<String>>,
     stdlib_modules: &'a HashSet<String>,
     excluded_external_modules: &'a HashSet<String>,
+    installed_distributions: &'a HashSet<String>,
     project_config: &'a ProjectConfig,
 }
 
+/// How strictly a setting reports findings, from `off` to `error`
+fn strictness(setting: &RuleSetting) -> u8 {
+    match setting {
+        RuleSetting::Off => 0,
+        RuleSetting::Info => 1,
+        RuleSetting::Warn => 2,
+        RuleSetting::Error => 3,
+    }
+}
+
 impl<'a> ExternalDependencyChecker<'a> {
     pub fn new(
         project_config: &'a ProjectConfig,
         module_mappings: &'a HashMap<String, Vec<String>>,
         stdlib_modules: &'a HashSet<String>,
         excluded_external_modules: &'a HashSet<String>,
+        installed_distributions: &'a HashSet<String>,
         package_resolver: &'a PackageResolver<'a>,
     ) -> Self {
         Self {
@@ -28,6 +41,7 @@ impl<'a> ExternalDependencyChecker<'a> {
             module_mappings,
             stdlib_modules,
             excluded_external_modules,
+            installed_distributions,
             project_config,
         }
     }
@@ -38,6 +52,7 @@ impl<'a> ExternalDependencyChecker<'a> {
         import: &ExternalImportWithDistributionNames<'a>,
     ) -> Option<Diagnostic> {
         let module_config = processed_file.module_config();
+        let local_imports = self.project_config.local_imports_for(module_config);
         if module_config
             .cannot_depend_on_external
             .as_ref()
@@ -54,14 +69,17 @@ impl<'a> ExternalDependencyChecker<'a> {
                 });
 
             if !import.is_global_scope() {
-                if let Ok(severity) = (&self.project_config.rules.local_imports).try_into() {
-                    return Some(Diagnostic::new_located(
-                        severity,
-                        diagnostic,
-                        processed_file.relative_file_path().to_path_buf(),
-                        processed_file.line_number(import.alias_offset()),
-                        Some(processed_file.line_number(import.import_offset())),
-                    ));
+                if let Ok(severity) = local_imports.try_into() {
+                    return Some(
+                        Diagnostic::new_located(
+                            severity,
+                            diagnostic,
+                            processed_file.relative_file_path().to_path_buf(),
+                            processed_file.line_number(import.alias_offset()),
+                            Some(processed_file.line_number(import.import_offset())),
+                        )
+                        .with_local_import(),
+                    );
                 }
                 return None;
             }
@@ -88,14 +106,17 @@ impl<'a> ExternalDependencyChecker<'a> {
                 });
 
             if !import.is_global_scope() {
-                if let Ok(severity) = (&self.project_config.rules.local_imports).try_into() {
-                    return Some(Diagnostic::new_located(
-                        severity,
-                        diagnostic,
-                        processed_file.relative_file_path().to_path_buf(),
-                        processed_file.line_number(import.alias_offset()),
-                        Some(processed_file.line_number(import.import_offset())),
-                    ));
+                if let Ok(severity) = local_imports.try_into() {
+                    return Some(
+                        Diagnostic::new_located(
+                            severity,
+                            diagnostic,
+                            processed_file.relative_file_path().to_path_buf(),
+                            processed_file.line_number(import.alias_offset()),
+                            Some(processed_file.line_number(import.import_offset())),
+                        )
+                        .with_local_import(),
+                    );
                 }
                 return None;
             }
@@ -111,6 +132,67 @@ impl<'a> ExternalDependencyChecker<'a> {
         }
     }
 
+    fn is_dependency_available(&self, processed_file: &FileModule<'a>, dist_name: &str) -> bool {
+        processed_file.is_dependency_available(dist_name)
+            || (self.project_config.external.allow_transitive_imports
+                && processed_file
+                    .package
+                    .locked_dependencies
+                    .contains(dist_name))
+            || self.installed_distributions.contains(
+                &self
+                    .project_config
+                    .external
+                    .normalize_package_name(dist_name),
+            )
+    }
+
+    /// When an import is only satisfied by dependency groups listed in `missing_group_severities`,
+    /// the least strict of their settings. Imports satisfied any other way (by the main
+    /// dependency list, an extra, an unlisted group, or another known source) return `None`.
+    fn missing_group_setting(
+        &self,
+        processed_file: &FileModule<'a>,
+        distribution_names: &[String],
+    ) -> Option<&'a RuleSetting> {
+        let group_severities = &self.project_config.external.missing_group_severities;
+        if group_severities.is_empty() {
+            return None;
+        }
+
+        let mut settings = Vec::new();
+        for dist_name in distribution_names {
+            match processed_file
+                .package
+                .dependency_sources
+                .get(
+                    &self
+                        .project_config
+                        .external
+                        .normalize_package_name(dist_name),
+                )
+                .filter(|sources| !sources.is_empty())
+            {
+                Some(sources) => {
+                    for source in sources {
+                        let DependencySource::Group(group) = source else {
+                            return None;
+                        };
+                        settings.push(group_severities.iter().find_map(|(name, setting)| {
+                            (normalize_package_name(name) == *group).then_some(setting)
+                        })?);
+                    }
+                }
+                None if self.is_dependency_available(processed_file, dist_name) => return None,
+                None => {}
+            }
+        }
+
+        settings
+            .into_iter()
+            .min_by_key(|setting| strictness(setting))
+    }
+
     fn check_import(
         &'a self,
         import: ExternalImportWithDistributionNames<'a>,
@@ -130,12 +212,23 @@ impl<'a> ExternalDependencyChecker<'a> {
         let is_declared = import
             .distribution_names
             .iter()
-            .any(|dist_name| processed_file.declared_dependencies().contains(dist_name));
+            .any(|dist_name| self.is_dependency_available(processed_file, dist_name));
+        let group_setting = if is_declared {
+            self.missing_group_setting(processed_file, &import.distribution_names)
+        } else {
+            None
+        };
 
-        if !is_declared {
+        if !is_declared || group_setting.is_some() {
+            let suggestion = import.distribution_names.iter().find_map(|dist_name| {
+                self.project_config
+                    .external
+                    .suggest_dependency(dist_name, processed_file.declared_dependencies())
+            });
             let diagnostic =
                 DiagnosticDetails::Code(CodeDiagnostic::UndeclaredExternalDependency {
                     dependency: import.top_level_module_name().to_string(),
+                    suggestion: suggestion.map(|suggestion| suggestion.to_string()),
                     package_name: processed_file
                         .package
                         .name
@@ -145,15 +238,35 @@ impl<'a> ExternalDependencyChecker<'a> {
                         }),
                 });
 
-            if !import.is_global_scope() {
-                if let Ok(severity) = (&self.project_config.rules.local_imports).try_into() {
-                    return Some(Diagnostic::new_located(
+            let local_imports = self
+                .project_config
+                .local_imports_for(processed_file.module_config());
+            // A package declared by a group is governed by the group's setting, and
+            // `local_imports` may only relax it. Otherwise it decides for local imports alone.
+            let setting = match group_setting {
+                Some(group_setting) if !import.is_global_scope() => Some(std::cmp::min_by_key(
+                    group_setting,
+                    local_imports,
+                    |setting| strictness(setting),
+                )),
+                Some(group_setting) => Some(group_setting),
+                None if !import.is_global_scope() => Some(local_imports),
+                None => None,
+            };
+            if let Some(setting) = setting {
+                if let Ok(severity) = setting.try_into() {
+                    let diagnostic = Diagnostic::new_located(
                         severity,
                         diagnostic,
                         processed_file.relative_file_path().to_path_buf(),
                         processed_file.line_number(import.alias_offset()),
                         Some(processed_file.line_number(import.import_offset())),
-                    ));
+                    );
+                    return Some(if import.is_global_scope() {
+                        diagnostic
+                    } else {
+                        diagnostic.with_local_import()
+                    });
                 }
                 return None;
             }
@@ -1,17 +1,24 @@
 use std::collections::HashSet;
 use std::path::Path;
 
-use crate::config::{ProjectConfig, RuleSetting};
+use regex::Regex;
+
+use crate::config::error::ConfigError;
+use crate::config::{ModuleConfig, ProjectConfig, RuleSetting, RulesConfig};
 use crate::diagnostics::{CodeDiagnostic, Diagnostic, DiagnosticDetails, Severity};
 use crate::processors::ignore_directive::{IgnoreDirective, IgnoreDirectives};
 
 pub struct IgnoreDirectivePostProcessor<'a> {
     project_config: &'a ProjectConfig,
+    reason_pattern: Option<Regex>,
 }
 
 impl<'a> IgnoreDirectivePostProcessor<'a> {
-    pub fn new(project_config: &'a ProjectConfig) -> Self {
-        Self { project_config }
+    pub fn try_new(project_config: &'a ProjectConfig) -> Result<Self, ConfigError> {
+        Ok(Self {
+            project_config,
+            reason_pattern: project_config.rules.ignore_directive_reason_regex()?,
+        })
     }
 
     fn get_unused_ignore_directive_diagnostic(
@@ -67,24 +74,34 @@ impl<'a> IgnoreDirectivePostProcessor<'a> {
         &self,
         ignore_directive: &IgnoreDirective,
         relative_file_path: &Path,
+        rules: &RulesConfig,
     ) -> Option<Diagnostic> {
-        if self.project_config.rules.require_ignore_directive_reasons == RuleSetting::Off {
+        if rules.require_ignore_directive_reasons == RuleSetting::Off {
             return None;
         }
 
-        if ignore_directive.reason.is_empty() {
-            Some(Diagnostic::new_located(
-                (&self.project_config.rules.require_ignore_directive_reasons)
-                    .try_into()
-                    .unwrap(),
-                DiagnosticDetails::Code(CodeDiagnostic::MissingIgnoreDirectiveReason()),
-                relative_file_path.to_path_buf(),
-                ignore_directive.line_no,
-                None,
-            ))
+        let details = if ignore_directive.reason.is_empty() {
+            CodeDiagnostic::MissingIgnoreDirectiveReason()
         } else {
-            None
-        }
+            match &self.reason_pattern {
+                Some(pattern) if !pattern.is_match(&ignore_directive.reason) => {
+                    CodeDiagnostic::IgnoreDirectiveReasonMismatch {
+                        reason: ignore_directive.reason.clone(),
+                        pattern: pattern.as_str().to_string(),
+                    }
+                }
+                _ => return None,
+            }
+        };
+        Some(Diagnostic::new_located(
+            (&rules.require_ignore_directive_reasons)
+                .try_into()
+                .unwrap(),
+            DiagnosticDetails::Code(details),
+            relative_file_path.to_path_buf(),
+            ignore_directive.line_no,
+            None,
+        ))
     }
 
     fn handle_ignore_directive(
@@ -92,15 +109,16 @@ impl<'a> IgnoreDirectivePostProcessor<'a> {
         ignore_directive: &IgnoreDirective,
         diagnostics: &mut Vec<Diagnostic>,
         relative_file_path: &Path,
+        rules: &RulesConfig,
         matched_diagnostic_indices: &mut HashSet<usize>,
     ) {
         if let Some(diagnostic) =
-            self.check_missing_ignore_directive_reason(ignore_directive, relative_file_path)
+            self.check_missing_ignore_directive_reason(ignore_directive, relative_file_path, rules)
         {
             diagnostics.push(diagnostic)
         }
 
-        if let Ok(severity) = (&self.project_config.rules.unused_ignore_directives).try_into() {
+        if let Ok(severity) = (&rules.unused_ignore_directives).try_into() {
             if let Some(diagnostic) = self.check_unused_ignore_directive(
                 ignore_directive,
                 diagnostics,
@@ -126,12 +144,16 @@ impl<'a> IgnoreDirectivePostProcessor<'a> {
         });
     }
 
+    /// Remove the diagnostics suppressed by ignore directives, and report directives which break the
+    /// ignore directive rules. Rules are resolved for `module_config`, the module holding the file.
     pub fn process_diagnostics(
         &self,
         ignore_directives: &IgnoreDirectives,
         diagnostics: &mut Vec<Diagnostic>,
         relative_file_path: &Path,
+        module_config: &ModuleConfig,
     ) {
+        let rules = self.project_config.rules_for_module(module_config);
         let mut matched_diagnostic_indices: HashSet<usize> = HashSet::new();
         // Using sorted directives, we can greedily match diagnostics to ignore directives
         // to canonically determine which diagnostics are unused
@@ -140,13 +162,14 @@ impl<'a> IgnoreDirectivePostProcessor<'a> {
                 ignore_directive,
                 diagnostics,
                 relative_file_path,
+                &rules,
                 &mut matched_diagnostic_indices,
             );
         }
 
         self.remove_ignored_diagnostics(diagnostics, &matched_diagnostic_indices);
 
-        if let Ok(severity) = (&self.project_config.rules.unused_ignore_directives).try_into() {
+        if let Ok(severity) = (&rules.unused_ignore_directives).try_into() {
             for ignore_directive in ignore_directives.redundant_directives() {
                 diagnostics.push(self.get_unused_ignore_directive_diagnostic(
                     ignore_directive,
@@ -120,6 +120,9 @@ impl<'a> InterfaceChecker<'a> {
                 &dependency_module_config.path,
                 &file_module.module_config().path,
             );
+            let local_imports = self
+                .project_config
+                .local_imports_for(file_module.module_config());
             match check_result {
                 InterfaceCheckResult::NotExposed => {
                     let diagnostic = DiagnosticDetails::Code(CodeDiagnostic::PrivateDependency {
@@ -130,18 +133,19 @@ impl<'a> InterfaceChecker<'a> {
 
                     if let Dependency::Import(import) = dependency {
                         if !import.is_global_scope {
-                            if let Ok(severity) =
-                                (&self.project_config.rules.local_imports).try_into()
-                            {
-                                return Ok(vec![Diagnostic::new_located(
-                                    severity,
-                                    diagnostic,
-                                    file_module.relative_file_path().to_path_buf(),
-                                    file_module.line_number(dependency.offset()),
-                                    dependency
-                                        .original_line_offset()
-                                        .map(|offset| file_module.line_number(offset)),
-                                )]);
+                            if let Ok(severity) = local_imports.try_into() {
+                                return Ok(vec![
+                                    Diagnostic::new_located(
+                                        severity,
+                                        diagnostic,
+                                        file_module.relative_file_path().to_path_buf(),
+                                        file_module.line_number(dependency.offset()),
+                                        dependency
+                                            .original_line_offset()
+                                            .map(|offset| file_module.line_number(offset)),
+                                    )
+                                    .with_local_import(),
+                                ]);
                             }
                             return Ok(vec![]);
                         }
@@ -169,18 +173,19 @@ impl<'a> InterfaceChecker<'a> {
 
                     if let Dependency::Import(import) = dependency {
                         if !import.is_global_scope {
-                            if let Ok(severity) =
-                                (&self.project_config.rules.local_imports).try_into()
-                            {
-                                return Ok(vec![Diagnostic::new_located(
-                                    severity,
-                                    diagnostic,
-                                    file_module.relative_file_path().to_path_buf(),
-                                    file_module.line_number(dependency.offset()),
-                                    dependency
-                                        .original_line_offset()
-                                        .map(|offset| file_module.line_number(offset)),
-                                )]);
+                            if let Ok(severity) = local_imports.try_into() {
+                                return Ok(vec![
+                                    Diagnostic::new_located(
+                                        severity,
+                                        diagnostic,
+                                        file_module.relative_file_path().to_path_buf(),
+                                        file_module.line_number(dependency.offset()),
+                                        dependency
+                                            .original_line_offset()
+                                            .map(|offset| file_module.line_number(offset)),
+                                    )
+                                    .with_local_import(),
+                                ]);
                             }
                             return Ok(vec![]);
                         }
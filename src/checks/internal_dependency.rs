@@ -6,7 +6,7 @@ use crate::{
     dependencies::Dependency,
     diagnostics::{
         CodeDiagnostic, ConfigurationDiagnostic, Diagnostic, DiagnosticDetails, FileChecker,
-        Result as DiagnosticResult,
+        Result as DiagnosticResult, Severity,
     },
     modules::ModuleTree,
     processors::FileModule,
@@ -111,6 +111,7 @@ impl<'a> InternalDependencyChecker<'a> {
         layers: &[LayerConfig],
     ) -> DiagnosticResult<Vec<Diagnostic>> {
         let file_module_config = file_module.module_config();
+        let local_imports = self.project_config.local_imports_for(file_module_config);
         if dependency_module_config == file_module_config {
             return Ok(vec![]);
         }
@@ -144,17 +145,19 @@ impl<'a> InternalDependencyChecker<'a> {
 
                 if let Dependency::Import(import) = dependency {
                     if !import.is_global_scope {
-                        if let Ok(severity) = (&self.project_config.rules.local_imports).try_into()
-                        {
-                            return Ok(vec![Diagnostic::new_located(
-                                severity,
-                                details,
-                                relative_file_path.to_path_buf(),
-                                file_module.line_number(dependency.offset()),
-                                dependency
-                                    .original_line_offset()
-                                    .map(|offset| file_module.line_number(offset)),
-                            )]);
+                        if let Ok(severity) = local_imports.try_into() {
+                            return Ok(vec![
+                                Diagnostic::new_located(
+                                    severity,
+                                    details,
+                                    relative_file_path.to_path_buf(),
+                                    file_module.line_number(dependency.offset()),
+                                    dependency
+                                        .original_line_offset()
+                                        .map(|offset| file_module.line_number(offset)),
+                                )
+                                .with_local_import(),
+                            ]);
                         }
                         return Ok(vec![]);
                     }
@@ -187,17 +190,19 @@ impl<'a> InternalDependencyChecker<'a> {
 
                 if let Dependency::Import(import) = dependency {
                     if !import.is_global_scope {
-                        if let Ok(severity) = (&self.project_config.rules.local_imports).try_into()
-                        {
-                            return Ok(vec![Diagnostic::new_located(
-                                severity,
-                                details,
-                                relative_file_path.to_path_buf(),
-                                file_module.line_number(dependency.offset()),
-                                dependency
-                                    .original_line_offset()
-                                    .map(|offset| file_module.line_number(offset)),
-                            )]);
+                        if let Ok(severity) = local_imports.try_into() {
+                            return Ok(vec![
+                                Diagnostic::new_located(
+                                    severity,
+                                    details,
+                                    relative_file_path.to_path_buf(),
+                                    file_module.line_number(dependency.offset()),
+                                    dependency
+                                        .original_line_offset()
+                                        .map(|offset| file_module.line_number(offset)),
+                                )
+                                .with_local_import(),
+                            ]);
                         }
                         return Ok(vec![]);
                     }
@@ -220,9 +225,10 @@ impl<'a> InternalDependencyChecker<'a> {
 
                 if let Dependency::Import(import) = dependency {
                     if !import.is_global_scope {
-                        if let Ok(severity) = (&self.project_config.rules.local_imports).try_into()
-                        {
-                            return Ok(vec![Diagnostic::new_global(severity, details)]);
+                        if let Ok(severity) = local_imports.try_into() {
+                            return Ok(vec![
+                                Diagnostic::new_global(severity, details).with_local_import(),
+                            ]);
                         }
                         return Ok(vec![]);
                     }
@@ -248,16 +254,19 @@ impl<'a> InternalDependencyChecker<'a> {
 
             if let Dependency::Import(import) = dependency {
                 if !import.is_global_scope {
-                    if let Ok(severity) = (&self.project_config.rules.local_imports).try_into() {
-                        return Ok(vec![Diagnostic::new_located(
-                            severity,
-                            diagnostic,
-                            relative_file_path.to_path_buf(),
-                            file_module.line_number(dependency.offset()),
-                            dependency
-                                .original_line_offset()
-                                .map(|offset| file_module.line_number(offset)),
-                        )]);
+                    if let Ok(severity) = local_imports.try_into() {
+                        return Ok(vec![
+                            Diagnostic::new_located(
+                                severity,
+                                diagnostic,
+                                relative_file_path.to_path_buf(),
+                                file_module.line_number(dependency.offset()),
+                                dependency
+                                    .original_line_offset()
+                                    .map(|offset| file_module.line_number(offset)),
+                            )
+                            .with_local_import(),
+                        ]);
                     }
                     return Ok(vec![]);
                 }
@@ -296,19 +305,23 @@ impl<'a> InternalDependencyChecker<'a> {
 
                 if let Dependency::Import(import) = dependency {
                     if !import.is_global_scope {
-                        match &self.project_config.rules.local_imports {
-                            RuleSetting::Warn | RuleSetting::Error => {
-                                return Ok(vec![Diagnostic::new_located_warning(
-                                    relative_file_path.to_path_buf(),
-                                    file_module.line_number(dependency.offset()),
-                                    dependency
-                                        .original_line_offset()
-                                        .map(|offset| file_module.line_number(offset)),
-                                    diagnostic,
-                                )]);
-                            }
-                            _ => return Ok(vec![]),
-                        }
+                        let severity = match local_imports {
+                            RuleSetting::Warn | RuleSetting::Error => Severity::Warning,
+                            RuleSetting::Info => Severity::Info,
+                            RuleSetting::Off => return Ok(vec![]),
+                        };
+                        return Ok(vec![
+                            Diagnostic::new_located(
+                                severity,
+                                diagnostic,
+                                relative_file_path.to_path_buf(),
+                                file_module.line_number(dependency.offset()),
+                                dependency
+                                    .original_line_offset()
+                                    .map(|offset| file_module.line_number(offset)),
+                            )
+                            .with_local_import(),
+                        ]);
                     }
                 }
 
@@ -331,17 +344,19 @@ impl<'a> InternalDependencyChecker<'a> {
 
                 if let Dependency::Import(import) = dependency {
                     if !import.is_global_scope {
-                        if let Ok(severity) = (&self.project_config.rules.local_imports).try_into()
-                        {
-                            return Ok(vec![Diagnostic::new_located(
-                                severity,
-                                diagnostic,
-                                relative_file_path.to_path_buf(),
-                                file_module.line_number(dependency.offset()),
-                                dependency
-                                    .original_line_offset()
-                                    .map(|offset| file_module.line_number(offset)),
-                            )]);
+                        if let Ok(severity) = local_imports.try_into() {
+                            return Ok(vec![
+                                Diagnostic::new_located(
+                                    severity,
+                                    diagnostic,
+                                    relative_file_path.to_path_buf(),
+                                    file_module.line_number(dependency.offset()),
+                                    dependency
+                                        .original_line_offset()
+                                        .map(|offset| file_module.line_number(offset)),
+                                )
+                                .with_local_import(),
+                            ]);
                         }
                         return Ok(vec![]);
                     }
@@ -359,6 +374,37 @@ impl<'a> InternalDependencyChecker<'a> {
         }
     }
 
+    /// Report a dependency on a deprecated module. This is independent of whether the dependency is allowed.
+    fn check_deprecated_module_usage(
+        &self,
+        file_module: &FileModule,
+        dependency: &Dependency,
+        dependency_module_config: &ModuleConfig,
+    ) -> Option<Diagnostic> {
+        let file_module_config = file_module.module_config();
+        if !dependency_module_config.deprecated || dependency_module_config == file_module_config {
+            return None;
+        }
+        let severity = self
+            .project_config
+            .deprecated_module_usage_for(file_module_config)
+            .try_into()
+            .ok()?;
+        Some(Diagnostic::new_located(
+            severity,
+            DiagnosticDetails::Code(CodeDiagnostic::DeprecatedModuleUsage {
+                dependency: dependency.module_path().to_string(),
+                usage_module: file_module_config.path.to_string(),
+                definition_module: dependency_module_config.path.to_string(),
+            }),
+            file_module.relative_file_path().to_path_buf(),
+            file_module.line_number(dependency.offset()),
+            dependency
+                .original_line_offset()
+                .map(|offset| file_module.line_number(offset)),
+        ))
+    }
+
     fn check_dependency(
         &self,
         dependency: &Dependency,
@@ -376,12 +422,18 @@ impl<'a> InternalDependencyChecker<'a> {
                 return Ok(vec![]);
             }
 
-            self.check_dependency_rules(
+            let mut diagnostics = self.check_dependency_rules(
                 file_module,
                 dependency,
                 dependency_module_config,
                 &self.project_config.layers,
-            )
+            )?;
+            diagnostics.extend(self.check_deprecated_module_usage(
+                file_module,
+                dependency,
+                dependency_module_config,
+            ));
+            Ok(diagnostics)
         } else {
             Ok(vec![Diagnostic::new_global_error(
                 DiagnosticDetails::Configuration(ConfigurationDiagnostic::ModuleConfigNotFound {
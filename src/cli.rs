@@ -21,6 +21,7 @@ pub struct EmojiIcons;
 impl EmojiIcons {
     pub const SUCCESS: &str = "✅";
     pub const WARNING: &str = "⚠️ ";
+    pub const INFO: &str = "ℹ️ ";
     pub const FAIL: &str = "❌";
 }
 
@@ -29,6 +30,7 @@ pub struct SimpleIcons;
 impl SimpleIcons {
     pub const SUCCESS: &str = "[OK]";
     pub const WARNING: &str = "[WARN]";
+    pub const INFO: &str = "[INFO]";
     pub const FAIL: &str = "[FAIL]";
 }
 
@@ -48,6 +50,14 @@ pub fn warning() -> &'static str {
     }
 }
 
+pub fn info() -> &'static str {
+    if supports_emoji() {
+        EmojiIcons::INFO
+    } else {
+        SimpleIcons::INFO
+    }
+}
+
 pub fn fail() -> &'static str {
     if supports_emoji() {
         EmojiIcons::FAIL
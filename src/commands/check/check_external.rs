@@ -1,20 +1,20 @@
 use crate::checks::{ExternalDependencyChecker, IgnoreDirectivePostProcessor};
 use crate::commands::check;
-use crate::config::ProjectConfig;
+use crate::config::{ProjectConfig, RenameOrigin};
 use crate::dependencies::import::with_distribution_names;
 use crate::diagnostics::{
     CodeDiagnostic, ConfigurationDiagnostic, Diagnostic, DiagnosticDetails, DiagnosticError,
-    DiagnosticPipeline, FileChecker, FileProcessor, Result as DiagnosticResult,
+    DiagnosticPipeline, FileChecker, FileProcessor, Result as DiagnosticResult, Severity,
 };
 use crate::filesystem::{self, ProjectFile};
 use crate::interrupt::check_interrupt;
 use crate::modules::{ModuleTree, ModuleTreeBuilder};
 use crate::processors::ExternalDependencyExtractor;
 use crate::processors::file_module::FileModule;
-use crate::resolvers::{PackageResolver, SourceRootResolver};
+use crate::resolvers::{PackageResolution, PackageResolver, SourceRootResolver};
 use pyo3::prelude::*;
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use dashmap::{DashMap, DashSet};
 use rayon::prelude::*;
@@ -34,16 +34,17 @@ struct CheckExternalPipeline<'a> {
 }
 
 impl<'a> CheckExternalPipeline<'a> {
-    pub fn new(
+    pub fn try_new(
         source_roots: &'a [PathBuf],
         project_config: &'a ProjectConfig,
         module_tree: &'a ModuleTree,
         module_mappings: &'a HashMap<String, Vec<String>>,
         stdlib_modules: &'a HashSet<String>,
         excluded_external_modules: &'a HashSet<String>,
+        installed_distributions: &'a HashSet<String>,
         package_resolver: &'a PackageResolver,
-    ) -> Self {
-        Self {
+    ) -> Result<Self> {
+        Ok(Self {
             module_mappings,
             excluded_external_modules,
             seen_dependencies: DashMap::new(),
@@ -59,10 +60,12 @@ impl<'a> CheckExternalPipeline<'a> {
                 module_mappings,
                 stdlib_modules,
                 excluded_external_modules,
+                installed_distributions,
                 package_resolver,
             ),
-            ignore_directive_post_processor: IgnoreDirectivePostProcessor::new(project_config),
-        }
+            ignore_directive_post_processor: IgnoreDirectivePostProcessor::try_new(project_config)
+                .map_err(|err| CheckError::Configuration(err.to_string()))?,
+        })
     }
 }
 
@@ -119,6 +122,7 @@ impl<'a> FileChecker<'a> for CheckExternalPipeline<'a> {
             &processed_file.ignore_directives,
             &mut diagnostics,
             processed_file.relative_file_path(),
+            processed_file.module_config(),
         );
 
         Ok(diagnostics)
@@ -128,10 +132,14 @@ impl<'a> FileChecker<'a> for CheckExternalPipeline<'a> {
 struct CheckExternalMetadata {
     module_mappings: HashMap<String, Vec<String>>,
     stdlib_modules: Vec<String>,
+    installed_distributions: HashSet<String>,
 }
 
 /// Get metadata for checking external dependencies.
-fn get_check_external_metadata(project_config: &ProjectConfig) -> Result<CheckExternalMetadata> {
+fn get_check_external_metadata(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+) -> Result<CheckExternalMetadata> {
     Python::attach(|py| {
         let external_utils = PyModule::import(py, "tach.utils.external")
             .expect("Failed to import tach.utils.external");
@@ -149,33 +157,109 @@ fn get_check_external_metadata(project_config: &ProjectConfig) -> Result<CheckEx
             .expect("Failed to call get_stdlib_modules")
             .extract()
             .expect("Failed to extract stdlib_modules");
+        let installed_distributions: HashSet<String> =
+            if project_config.external.use_installed_distributions
+                || project_config.external.validate_installed_entries
+            {
+                let distributions: Vec<String> = external_utils
+                    .getattr("get_installed_distributions")
+                    .expect("Failed to get installed_distributions")
+                    .call0()
+                    .expect("Failed to call get_installed_distributions")
+                    .extract()
+                    .expect("Failed to extract installed_distributions");
+                distributions
+                    .iter()
+                    .map(|dist_name| project_config.external.normalize_package_name(dist_name))
+                    .collect()
+            } else {
+                HashSet::new()
+            };
 
-        if !project_config.external.rename.is_empty() {
-            for rename_pair in project_config.external.rename.iter() {
-                if let Some((module, name)) = rename_pair.split_once(':') {
-                    module_mappings.insert(module.to_string(), vec![name.to_string()]);
-                } else {
-                    return Err(check::error::CheckError::Configuration(
-                        "Invalid rename format: expected format is a list of 'module:name' pairs, e.g. ['PIL:pillow']".to_string()
-                    ));
-                }
-            }
+        let renames = project_config
+            .external
+            .rename_map(project_root)
+            .map_err(|err| check::error::CheckError::Configuration(err.to_string()))?;
+        for (name, module) in renames {
+            module_mappings.insert(module, vec![name]);
         }
 
         Ok(CheckExternalMetadata {
             module_mappings,
             stdlib_modules,
+            installed_distributions,
         })
     })
 }
 
+/// Find renames which neither name a dependency declared by any package,
+/// nor refer to a module which can be found within the source roots.
+/// `renames` are the effective renames, as returned by `rename_map_with_origins`,
+/// and dangling ones are returned as sorted 'module:name' pairs.
+fn find_dangling_renames(
+    project_config: &ProjectConfig,
+    renames: &HashMap<String, (RenameOrigin, String)>,
+    package_resolver: &PackageResolver,
+) -> Vec<String> {
+    let mut dangling: Vec<String> = renames
+        .iter()
+        .filter(|(name, (_, module))| {
+            let is_declared = package_resolver
+                .packages()
+                .any(|package| package.dependencies.contains(*name));
+            let is_source_module = project_config.external.is_first_party(module)
+                || package_resolver
+                    .packages()
+                    .any(|package| package.is_editable_module(module))
+                || matches!(
+                    package_resolver.resolve_module_path(module),
+                    PackageResolution::Found { .. }
+                );
+            !is_declared && !is_source_module
+        })
+        .map(|(name, (_, module))| format!("{module}:{name}"))
+        .collect();
+    dangling.sort();
+    dangling
+}
+
+/// Find renames and `exclude` entries whose distribution is neither declared by any
+/// package nor installed, as (field, entry) pairs. Renames are taken from the effective
+/// `renames`, and tagged with the field they were configured by.
+fn find_unmatched_external_entries(
+    project_config: &ProjectConfig,
+    renames: &HashMap<String, (RenameOrigin, String)>,
+    declared_dependencies: &HashSet<&String>,
+    installed_distributions: &HashSet<String>,
+) -> Vec<(String, String)> {
+    let is_known = |name: &String| {
+        declared_dependencies.contains(name) || installed_distributions.contains(name)
+    };
+    let mut unmatched: Vec<(String, String)> = renames
+        .iter()
+        .filter(|(name, _)| !is_known(name))
+        .map(|(name, (origin, module))| (origin.to_string(), format!("{module}:{name}")))
+        .collect();
+    unmatched.sort();
+    unmatched.extend(
+        project_config
+            .external
+            .exclude
+            .iter()
+            .filter(|excluded| !is_known(&project_config.external.normalize_package_name(excluded)))
+            .map(|excluded| ("exclude".to_string(), excluded.clone())),
+    );
+    unmatched
+}
+
 pub fn check(project_root: &PathBuf, project_config: &ProjectConfig) -> Result<Vec<Diagnostic>> {
-    let metadata = get_check_external_metadata(project_config)?;
+    let metadata = get_check_external_metadata(project_root, project_config)?;
     check_with_modules(
         project_root,
         project_config,
         &metadata.module_mappings,
         &metadata.stdlib_modules,
+        &metadata.installed_distributions,
     )
 }
 
@@ -184,6 +268,7 @@ fn check_with_modules(
     project_config: &ProjectConfig,
     module_mappings: &HashMap<String, Vec<String>>,
     stdlib_modules: &[String],
+    installed_distributions: &HashSet<String>,
 ) -> Result<Vec<Diagnostic>> {
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
     let stdlib_modules: HashSet<String> = stdlib_modules.iter().cloned().collect();
@@ -193,14 +278,76 @@ fn check_with_modules(
         project_root,
         &project_config.exclude,
         project_config.respect_gitignore,
-    )?;
+    )?
+    .with_source_extensions(&project_config.source_extensions);
     let source_root_resolver = SourceRootResolver::new(project_root, &file_walker);
     let source_roots: Vec<PathBuf> = source_root_resolver.resolve(&project_config.source_roots)?;
-    let package_resolver = PackageResolver::try_new(project_root, &source_roots, &file_walker)?;
+    let package_resolver = PackageResolver::try_new(
+        project_root,
+        &source_roots,
+        &file_walker,
+        &project_config.external,
+    )?;
+
+    for package in package_resolver.packages() {
+        diagnostics.extend(package.warnings.iter().map(|warning| {
+            Diagnostic::new_global_warning(DiagnosticDetails::Configuration(
+                ConfigurationDiagnostic::DependencyParsingWarning {
+                    kind: warning.kind().to_string(),
+                    message: warning.to_string(),
+                },
+            ))
+        }));
+    }
+
+    let renames = if project_config.external.validate_installed_entries
+        || project_config.external.validate_renames
+    {
+        project_config
+            .external
+            .rename_map_with_origins(project_root)
+            .map_err(|err| CheckError::Configuration(err.to_string()))?
+    } else {
+        HashMap::new()
+    };
+
+    if project_config.external.validate_installed_entries {
+        let declared_dependencies: HashSet<&String> = package_resolver
+            .packages()
+            .flat_map(|package| package.dependencies.iter())
+            .collect();
+        diagnostics.extend(
+            find_unmatched_external_entries(
+                project_config,
+                &renames,
+                &declared_dependencies,
+                installed_distributions,
+            )
+            .into_iter()
+            .map(|(field, entry)| {
+                Diagnostic::new_global_warning(DiagnosticDetails::Configuration(
+                    ConfigurationDiagnostic::UnmatchedExternalEntry { field, entry },
+                ))
+            }),
+        );
+    }
+
+    if project_config.external.validate_renames {
+        diagnostics.extend(
+            find_dangling_renames(project_config, &renames, &package_resolver)
+                .into_iter()
+                .map(|rename| {
+                    Diagnostic::new_global_warning(DiagnosticDetails::Configuration(
+                        ConfigurationDiagnostic::DanglingRename { rename },
+                    ))
+                }),
+        );
+    }
+
     let module_tree_builder = ModuleTreeBuilder::new(
         &source_roots,
         &file_walker,
-        project_config.forbid_circular_dependencies,
+        project_config.circular_dependencies(),
         project_config.root_module,
     );
 
@@ -218,15 +365,22 @@ fn check_with_modules(
     check_interrupt().map_err(|_| CheckError::Interrupt)?;
     let module_tree = module_tree_builder.build(valid_modules)?;
 
-    let pipeline = CheckExternalPipeline::new(
+    // Installed distributions may have been queried only to validate configuration
+    let available_installed_distributions = if project_config.external.use_installed_distributions {
+        installed_distributions.clone()
+    } else {
+        HashSet::new()
+    };
+    let pipeline = CheckExternalPipeline::try_new(
         &source_roots,
         project_config,
         &module_tree,
         module_mappings,
         &stdlib_modules,
         &excluded_external_modules,
+        &available_installed_distributions,
         &package_resolver,
-    );
+    )?;
 
     diagnostics.par_extend(source_roots.par_iter().flat_map(|source_root| {
         file_walker
@@ -293,28 +447,60 @@ fn check_with_modules(
                 None => continue, // Skip packages we can't resolve dependencies for
             };
 
-            let unused_dependency_diagnostics = package
+            let mut unused_dependencies: BTreeSet<&str> = package
                 .dependencies
                 .difference(&seen_dependencies)
+                .map(String::as_str)
                 .filter(|&dep| !pipeline.excluded_external_modules.contains(dep)) // 'exclude' should hide unused errors unconditionally
-                .map(|dep| {
-                    Diagnostic::new_global(
-                        (&project_config.rules.unused_external_dependencies)
-                            .try_into()
-                            .unwrap(),
-                        DiagnosticDetails::Code(CodeDiagnostic::UnusedExternalDependency {
-                            package_module_name: dep.clone(),
-                            package_name: package
-                                .name
-                                .as_ref()
-                                .map_or(package_root.display().to_string(), |name| {
-                                    name.to_string()
-                                }),
-                        }),
+                .filter(|&dep| {
+                    !package.is_dependency_only_from_groups(
+                        dep,
+                        &project_config.external.unused_check_exempt_groups,
                     )
-                });
+                })
+                .collect();
+            let severity: Severity = (&project_config.rules.unused_external_dependencies)
+                .try_into()
+                .unwrap();
+            let package_name = package
+                .name
+                .as_ref()
+                .map_or(package_root.display().to_string(), |name| name.to_string());
+            let normalize = |dep: &str| project_config.external.name_normalization.normalize(dep);
+
+            if project_config.external.collapse_unused_groups {
+                // Report each group whose members are all unused as a single finding
+                let unused_groups: Vec<_> = package
+                    .dependency_groups()
+                    .into_iter()
+                    .filter(|(_, members)| {
+                        members
+                            .iter()
+                            .all(|member| unused_dependencies.contains(member))
+                    })
+                    .collect();
+                for (group, members) in unused_groups {
+                    unused_dependencies.retain(|dep| !members.contains(dep));
+                    diagnostics.push(Diagnostic::new_global(
+                        severity,
+                        DiagnosticDetails::Code(CodeDiagnostic::UnusedDependencyGroup {
+                            group: group.to_string(),
+                            members: members.into_iter().map(normalize).collect(),
+                            package_name: package_name.clone(),
+                        }),
+                    ));
+                }
+            }
 
-            diagnostics.extend(unused_dependency_diagnostics);
+            diagnostics.extend(unused_dependencies.into_iter().map(|dep| {
+                Diagnostic::new_global(
+                    severity,
+                    DiagnosticDetails::Code(CodeDiagnostic::UnusedExternalDependency {
+                        package_module_name: normalize(dep),
+                        package_name: package_name.clone(),
+                    }),
+                )
+            }));
         }
     }
 
@@ -322,16 +508,25 @@ fn check_with_modules(
         return Err(CheckError::Interrupt);
     }
 
+    if !project_config.relative_paths {
+        diagnostics = diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.with_absolute_path(project_root))
+            .collect();
+    }
+
     Ok(diagnostics)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::ProjectConfig;
-    use crate::diagnostics::Severity;
+    use crate::config::RuleSetting;
+    use crate::config::{ExternalDependencyConfig, ProjectConfig};
+    use crate::diagnostics::serialize_check_output_json;
     use crate::tests::fixtures::example_dir;
     use rstest::*;
+    use std::collections::BTreeMap;
 
     #[fixture]
     fn project_config() -> ProjectConfig {
@@ -365,14 +560,21 @@ mod tests {
         module_mapping: HashMap<String, Vec<String>>,
     ) {
         let project_root = example_dir.join("multi_package");
-        let result =
-            check_with_modules(&project_root, &project_config, &module_mapping, &[]).unwrap();
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &module_mapping,
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
         assert_eq!(result.len(), 1);
         assert!(matches!(
             result[0],
             Diagnostic::Global {
                 severity: Severity::Error,
-                details: DiagnosticDetails::Code(CodeDiagnostic::UnusedExternalDependency { .. })
+                details: DiagnosticDetails::Code(CodeDiagnostic::UnusedExternalDependency { .. }),
+                ..
             }
         ));
         assert_eq!(
@@ -390,13 +592,20 @@ mod tests {
         project_config: ProjectConfig,
     ) {
         let project_root = example_dir.join("multi_package");
-        let result =
-            check_with_modules(&project_root, &project_config, &HashMap::new(), &[]).unwrap();
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
         assert_eq!(result.len(), 3);
         assert!(result.iter().any(|d| d.details()
             == &DiagnosticDetails::Code(CodeDiagnostic::UndeclaredExternalDependency {
                 dependency: "git".to_string(),
-                package_name: "myorg-pack-a".to_string()
+                package_name: "myorg-pack-a".to_string(),
+                suggestion: None,
             })));
         assert!(result.iter().any(|d| d.details()
             == &DiagnosticDetails::Code(CodeDiagnostic::UnusedExternalDependency {
@@ -409,4 +618,531 @@ mod tests {
                 package_name: "myorg-pack-a".to_string()
             })));
     }
+
+    #[rstest]
+    fn check_external_dependencies_relative_paths(
+        example_dir: PathBuf,
+        project_config: ProjectConfig,
+    ) {
+        let project_root = example_dir.join("multi_package");
+        let find_undeclared_path = |diagnostics: Vec<Diagnostic>| {
+            diagnostics
+                .into_iter()
+                .find(|d| {
+                    matches!(
+                        d.details(),
+                        DiagnosticDetails::Code(
+                            CodeDiagnostic::UndeclaredExternalDependency { .. }
+                        )
+                    )
+                })
+                .and_then(|d| d.file_path().cloned())
+                .unwrap()
+        };
+
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let file_path = find_undeclared_path(result);
+        assert!(file_path.is_relative());
+
+        let absolute_config = ProjectConfig {
+            relative_paths: false,
+            ..project_config
+        };
+        let result = check_with_modules(
+            &project_root,
+            &absolute_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let absolute_file_path = find_undeclared_path(result);
+        assert_eq!(absolute_file_path, project_root.join(file_path));
+    }
+
+    #[rstest]
+    fn check_external_dependencies_dangling_renames(
+        example_dir: PathBuf,
+        project_config: ProjectConfig,
+    ) {
+        let project_root = example_dir.join("multi_package");
+        let project_config = ProjectConfig {
+            external: ExternalDependencyConfig {
+                rename: vec!["git:GitPython".to_string(), "yaml:pyyaml".to_string()],
+                renames: BTreeMap::from([(
+                    "toml_compat".to_string(),
+                    "tomlkit-compat".to_string(),
+                )]),
+                validate_renames: true,
+                ..Default::default()
+            },
+            ..project_config
+        };
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let dangling: Vec<_> = result
+            .iter()
+            .filter_map(|d| match d.details() {
+                DiagnosticDetails::Configuration(ConfigurationDiagnostic::DanglingRename {
+                    rename,
+                }) => Some(rename.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(dangling, vec!["toml_compat:tomlkit_compat", "yaml:pyyaml"]);
+    }
+
+    #[rstest]
+    fn check_external_dependencies_unused_check_exempt_groups() {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(
+            project.path().join("pyproject.toml"),
+            r#"
+            [project]
+            name = "example"
+            dependencies = ["requests", "rich"]
+
+            [dependency-groups]
+            dev = ["ruff", "mypy"]
+            "#,
+        )
+        .unwrap();
+        std::fs::create_dir(project.path().join("src")).unwrap();
+        std::fs::write(project.path().join("src/app.py"), "import requests\n").unwrap();
+
+        let project_root = project.path().to_path_buf();
+        let project_config = ProjectConfig {
+            source_roots: vec![PathBuf::from("src")],
+            external: ExternalDependencyConfig {
+                unused_check_exempt_groups: vec!["dev".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let unused: Vec<_> = result
+            .iter()
+            .filter_map(|d| match d.details() {
+                DiagnosticDetails::Code(CodeDiagnostic::UnusedExternalDependency {
+                    package_module_name,
+                    ..
+                }) => Some(package_module_name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(unused, vec!["rich"]);
+    }
+
+    #[rstest]
+    #[case(false, vec![])]
+    #[case(true, vec!["torch"])]
+    fn check_external_dependencies_preserve_unknown_chars(
+        #[case] preserve_unknown_chars: bool,
+        #[case] expected_undeclared: Vec<&str>,
+    ) {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(
+            project.path().join("pyproject.toml"),
+            "[project]\nname = \"example\"\ndependencies = [\"torch+cu121\"]\n",
+        )
+        .unwrap();
+        std::fs::create_dir(project.path().join("src")).unwrap();
+        std::fs::write(project.path().join("src/app.py"), "import torch\n").unwrap();
+
+        let project_root = project.path().to_path_buf();
+        let project_config = ProjectConfig {
+            source_roots: vec![PathBuf::from("src")],
+            external: ExternalDependencyConfig {
+                preserve_unknown_chars,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::from([("torch".to_string(), vec!["torch-cu121".to_string()])]),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let undeclared: Vec<_> = result
+            .iter()
+            .filter_map(|d| match d.details() {
+                DiagnosticDetails::Code(CodeDiagnostic::UndeclaredExternalDependency {
+                    dependency,
+                    ..
+                }) => Some(dependency.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(undeclared, expected_undeclared);
+    }
+
+    #[rstest]
+    #[case("libs/mypkg/mypkg")]
+    #[case("libs/mypkg/src/mypkg")]
+    fn check_external_dependencies_editable_requirements(#[case] package_dir: &str) {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(
+            project.path().join("requirements.txt"),
+            "-e ./libs/mypkg\nrequests\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(project.path().join(package_dir)).unwrap();
+        std::fs::write(project.path().join(package_dir).join("__init__.py"), "").unwrap();
+        std::fs::create_dir(project.path().join("src")).unwrap();
+        std::fs::write(
+            project.path().join("src/app.py"),
+            "import mypkg\nimport requests\nimport yaml\n",
+        )
+        .unwrap();
+
+        let project_root = project.path().to_path_buf();
+        let project_config = ProjectConfig {
+            source_roots: vec![PathBuf::from("src")],
+            ..Default::default()
+        };
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let undeclared: Vec<_> = result
+            .iter()
+            .filter_map(|d| match d.details() {
+                DiagnosticDetails::Code(CodeDiagnostic::UndeclaredExternalDependency {
+                    dependency,
+                    ..
+                }) => Some(dependency.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(undeclared, vec!["yaml"]);
+    }
+
+    #[rstest]
+    #[case(true, vec![])]
+    #[case(false, vec!["urllib3"])]
+    fn check_external_dependencies_allow_transitive_imports(
+        #[case] allow_transitive_imports: bool,
+        #[case] expected_undeclared: Vec<&str>,
+    ) {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(
+            project.path().join("pyproject.toml"),
+            "[project]\nname = \"example\"\ndependencies = [\"requests\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project.path().join("conda-lock.yml"),
+            "version: 1\npackage:\n- name: requests\n  version: 2.31.0\n- name: urllib3\n  version: 2.2.0\n",
+        )
+        .unwrap();
+        std::fs::create_dir(project.path().join("src")).unwrap();
+        std::fs::write(
+            project.path().join("src/app.py"),
+            "import requests\nimport urllib3\n",
+        )
+        .unwrap();
+
+        let project_root = project.path().to_path_buf();
+        let project_config = ProjectConfig {
+            source_roots: vec![PathBuf::from("src")],
+            external: ExternalDependencyConfig {
+                conda_lock: true,
+                allow_transitive_imports,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let undeclared: Vec<_> = result
+            .iter()
+            .filter_map(|d| match d.details() {
+                DiagnosticDetails::Code(CodeDiagnostic::UndeclaredExternalDependency {
+                    dependency,
+                    ..
+                }) => Some(dependency.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(undeclared, expected_undeclared);
+        assert!(!result.iter().any(|d| matches!(
+            d.details(),
+            DiagnosticDetails::Code(CodeDiagnostic::UnusedExternalDependency { .. })
+        )));
+    }
+
+    #[rstest]
+    fn check_external_dependencies_collapse_unused_groups() {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(
+            project.path().join("pyproject.toml"),
+            r#"
+            [project]
+            name = "example"
+            dependencies = ["requests"]
+
+            [dependency-groups]
+            dev = ["pytest", "ruff"]
+            docs = ["mkdocs", "mkdocs-material"]
+            "#,
+        )
+        .unwrap();
+        std::fs::create_dir(project.path().join("src")).unwrap();
+        std::fs::write(
+            project.path().join("src/app.py"),
+            "import requests\nimport pytest\n",
+        )
+        .unwrap();
+
+        let project_root = project.path().to_path_buf();
+        let project_config = ProjectConfig {
+            source_roots: vec![PathBuf::from("src")],
+            external: ExternalDependencyConfig {
+                include_dependency_groups: vec!["all".to_string()],
+                collapse_unused_groups: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let unused_details: Vec<_> = result
+            .iter()
+            .map(|d| d.details())
+            .filter(|details| {
+                matches!(
+                    details,
+                    DiagnosticDetails::Code(
+                        CodeDiagnostic::UnusedExternalDependency { .. }
+                            | CodeDiagnostic::UnusedDependencyGroup { .. }
+                    )
+                )
+            })
+            .collect();
+        assert_eq!(
+            unused_details,
+            vec![
+                &DiagnosticDetails::Code(CodeDiagnostic::UnusedDependencyGroup {
+                    group: "docs".to_string(),
+                    members: vec!["mkdocs".to_string(), "mkdocs_material".to_string()],
+                    package_name: "example".to_string(),
+                }),
+                &DiagnosticDetails::Code(CodeDiagnostic::UnusedExternalDependency {
+                    package_module_name: "ruff".to_string(),
+                    package_name: "example".to_string(),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_external_dependencies_json_separates_parsing_warnings() {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(
+            project.path().join("pyproject.toml"),
+            r#"
+            [project]
+            name = "example"
+            dependencies = ["requests"]
+
+            [tool.poetry.dependencies]
+            python = "^3.10"
+            rich = "*"
+            "#,
+        )
+        .unwrap();
+        std::fs::create_dir(project.path().join("src")).unwrap();
+        std::fs::write(project.path().join("src/app.py"), "import yaml\n").unwrap();
+
+        let project_root = project.path().to_path_buf();
+        let project_config = ProjectConfig {
+            source_roots: vec![PathBuf::from("src")],
+            ..Default::default()
+        };
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let output: serde_json::Value =
+            serde_json::from_str(&serialize_check_output_json(result, false)).unwrap();
+
+        let warning_kinds: Vec<_> = output["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|warning| warning["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(warning_kinds, vec!["project_and_poetry_dependencies"]);
+        let findings = output["findings"].as_array().unwrap();
+        assert!(!findings.is_empty());
+        assert!(
+            findings
+                .iter()
+                .all(|finding| !finding.to_string().contains("Poetry"))
+        );
+    }
+
+    #[rstest]
+    #[case("import pytest\n", RuleSetting::Warn, vec![Severity::Warning])]
+    #[case("import yaml\n", RuleSetting::Warn, vec![Severity::Error])]
+    #[case("def run():\n    import pytest\n", RuleSetting::Off, vec![])]
+    #[case("def run():\n    import pytest\n", RuleSetting::Warn, vec![Severity::Warning])]
+    #[case("def run():\n    import yaml\n", RuleSetting::Off, vec![Severity::Error])]
+    fn check_external_dependencies_missing_group_severities(
+        #[case] source: &str,
+        #[case] dev_setting: RuleSetting,
+        #[case] expected_severities: Vec<Severity>,
+    ) {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(
+            project.path().join("pyproject.toml"),
+            r#"
+            [project]
+            name = "example"
+            dependencies = []
+
+            [dependency-groups]
+            dev = ["pytest"]
+            "#,
+        )
+        .unwrap();
+        std::fs::create_dir(project.path().join("src")).unwrap();
+        std::fs::write(project.path().join("src/app.py"), source).unwrap();
+
+        let project_root = project.path().to_path_buf();
+        let project_config = ProjectConfig {
+            source_roots: vec![PathBuf::from("src")],
+            external: ExternalDependencyConfig {
+                missing_group_severities: BTreeMap::from([("dev".to_string(), dev_setting)]),
+                unused_check_exempt_groups: vec!["dev".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+        )
+        .unwrap();
+        let severities: Vec<_> = result
+            .iter()
+            .filter(|d| {
+                matches!(
+                    d.details(),
+                    DiagnosticDetails::Code(CodeDiagnostic::UndeclaredExternalDependency { .. })
+                )
+            })
+            .map(|d| d.severity())
+            .collect();
+        assert_eq!(severities, expected_severities);
+    }
+
+    #[rstest]
+    #[case(true, vec![
+        ("rename", "yaml:pyyaml"),
+        ("renames", "toml_compat:tomlkit_compat"),
+        ("exclude", "removed-dep"),
+    ])]
+    #[case(false, vec![])]
+    fn check_external_dependencies_unmatched_external_entries(
+        #[case] validate_installed_entries: bool,
+        #[case] expected: Vec<(&str, &str)>,
+    ) {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(
+            project.path().join("pyproject.toml"),
+            r#"
+            [project]
+            name = "example"
+            dependencies = ["Pillow", "requests"]
+            "#,
+        )
+        .unwrap();
+        std::fs::create_dir(project.path().join("src")).unwrap();
+        std::fs::write(
+            project.path().join("src/app.py"),
+            "import PIL\nimport requests\n",
+        )
+        .unwrap();
+
+        let project_root = project.path().to_path_buf();
+        let project_config = ProjectConfig {
+            source_roots: vec![PathBuf::from("src")],
+            external: ExternalDependencyConfig {
+                rename: vec!["PIL:pillow".to_string(), "yaml:pyyaml".to_string()],
+                renames: BTreeMap::from([(
+                    "toml_compat".to_string(),
+                    "tomlkit-compat".to_string(),
+                )]),
+                exclude: vec!["pytest".to_string(), "removed-dep".to_string()],
+                validate_installed_entries,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let result = check_with_modules(
+            &project_root,
+            &project_config,
+            &HashMap::new(),
+            &[],
+            &HashSet::from(["pytest".to_string()]),
+        )
+        .unwrap();
+        let unmatched: Vec<_> = result
+            .iter()
+            .filter_map(|d| match d.details() {
+                DiagnosticDetails::Configuration(
+                    ConfigurationDiagnostic::UnmatchedExternalEntry { field, entry },
+                ) => Some((field.as_str(), entry.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(unmatched, expected);
+    }
 }
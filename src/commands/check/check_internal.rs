@@ -11,11 +11,11 @@ use crate::{
     config::ProjectConfig,
     diagnostics::{
         ConfigurationDiagnostic, Diagnostic, DiagnosticDetails, DiagnosticError,
-        DiagnosticPipeline, FileChecker, FileProcessor, Result as DiagnosticResult,
+        DiagnosticPipeline, FileChecker, FileProcessor, Result as DiagnosticResult, Severity,
     },
     filesystem::{self as fs, ProjectFile},
     interrupt::check_interrupt,
-    modules::{ModuleTree, ModuleTreeBuilder},
+    modules::{ModuleTree, ModuleTreeBuilder, validation::find_dependency_cycles},
     processors::{FileModule, InternalDependencyExtractor},
     resolvers::{PackageResolver, SourceRootResolver},
 };
@@ -31,14 +31,14 @@ struct CheckInternalPipeline<'a> {
 }
 
 impl<'a> CheckInternalPipeline<'a> {
-    pub fn new(
+    pub fn try_new(
         project_config: &'a ProjectConfig,
         source_roots: &'a [PathBuf],
         module_tree: &'a ModuleTree,
         package_resolver: &'a PackageResolver,
         found_imports: &'a AtomicBool,
-    ) -> Self {
-        Self {
+    ) -> Result<Self> {
+        Ok(Self {
             found_imports,
             dependency_extractor: InternalDependencyExtractor::new(
                 source_roots,
@@ -48,8 +48,9 @@ impl<'a> CheckInternalPipeline<'a> {
             ),
             dependency_checker: None,
             interface_checker: None,
-            ignore_directive_post_processor: IgnoreDirectivePostProcessor::new(project_config),
-        }
+            ignore_directive_post_processor: IgnoreDirectivePostProcessor::try_new(project_config)
+                .map_err(|err| CheckError::Configuration(err.to_string()))?,
+        })
     }
 
     pub fn with_dependency_checker(
@@ -109,6 +110,7 @@ impl<'a> FileChecker<'a> for CheckInternalPipeline<'a> {
             &processed_file.ignore_directives,
             &mut diagnostics,
             processed_file.relative_file_path(),
+            processed_file.module_config(),
         );
 
         Ok(diagnostics)
@@ -137,14 +139,20 @@ pub fn check(
         project_root,
         &project_config.exclude,
         project_config.respect_gitignore,
-    )?;
+    )?
+    .with_source_extensions(&project_config.source_extensions);
     let source_root_resolver = SourceRootResolver::new(project_root, &file_walker);
     let source_roots = source_root_resolver.resolve(&project_config.source_roots)?;
-    let package_resolver = PackageResolver::try_new(project_root, &source_roots, &file_walker)?;
+    let package_resolver = PackageResolver::try_new(
+        project_root,
+        &source_roots,
+        &file_walker,
+        &project_config.external,
+    )?;
     let module_tree_builder = ModuleTreeBuilder::new(
         &source_roots,
         &file_walker,
-        project_config.forbid_circular_dependencies,
+        project_config.circular_dependencies(),
         project_config.root_module,
     );
 
@@ -159,6 +167,25 @@ pub fn check(
         ));
     }
 
+    // Cycles which are errors fail the module tree build instead
+    let circular_dependencies = project_config.circular_dependencies();
+    if !circular_dependencies.is_error()
+        && let Ok(severity) = Severity::try_from(&circular_dependencies)
+    {
+        diagnostics.extend(
+            find_dependency_cycles(&valid_modules)
+                .into_iter()
+                .map(|cycle| {
+                    Diagnostic::new_global(
+                        severity,
+                        DiagnosticDetails::Configuration(
+                            ConfigurationDiagnostic::CircularDependency { cycle },
+                        ),
+                    )
+                }),
+        );
+    }
+
     check_interrupt().map_err(|_| CheckError::Interrupt)?;
     let module_tree = module_tree_builder.build(valid_modules)?;
 
@@ -176,13 +203,13 @@ pub fn check(
         None
     };
 
-    let pipeline = CheckInternalPipeline::new(
+    let pipeline = CheckInternalPipeline::try_new(
         project_config,
         &source_roots,
         &module_tree,
         &package_resolver,
         &found_imports,
-    )
+    )?
     .with_dependency_checker(dependency_checker)
     .with_interface_checker(interface_checker);
 
@@ -253,14 +280,22 @@ pub fn check(
         ));
     }
 
+    if !project_config.relative_paths {
+        diagnostics = diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.with_absolute_path(project_root))
+            .collect();
+    }
+
     Ok(diagnostics)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::ProjectConfig;
+    use crate::config::{ProjectConfig, RuleSetting};
     use crate::diagnostics::Severity;
+    use crate::modules::error::ModuleTreeError;
     use crate::tests::fixtures::example_dir;
     use rstest::*;
 
@@ -302,8 +337,131 @@ mod tests {
                 severity: Severity::Error,
                 details: DiagnosticDetails::Configuration(
                     ConfigurationDiagnostic::SkippedFileSyntaxError { .. }
-                )
+                ),
+                ..
             }
         ));
     }
+
+    fn cyclic_project(
+        circular_dependencies: Option<RuleSetting>,
+        forbid_circular_dependencies: bool,
+    ) -> (tempfile::TempDir, ProjectConfig) {
+        use crate::config::{DependencyConfig, ModuleConfig, RulesConfig};
+        use std::fs;
+
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        fs::create_dir_all(project.path().join("a")).unwrap();
+        fs::create_dir_all(project.path().join("b")).unwrap();
+        fs::write(project.path().join("a/__init__.py"), "import b\n").unwrap();
+        fs::write(project.path().join("b/__init__.py"), "import a\n").unwrap();
+        let project_config = ProjectConfig {
+            modules: vec![
+                ModuleConfig::from_path_and_dependencies(
+                    "a",
+                    Some(vec![DependencyConfig::from_path("b")]),
+                ),
+                ModuleConfig::from_path_and_dependencies(
+                    "b",
+                    Some(vec![DependencyConfig::from_path("a")]),
+                ),
+            ],
+            forbid_circular_dependencies,
+            rules: RulesConfig {
+                circular_dependencies,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        (project, project_config)
+    }
+
+    #[rstest]
+    #[case(Some(RuleSetting::Warn), true, vec![Severity::Warning])]
+    #[case(Some(RuleSetting::Warn), false, vec![Severity::Warning])]
+    #[case(Some(RuleSetting::Info), false, vec![Severity::Info])]
+    #[case(Some(RuleSetting::Off), true, vec![])]
+    #[case(None, false, vec![])]
+    fn check_internal_reported_circular_dependencies(
+        #[case] circular_dependencies: Option<RuleSetting>,
+        #[case] forbid_circular_dependencies: bool,
+        #[case] expected: Vec<Severity>,
+    ) {
+        let (project, project_config) =
+            cyclic_project(circular_dependencies, forbid_circular_dependencies);
+
+        let result = check(&project.path().to_path_buf(), &project_config, true, false).unwrap();
+
+        assert_eq!(
+            result
+                .iter()
+                .filter(|diagnostic| matches!(
+                    diagnostic.details(),
+                    DiagnosticDetails::Configuration(
+                        ConfigurationDiagnostic::CircularDependency { .. }
+                    )
+                ))
+                .map(|diagnostic| diagnostic.severity())
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(Some(RuleSetting::Error), false)]
+    #[case(Some(RuleSetting::Error), true)]
+    #[case(None, true)]
+    fn check_internal_fails_on_circular_dependencies(
+        #[case] circular_dependencies: Option<RuleSetting>,
+        #[case] forbid_circular_dependencies: bool,
+    ) {
+        let (project, project_config) =
+            cyclic_project(circular_dependencies, forbid_circular_dependencies);
+
+        assert!(matches!(
+            check(&project.path().to_path_buf(), &project_config, true, false),
+            Err(CheckError::ModuleTree(ModuleTreeError::CircularDependency(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn check_internal_reports_deprecated_module_usage() {
+        use crate::config::{DependencyConfig, ModuleConfig};
+        use std::fs;
+
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        fs::create_dir_all(project.path().join("legacy")).unwrap();
+        fs::create_dir_all(project.path().join("app")).unwrap();
+        fs::write(project.path().join("legacy/__init__.py"), "").unwrap();
+        fs::write(
+            project.path().join("app/__init__.py"),
+            "from legacy import helpers\n",
+        )
+        .unwrap();
+        let project_config = ProjectConfig {
+            modules: vec![
+                ModuleConfig::from_path_and_dependencies(
+                    "app",
+                    Some(vec![DependencyConfig::from_path("legacy")]),
+                ),
+                ModuleConfig::from_path("legacy").with_deprecated(true),
+            ],
+            ..Default::default()
+        };
+
+        let result = check(&project.path().to_path_buf(), &project_config, true, false).unwrap();
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|diagnostic| (diagnostic.severity(), diagnostic.message()))
+                .collect::<Vec<_>>(),
+            [(
+                Severity::Warning,
+                "Module 'app' uses 'legacy.helpers' from deprecated module 'legacy'.".to_string()
+            )]
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    cli::{create_clickable_link, fail, warning},
+    cli::{create_clickable_link, fail, info, warning},
     diagnostics::{CodeDiagnostic, Diagnostic, DiagnosticDetails, Severity},
 };
 use std::collections::HashMap;
@@ -23,6 +23,7 @@ impl From<&DiagnosticDetails> for DiagnosticGroupKind {
             DiagnosticDetails::Code(code_diagnostic_details) => match code_diagnostic_details {
                 CodeDiagnostic::UndeclaredDependency { .. }
                 | CodeDiagnostic::DeprecatedDependency { .. }
+                | CodeDiagnostic::DeprecatedModuleUsage { .. }
                 | CodeDiagnostic::ForbiddenDependency { .. }
                 | CodeDiagnostic::LayerViolation { .. }
                 | CodeDiagnostic::ClosedLayerViolation { .. } => Self::InternalDependency,
@@ -33,11 +34,13 @@ impl From<&DiagnosticDetails> for DiagnosticGroupKind {
                 CodeDiagnostic::UndeclaredExternalDependency { .. }
                 | CodeDiagnostic::ModuleUndeclaredExternalDependency { .. }
                 | CodeDiagnostic::ModuleForbiddenExternalDependency { .. }
-                | CodeDiagnostic::UnusedExternalDependency { .. } => Self::ExternalDependency,
+                | CodeDiagnostic::UnusedExternalDependency { .. }
+                | CodeDiagnostic::UnusedDependencyGroup { .. } => Self::ExternalDependency,
 
                 CodeDiagnostic::UnnecessarilyIgnoredDependency { .. }
                 | CodeDiagnostic::UnusedIgnoreDirective()
-                | CodeDiagnostic::MissingIgnoreDirectiveReason() => Self::Other,
+                | CodeDiagnostic::MissingIgnoreDirectiveReason()
+                | CodeDiagnostic::IgnoreDirectiveReasonMismatch { .. } => Self::Other,
             },
         }
     }
@@ -135,6 +138,13 @@ fn format_diagnostic(diagnostic: &Diagnostic) -> String {
             style(":").yellow().bold(),
             style(diagnostic.message()).yellow(),
         ),
+        Severity::Info => format!(
+            "{} {}{} {}",
+            info(),
+            style(error_location).cyan().bold(),
+            style(":").cyan().bold(),
+            style(diagnostic.message()).cyan(),
+        ),
     }
 }
 
@@ -143,6 +153,7 @@ fn format_diagnostic_group(group: &mut DiagnosticGroup) -> String {
     let header = match group.severity {
         Severity::Error => style(&group.header).red().bold(),
         Severity::Warning => style(&group.header).yellow().bold(),
+        Severity::Info => style(&group.header).cyan().bold(),
     };
     let diagnostics = group
         .diagnostics
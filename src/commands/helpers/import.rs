@@ -53,8 +53,14 @@ pub fn get_located_project_imports<P: AsRef<Path>>(
         project_root,
         &project_config.exclude,
         project_config.respect_gitignore,
+    )?
+    .with_source_extensions(&project_config.source_extensions);
+    let package_resolver = PackageResolver::try_new(
+        project_root,
+        source_roots,
+        &file_walker,
+        &project_config.external,
     )?;
-    let package_resolver = PackageResolver::try_new(project_root, source_roots, &file_walker)?;
     let package = match package_resolver.resolve_file_path(file_path.as_ref()) {
         PackageResolution::Found { package, .. } => package,
         PackageResolution::NotFound | PackageResolution::Excluded => {
@@ -114,8 +120,14 @@ pub fn get_located_external_imports<P: AsRef<Path>>(
         project_root,
         &project_config.exclude,
         project_config.respect_gitignore,
+    )?
+    .with_source_extensions(&project_config.source_extensions);
+    let package_resolver = PackageResolver::try_new(
+        project_root,
+        source_roots,
+        &file_walker,
+        &project_config.external,
     )?;
-    let package_resolver = PackageResolver::try_new(project_root, source_roots, &file_walker)?;
     let package = match package_resolver.resolve_file_path(file_path.as_ref()) {
         PackageResolution::Found { package, .. } => package,
         PackageResolution::NotFound | PackageResolution::Excluded => {
@@ -136,7 +148,10 @@ pub fn get_located_external_imports<P: AsRef<Path>>(
             )
         })
         .filter(|import| {
-            if ignore_directives.is_ignored(import) {
+            if ignore_directives.is_ignored(import)
+                || project_config.external.is_first_party(import.module_path())
+                || package.is_editable_module(import.module_path())
+            {
                 return false;
             }
 
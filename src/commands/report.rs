@@ -10,8 +10,8 @@ use thiserror::Error;
 use crate::cli;
 use crate::cli::create_clickable_link;
 use crate::colors::*;
-use crate::config::ProjectConfig;
 use crate::config::root_module::RootModuleTreatment;
+use crate::config::{ProjectConfig, RuleSetting};
 use crate::dependencies::LocatedImport;
 use crate::filesystem;
 use crate::interrupt::check_interrupt;
@@ -225,13 +225,14 @@ pub fn create_dependency_report(
         project_root,
         &project_config.exclude,
         project_config.respect_gitignore,
-    )?;
+    )?
+    .with_source_extensions(&project_config.source_extensions);
     let source_root_resolver = SourceRootResolver::new(project_root, &file_walker);
     let source_roots = source_root_resolver.resolve(&project_config.source_roots)?;
     let module_tree_builder = ModuleTreeBuilder::new(
         &source_roots,
         &file_walker,
-        false,                      // skip circular dependency check in report
+        RuleSetting::Off,           // skip circular dependency check in report
         RootModuleTreatment::Allow, // skip root module check in report
     );
     let (valid_modules, _) = module_tree_builder.resolve_modules(project_config.all_modules());
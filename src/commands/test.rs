@@ -55,7 +55,8 @@ impl TachPytestPluginHandler {
             &project_config.exclude,
             project_config.respect_gitignore,
         )
-        .unwrap();
+        .unwrap()
+        .with_source_extensions(&project_config.source_extensions);
         let source_root_resolver = SourceRootResolver::new(&project_root, &file_walker);
         let source_roots = source_root_resolver
             .resolve(&project_config.source_roots)
@@ -63,7 +64,7 @@ impl TachPytestPluginHandler {
         let module_tree_builder = ModuleTreeBuilder::new(
             &source_roots,
             &file_walker,
-            project_config.forbid_circular_dependencies,
+            project_config.circular_dependencies(),
             project_config.root_module,
         );
 
@@ -146,7 +147,8 @@ fn get_changed_module_paths(
         project_root,
         &project_config.exclude,
         project_config.respect_gitignore,
-    )?;
+    )?
+    .with_source_extensions(&project_config.source_extensions);
     let source_root_resolver = SourceRootResolver::new(project_root, &file_walker);
     let source_roots: Vec<PathBuf> = source_root_resolver.resolve(&project_config.source_roots)?;
 
@@ -1,5 +1,9 @@
-use pyo3::{prelude::*, types::PyString};
+use pyo3::{
+    prelude::*,
+    types::{PyInt, PyString},
+};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 use super::utils;
 
@@ -8,6 +12,29 @@ use super::utils;
 pub enum CacheBackend {
     #[default]
     Disk,
+    /// A cache held in memory, which lasts only as long as the current process
+    Memory,
+    /// A remote cache server, which stores each entry at `<url>/<cache key>`
+    Http {
+        url: String,
+        /// Fail the run on network errors, instead of treating them as cache misses
+        #[serde(default, skip_serializing_if = "utils::is_default")]
+        strict: bool,
+        /// The environment variable holding the value of the `Authorization` header, if any
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        auth_header_env: Option<String>,
+    },
+    /// An S3 bucket, which stores each entry as the object `<prefix><cache key>`.
+    /// Credentials are read from the standard AWS environment variables.
+    S3 {
+        bucket: String,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        prefix: String,
+        region: String,
+        /// The URL of an S3-compatible service (such as MinIO or R2) to use instead of AWS
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
+    },
 }
 
 impl<'py> IntoPyObject<'py> for CacheBackend {
@@ -17,11 +44,59 @@ impl<'py> IntoPyObject<'py> for CacheBackend {
     fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
         match self {
             Self::Disk => "disk".into_pyobject(py),
+            Self::Memory => "memory".into_pyobject(py),
+            Self::Http { .. } => "http".into_pyobject(py),
+            Self::S3 { .. } => "s3".into_pyobject(py),
         }
     }
 }
 
-#[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq)]
+/// A zstd compression level, from 1 (fastest) to 22 (smallest)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "i32", into = "i32")]
+pub struct CompressionLevel(i32);
+
+impl CompressionLevel {
+    pub const MIN: i32 = 1;
+    pub const MAX: i32 = 22;
+
+    pub fn level(&self) -> i32 {
+        self.0
+    }
+}
+
+impl TryFrom<i32> for CompressionLevel {
+    type Error = String;
+
+    fn try_from(level: i32) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&level) {
+            Ok(Self(level))
+        } else {
+            Err(format!(
+                "compression level must be between {} and {}, found {level}",
+                Self::MIN,
+                Self::MAX
+            ))
+        }
+    }
+}
+
+impl From<CompressionLevel> for i32 {
+    fn from(level: CompressionLevel) -> Self {
+        level.0
+    }
+}
+
+impl<'py> IntoPyObject<'py> for CompressionLevel {
+    type Target = PyInt;
+    type Output = Bound<'py, Self::Target>;
+    type Error = std::convert::Infallible;
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        self.0.into_pyobject(py)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[pyclass(get_all, module = "tach.extension")]
 pub struct CacheConfig {
     #[serde(default, skip_serializing_if = "utils::is_default")]
@@ -30,4 +105,87 @@ pub struct CacheConfig {
     pub file_dependencies: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub env_dependencies: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub irrelevant_rules: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<CompressionLevel>,
+    /// Whether the Python interpreter version affects cached results
+    #[serde(
+        default = "utils::default_true",
+        skip_serializing_if = "utils::is_true"
+    )]
+    pub interpreter_sensitive: bool,
+    /// Whether the installed distributions also affect cached results, when `interpreter_sensitive` is set
+    #[serde(default, skip_serializing_if = "utils::is_default")]
+    pub interpreter_distributions: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            backend: Default::default(),
+            file_dependencies: Default::default(),
+            env_dependencies: Default::default(),
+            irrelevant_rules: Default::default(),
+            ttl_seconds: Default::default(),
+            max_size_bytes: Default::default(),
+            compression: Default::default(),
+            interpreter_sensitive: true,
+            interpreter_distributions: false,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// How long disk cache entries remain valid, or `None` if they never expire
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl_seconds.map(Duration::from_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("backend = \"disk\"\n")]
+    #[case("backend = \"memory\"\n")]
+    #[case("[backend.http]\nurl = \"https://cache.example.com\"\nstrict = true\n")]
+    #[case(
+        "[backend.s3]\nbucket = \"ci-artifacts\"\nprefix = \"tach/\"\nregion = \"auto\"\nendpoint = \"https://minio.internal:9000\"\n"
+    )]
+    fn test_cache_backend_round_trip(#[case] toml_str: &str) {
+        let config: CacheConfig = toml::from_str(toml_str).unwrap();
+        let serialized = toml::to_string(&config).unwrap();
+        assert_eq!(toml::from_str::<CacheConfig>(&serialized).unwrap(), config);
+    }
+
+    #[rstest]
+    #[case("compression = 3\n", true)]
+    #[case("compression = 0\n", false)]
+    #[case("compression = 23\n", false)]
+    fn test_compression_level_range(#[case] toml_str: &str, #[case] is_valid: bool) {
+        assert_eq!(toml::from_str::<CacheConfig>(toml_str).is_ok(), is_valid);
+    }
+
+    #[test]
+    fn test_s3_backend_defaults() {
+        let config: CacheConfig =
+            toml::from_str("[backend.s3]\nbucket = \"ci-artifacts\"\nregion = \"us-east-1\"\n")
+                .unwrap();
+        assert_eq!(
+            config.backend,
+            CacheBackend::S3 {
+                bucket: "ci-artifacts".to_string(),
+                prefix: String::new(),
+                region: "us-east-1".to_string(),
+                endpoint: None,
+            }
+        );
+    }
 }
@@ -31,6 +31,8 @@ pub struct DomainRootConfig {
     pub utility: bool,
     #[serde(default, skip_serializing_if = "Not::not")]
     pub unchecked: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub deprecated: bool,
 }
 
 impl DomainRootConfig {
@@ -156,6 +158,7 @@ impl Resolvable<ModuleConfig> for DomainRootConfig {
             self.utility,
             self.unchecked,
         )
+        .with_deprecated(self.deprecated)
     }
 }
 
@@ -174,6 +177,8 @@ impl Resolvable<ModuleConfig> for ModuleConfig {
             self.utility,
             self.unchecked,
         )
+        .with_rules(self.rules.clone())
+        .with_deprecated(self.deprecated)
         .with_copied_origin(self)
     }
 }
@@ -11,4 +11,27 @@ pub enum ConfigError {
     FileWalker(#[from] FileSystemError),
     #[error("Failed to resolve source roots.\n{0}")]
     SourceRootResolution(#[from] SourceRootResolverError),
+    #[error("Failed to serialize config.\n{0}")]
+    Serialization(#[from] toml::ser::Error),
+    #[error("Invalid 'ignore_directive_reason_pattern' in rules: '{pattern}'.\n{source}")]
+    InvalidIgnoreDirectiveReasonPattern {
+        pattern: String,
+        source: regex::Error,
+    },
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RenameError {
+    #[error(
+        "Invalid rename format: expected format is a list of 'module:name' pairs, e.g. ['PIL:pillow']"
+    )]
+    InvalidFormat(String),
+    #[error("Conflicting renames for package '{package}': '{first}' and '{second}'")]
+    Conflict {
+        package: String,
+        first: String,
+        second: String,
+    },
+    #[error("Failed to read rename file '{path}': {message}")]
+    File { path: String, message: String },
 }
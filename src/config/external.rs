@@ -1,11 +1,712 @@
-use pyo3::prelude::*;
-use serde::{Deserialize, Serialize};
+use pyo3::{prelude::*, types::PyString};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Not;
+use std::path::{Path, PathBuf};
 
+use super::error::RenameError;
+use super::rules::RuleSetting;
+use super::utils;
+use crate::external::parsing::{
+    NameNormalizer, normalize_package_name, normalize_package_name_pep503,
+    normalize_package_name_with,
+};
+use crate::external::suggest::suggest_similar_name;
+
+/// Selecting this group name includes every dependency group
+pub const ALL_DEPENDENCY_GROUPS: &str = "all";
+
+pub const DEFAULT_DEPENDENCY_GROUPS: [&str; 2] = ["default", "dev"];
+
+pub fn default_dependency_groups() -> Vec<String> {
+    DEFAULT_DEPENDENCY_GROUPS
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn is_default_dependency_groups(groups: &[String]) -> bool {
+    groups == default_dependency_groups().as_slice()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DependencyGroupsSetting {
+    /// Older configs select either every group or none
+    Bool(bool),
+    Groups(Vec<String>),
+}
+
+fn deserialize_dependency_groups<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match DependencyGroupsSetting::deserialize(deserializer)? {
+        DependencyGroupsSetting::Bool(true) => vec![ALL_DEPENDENCY_GROUPS.to_string()],
+        DependencyGroupsSetting::Bool(false) => vec![],
+        DependencyGroupsSetting::Groups(groups) => groups,
+    })
+}
+
+/// Groups which are commonly spelled in several ways, and are selected by any of their spellings
+const DEPENDENCY_GROUP_ALIASES: [&[&str]; 3] = [
+    &["dev", "development"],
+    &["test", "tests", "testing"],
+    &["doc", "docs", "documentation"],
+];
+
+fn canonical_group_name(group: &str) -> &str {
+    DEPENDENCY_GROUP_ALIASES
+        .iter()
+        .find(|aliases| aliases.contains(&group))
+        .map_or(group, |aliases| aliases[0])
+}
+
+/// Match a name against a pattern in which '*' matches any run of characters
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(name) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=name.len())
+                .filter(|&i| name.is_char_boundary(i))
+                .any(|i| wildcard_match(rest, &name[i..]))
+        }
+    }
+}
+
+/// Whether a single group selector (without any '!' prefix) matches the normalized group name
+fn group_selector_matches(selector: &str, group: &str) -> bool {
+    if selector == ALL_DEPENDENCY_GROUPS {
+        return true;
+    }
+    let selector = normalize_package_name(selector);
+    if selector.contains('*') {
+        return wildcard_match(&selector, group);
+    }
+    canonical_group_name(&selector) == canonical_group_name(group)
+}
+
+/// Whether the group is selected by a list of selectors. A group is selected when it matches
+/// `"all"`, a name (or one of its aliases), or a '*' wildcard, and no '!'-prefixed negation.
+pub fn is_dependency_group_selected(selection: &[String], group: &str) -> bool {
+    let group = normalize_package_name(group);
+    let (negations, selectors): (Vec<&String>, Vec<&String>) = selection
+        .iter()
+        .partition(|selector| selector.starts_with('!'));
+    selectors
+        .iter()
+        .any(|selector| group_selector_matches(selector, &group))
+        && !negations
+            .iter()
+            .any(|negation| group_selector_matches(&negation[1..], &group))
+}
+
+/// How package names are normalized when they are reported
+#[derive(Debug, Serialize, Default, Deserialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NameNormalization {
+    /// Lowercase, joined with '_' (matching import names)
+    #[default]
+    Import,
+    /// Strict PEP 503, lowercase with runs of '-', '_' and '.' replaced by '-'
+    Pep503,
+}
+
+impl NameNormalization {
+    pub fn normalize(&self, name: &str) -> String {
+        match self {
+            Self::Import => normalize_package_name(name),
+            Self::Pep503 => normalize_package_name_pep503(name),
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl<'py> IntoPyObject<'py> for NameNormalization {
+    type Target = PyString;
+    type Output = Bound<'py, Self::Target>;
+    type Error = std::convert::Infallible;
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        match self {
+            Self::Import => "import".into_pyobject(py),
+            Self::Pep503 => "pep503".into_pyobject(py),
+        }
+    }
+}
+
+/// The interpreter which PEP 508 environment markers are evaluated against.
+/// Markers which reference an unset variable are kept, so the default keeps every dependency.
 #[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq)]
 #[pyclass(get_all, module = "tach.extension")]
+pub struct MarkerEnvironment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sys_platform: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_name: Option<String>,
+}
+
+impl MarkerEnvironment {
+    pub fn is_empty(&self) -> bool {
+        self.python_version.is_none() && self.sys_platform.is_none() && self.os_name.is_none()
+    }
+}
+
+/// How a package is treated by the external dependency check
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackageTreatment {
+    /// Listed in `exclude`, so never reported
+    Excluded,
+    /// Provided under a different import name via `rename`
+    Renamed(String),
+    /// Matches a first-party prefix, so never treated as external
+    Internal,
+    Normal,
+}
+
+/// Where a rename was configured, in decreasing order of precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RenameOrigin {
+    /// The `rename` array of 'module:name' pairs
+    Array,
+    /// The `renames` table of module = name entries
+    Table,
+    /// The `rename_files` entry at the given position
+    File(usize),
+}
+
+impl std::fmt::Display for RenameOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Array => write!(f, "rename"),
+            Self::Table => write!(f, "renames"),
+            Self::File(index) => write!(f, "rename_files[{index}]"),
+        }
+    }
+}
+
+fn parse_rename_pair(rename_pair: &str) -> Result<(String, String), RenameError> {
+    match rename_pair.split_once(':') {
+        Some((module, name)) if !module.trim().is_empty() && !name.trim().is_empty() => {
+            Ok((module.trim().to_string(), name.trim().to_string()))
+        }
+        _ => Err(RenameError::InvalidFormat(rename_pair.to_string())),
+    }
+}
+
+/// Merge renames into a single map from normalized package name to import name.
+/// A package renamed by several origins takes the rename with the highest precedence,
+/// while differing renames of the same package from one origin are a conflict.
+/// Package names are normalized as by [`normalize_package_name_with`].
+pub fn merge_renames<I>(
+    renames: I,
+    preserve_unknown_chars: bool,
+) -> Result<HashMap<String, String>, RenameError>
+where
+    I: IntoIterator<Item = (RenameOrigin, String, String)>,
+{
+    Ok(merge_renames_with_origins(renames, preserve_unknown_chars)?
+        .into_iter()
+        .map(|(package, (_, module))| (package, module))
+        .collect())
+}
+
+/// Merge renames like [`merge_renames`], keeping the origin of each effective rename
+pub fn merge_renames_with_origins<I>(
+    renames: I,
+    preserve_unknown_chars: bool,
+) -> Result<HashMap<String, (RenameOrigin, String)>, RenameError>
+where
+    I: IntoIterator<Item = (RenameOrigin, String, String)>,
+{
+    let mut merged: HashMap<String, (RenameOrigin, String)> = HashMap::new();
+    for (origin, module, name) in renames {
+        let package = normalize_package_name_with(&name, preserve_unknown_chars);
+        match merged.get(&package) {
+            Some((existing_origin, _)) if *existing_origin < origin => {}
+            Some((existing_origin, existing_module))
+                if *existing_origin == origin && *existing_module != module =>
+            {
+                return Err(RenameError::Conflict {
+                    package,
+                    first: existing_module.clone(),
+                    second: module,
+                });
+            }
+            _ => {
+                merged.insert(package, (origin, module));
+            }
+        }
+    }
+    Ok(merged)
+}
+
+pub const DEFAULT_SUGGESTION_MAX_DISTANCE: usize = 2;
+
+fn default_suggestion_max_distance() -> usize {
+    DEFAULT_SUGGESTION_MAX_DISTANCE
+}
+
+fn is_default_suggestion_max_distance(distance: &usize) -> bool {
+    *distance == DEFAULT_SUGGESTION_MAX_DISTANCE
+}
+
+pub const DEFAULT_SUGGESTION_MIN_LENGTH: usize = 4;
+
+fn default_suggestion_min_length() -> usize {
+    DEFAULT_SUGGESTION_MIN_LENGTH
+}
+
+fn is_default_suggestion_min_length(length: &usize) -> bool {
+    *length == DEFAULT_SUGGESTION_MIN_LENGTH
+}
+
+pub const DEFAULT_MAX_DEPENDENCY_GROUP_DEPTH: usize = 64;
+
+fn default_max_dependency_group_depth() -> usize {
+    DEFAULT_MAX_DEPENDENCY_GROUP_DEPTH
+}
+
+fn is_default_max_dependency_group_depth(depth: &usize) -> bool {
+    *depth == DEFAULT_MAX_DEPENDENCY_GROUP_DEPTH
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
 pub struct ExternalDependencyConfig {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub exclude: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rename: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub renames: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rename_files: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub first_party_prefixes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub namespace_packages: Vec<String>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub detect_namespace_packages: bool,
+    #[serde(
+        default = "default_dependency_groups",
+        deserialize_with = "deserialize_dependency_groups",
+        skip_serializing_if = "is_default_dependency_groups"
+    )]
+    pub include_dependency_groups: Vec<String>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub single_dependency_source: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub conda_lock: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub pipfile_include_dev: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub allow_transitive_imports: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub validate_renames: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_insensitive_paths: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unused_check_exempt_groups: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub missing_group_severities: BTreeMap<String, RuleSetting>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub collapse_unused_groups: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub tool_plugins: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub uv_support: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub use_installed_distributions: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub validate_installed_entries: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pip_install_files: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub allow_external_source_paths: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub strict_source_paths: bool,
+    #[serde(
+        default = "default_suggestion_max_distance",
+        skip_serializing_if = "is_default_suggestion_max_distance"
+    )]
+    pub suggestion_max_distance: usize,
+    #[serde(
+        default = "default_suggestion_min_length",
+        skip_serializing_if = "is_default_suggestion_min_length"
+    )]
+    pub suggestion_min_length: usize,
+    #[serde(default, skip_serializing_if = "NameNormalization::is_default")]
+    pub name_normalization: NameNormalization,
+    #[serde(
+        default = "default_max_dependency_group_depth",
+        skip_serializing_if = "is_default_max_dependency_group_depth"
+    )]
+    pub max_dependency_group_depth: usize,
+    #[serde(default, skip_serializing_if = "MarkerEnvironment::is_empty")]
+    pub target_environment: MarkerEnvironment,
+    #[serde(
+        default = "utils::default_true",
+        skip_serializing_if = "utils::is_true"
+    )]
+    pub preserve_unknown_chars: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_layout_template: Option<String>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub strict_specifiers: bool,
+}
+
+/// Whether the module path is the given prefix or lies beneath it. A trailing '.*' is ignored.
+pub fn is_module_under_prefix(module_path: &str, prefix: &str) -> bool {
+    let prefix = prefix.strip_suffix(".*").unwrap_or(prefix);
+    module_path == prefix
+        || module_path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+impl Default for ExternalDependencyConfig {
+    fn default() -> Self {
+        Self {
+            exclude: Default::default(),
+            rename: Default::default(),
+            renames: Default::default(),
+            rename_files: Default::default(),
+            first_party_prefixes: Default::default(),
+            namespace_packages: Default::default(),
+            detect_namespace_packages: Default::default(),
+            include_dependency_groups: default_dependency_groups(),
+            single_dependency_source: Default::default(),
+            conda_lock: Default::default(),
+            pipfile_include_dev: Default::default(),
+            allow_transitive_imports: Default::default(),
+            validate_renames: Default::default(),
+            case_insensitive_paths: Default::default(),
+            unused_check_exempt_groups: Default::default(),
+            missing_group_severities: Default::default(),
+            collapse_unused_groups: Default::default(),
+            tool_plugins: Default::default(),
+            uv_support: Default::default(),
+            use_installed_distributions: Default::default(),
+            validate_installed_entries: Default::default(),
+            pip_install_files: Default::default(),
+            allow_external_source_paths: Default::default(),
+            strict_source_paths: Default::default(),
+            suggestion_max_distance: DEFAULT_SUGGESTION_MAX_DISTANCE,
+            suggestion_min_length: DEFAULT_SUGGESTION_MIN_LENGTH,
+            name_normalization: Default::default(),
+            max_dependency_group_depth: DEFAULT_MAX_DEPENDENCY_GROUP_DEPTH,
+            target_environment: Default::default(),
+            preserve_unknown_chars: true,
+            source_layout_template: Default::default(),
+            strict_specifiers: Default::default(),
+        }
+    }
+}
+
+impl ExternalDependencyConfig {
+    /// Normalize a distribution name, treating unknown characters as `preserve_unknown_chars` says
+    pub fn normalize_package_name(&self, name: &str) -> String {
+        normalize_package_name_with(name, self.preserve_unknown_chars)
+    }
+
+    /// A normalizer for the distribution names of manifests, following `preserve_unknown_chars`
+    pub fn name_normalizer(&self) -> NameNormalizer {
+        NameNormalizer::new(self.preserve_unknown_chars)
+    }
+
+    /// Whether the given module path falls under one of the configured first-party prefixes,
+    /// in which case it should never be treated as an external import.
+    pub fn is_first_party(&self, module_path: &str) -> bool {
+        self.first_party_prefixes
+            .iter()
+            .any(|prefix| is_module_under_prefix(module_path, prefix))
+    }
+
+    /// Determine how the package with the given name is treated.
+    /// When several rules match, `exclude` takes precedence over `rename`,
+    /// which takes precedence over `first_party_prefixes`.
+    /// `renames` are the effective renames, as returned by [`Self::rename_map`].
+    pub fn package_treatment(
+        &self,
+        package_name: &str,
+        renames: &HashMap<String, String>,
+    ) -> PackageTreatment {
+        let package_name = self.normalize_package_name(package_name);
+        if self
+            .exclude
+            .iter()
+            .any(|excluded| self.normalize_package_name(excluded) == package_name)
+        {
+            return PackageTreatment::Excluded;
+        }
+
+        if let Some(module) = renames.get(&package_name) {
+            return PackageTreatment::Renamed(module.clone());
+        }
+
+        if self.is_first_party(&package_name) {
+            return PackageTreatment::Internal;
+        }
+
+        PackageTreatment::Normal
+    }
+
+    /// The effective renames, as a map from normalized package name to import name.
+    ///
+    /// Renames are read from the `rename` array, the `renames` table and each of the
+    /// `rename_files` (relative to `project_root`), in decreasing order of precedence.
+    /// Rename files hold one 'module:name' pair per line, and may contain '#' comments.
+    pub fn rename_map(&self, project_root: &Path) -> Result<HashMap<String, String>, RenameError> {
+        Ok(self
+            .rename_map_with_origins(project_root)?
+            .into_iter()
+            .map(|(package, (_, module))| (package, module))
+            .collect())
+    }
+
+    /// The effective renames like [`Self::rename_map`], along with where each was configured
+    pub fn rename_map_with_origins(
+        &self,
+        project_root: &Path,
+    ) -> Result<HashMap<String, (RenameOrigin, String)>, RenameError> {
+        let mut renames = Vec::new();
+        for rename_pair in &self.rename {
+            let (module, name) = parse_rename_pair(rename_pair)?;
+            renames.push((RenameOrigin::Array, module, name));
+        }
+        renames.extend(
+            self.renames
+                .iter()
+                .map(|(module, name)| (RenameOrigin::Table, module.clone(), name.clone())),
+        );
+        for (index, rename_file) in self.rename_files.iter().enumerate() {
+            let path = project_root.join(rename_file);
+            let content = std::fs::read_to_string(&path).map_err(|err| RenameError::File {
+                path: path.display().to_string(),
+                message: err.to_string(),
+            })?;
+            for line in content.lines() {
+                let line = line.split('#').next().unwrap_or_default().trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let (module, name) = parse_rename_pair(line)?;
+                renames.push((RenameOrigin::File(index), module, name));
+            }
+        }
+        merge_renames_with_origins(renames, self.preserve_unknown_chars)
+    }
+
+    /// Suggest a declared dependency which the given name is likely a typo of
+    pub fn suggest_dependency<'a, I>(&self, name: &str, declared: I) -> Option<&'a str>
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        suggest_similar_name(
+            name,
+            declared,
+            self.suggestion_max_distance,
+            self.suggestion_min_length,
+        )
+    }
+
+    /// Whether source paths should be matched case-insensitively.
+    /// Unless configured, this assumes the platform's default filesystem behavior.
+    pub fn is_case_insensitive_fs(&self) -> bool {
+        self.case_insensitive_paths
+            .unwrap_or(cfg!(any(target_os = "macos", target_os = "windows")))
+    }
+
+    /// Whether dependencies from the named group (or environment) should be included
+    pub fn is_dependency_group_included(&self, group: &str) -> bool {
+        is_dependency_group_selected(&self.include_dependency_groups, group)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&["myorg"], "myorg.utils", true)]
+    #[case(&["myorg"], "myorg", true)]
+    #[case(&["myorg.*"], "myorg.utils", true)]
+    #[case(&["myorg"], "myorganization.utils", false)]
+    #[case(&[], "myorg.utils", false)]
+    fn test_is_first_party(
+        #[case] prefixes: &[&str],
+        #[case] module_path: &str,
+        #[case] expected: bool,
+    ) {
+        let config = ExternalDependencyConfig {
+            first_party_prefixes: prefixes.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        };
+        assert_eq!(config.is_first_party(module_path), expected);
+    }
+
+    #[rstest]
+    #[case(&["dev"], "dev", true)]
+    #[case(&["Dev_Tools"], "dev-tools", true)]
+    #[case(&["all"], "docs", true)]
+    #[case(&["dev"], "docs", false)]
+    #[case(&[], "dev", false)]
+    #[case(&["all", "!docs"], "docs", false)]
+    #[case(&["all", "!docs"], "documentation", false)]
+    #[case(&["test-*"], "test-unit", true)]
+    #[case(&["test-*"], "lint", false)]
+    #[case(&["dev"], "development", true)]
+    fn test_is_dependency_group_included(
+        #[case] groups: &[&str],
+        #[case] group: &str,
+        #[case] expected: bool,
+    ) {
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: groups.iter().map(|g| g.to_string()).collect(),
+            ..Default::default()
+        };
+        assert_eq!(config.is_dependency_group_included(group), expected);
+    }
+
+    #[rstest]
+    #[case(NameNormalization::Import, "Foo..Bar--Baz", "foo_bar_baz")]
+    #[case(NameNormalization::Pep503, "Foo..Bar--Baz", "foo-bar-baz")]
+    #[case(NameNormalization::Import, "Zope.Interface", "zope_interface")]
+    #[case(NameNormalization::Pep503, "Zope.Interface", "zope-interface")]
+    fn test_name_normalization(
+        #[case] mode: NameNormalization,
+        #[case] name: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(mode.normalize(name), expected);
+    }
+
+    #[rstest]
+    #[case("include_dependency_groups = true", &["all"])]
+    #[case("include_dependency_groups = false", &[])]
+    #[case("include_dependency_groups = [\"default\", \"test\"]", &["default", "test"])]
+    #[case("", &["default", "dev"])]
+    fn test_deserialize_include_dependency_groups(
+        #[case] content: &str,
+        #[case] expected: &[&str],
+    ) {
+        let config: ExternalDependencyConfig = toml::from_str(content).unwrap();
+        assert_eq!(config.include_dependency_groups, expected);
+    }
+
+    #[rstest]
+    #[case("pytest", PackageTreatment::Excluded)]
+    #[case("Pillow", PackageTreatment::Renamed("PIL".to_string()))]
+    #[case("myorg", PackageTreatment::Internal)]
+    #[case("requests", PackageTreatment::Normal)]
+    #[case("excluded-and-renamed", PackageTreatment::Excluded)]
+    #[case("renamed-and-internal", PackageTreatment::Renamed("renamed".to_string()))]
+    #[case("PyYAML", PackageTreatment::Renamed("yaml".to_string()))]
+    fn test_package_treatment(#[case] package_name: &str, #[case] expected: PackageTreatment) {
+        let config = ExternalDependencyConfig {
+            exclude: vec!["pytest".to_string(), "excluded_and_renamed".to_string()],
+            rename: vec![
+                "PIL:pillow".to_string(),
+                "excluded:excluded-and-renamed".to_string(),
+                "renamed:renamed_and_internal".to_string(),
+            ],
+            renames: BTreeMap::from([("yaml".to_string(), "pyyaml".to_string())]),
+            first_party_prefixes: vec!["myorg".to_string(), "renamed_and_internal".to_string()],
+            ..Default::default()
+        };
+        let renames = config.rename_map(Path::new(".")).unwrap();
+        assert_eq!(config.package_treatment(package_name, &renames), expected);
+    }
+
+    #[test]
+    fn test_rename_map_merges_sources_by_precedence() {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(
+            project.path().join("renames.txt"),
+            "# Generated from the lockfile\nPIL:pillow\nyaml_compat:PyYAML  # pinned\n\nattr:attrs\n",
+        )
+        .unwrap();
+        let config = ExternalDependencyConfig {
+            rename: vec!["PIL:Pillow".to_string()],
+            renames: BTreeMap::from([
+                ("yaml".to_string(), "pyyaml".to_string()),
+                ("Image".to_string(), "pillow".to_string()),
+            ]),
+            rename_files: vec![PathBuf::from("renames.txt")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.rename_map(project.path()).unwrap(),
+            HashMap::from([
+                ("pillow".to_string(), "PIL".to_string()),
+                ("pyyaml".to_string(), "yaml".to_string()),
+                ("attrs".to_string(), "attr".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_rename_map_with_origins() {
+        let project = tempfile::TempDir::with_prefix("tach-test").unwrap();
+        std::fs::write(project.path().join("renames.txt"), "attr:attrs\n").unwrap();
+        let config = ExternalDependencyConfig {
+            rename: vec!["PIL:Pillow".to_string()],
+            renames: BTreeMap::from([
+                ("yaml".to_string(), "pyyaml".to_string()),
+                ("Image".to_string(), "pillow".to_string()),
+            ]),
+            rename_files: vec![PathBuf::from("renames.txt")],
+            ..Default::default()
+        };
+
+        let renames = config.rename_map_with_origins(project.path()).unwrap();
+        assert_eq!(
+            renames,
+            HashMap::from([
+                (
+                    "pillow".to_string(),
+                    (RenameOrigin::Array, "PIL".to_string())
+                ),
+                (
+                    "pyyaml".to_string(),
+                    (RenameOrigin::Table, "yaml".to_string())
+                ),
+                (
+                    "attrs".to_string(),
+                    (RenameOrigin::File(0), "attr".to_string())
+                ),
+            ])
+        );
+        assert_eq!(RenameOrigin::File(0).to_string(), "rename_files[0]");
+    }
+
+    #[rstest]
+    #[case(vec!["PIL:pillow", "Image:Pillow"], Err(RenameError::Conflict {
+        package: "pillow".to_string(),
+        first: "PIL".to_string(),
+        second: "Image".to_string(),
+    }))]
+    #[case(vec!["PIL:pillow", "PIL:Pillow"], Ok(HashMap::from([("pillow".to_string(), "PIL".to_string())])))]
+    #[case(vec!["pillow"], Err(RenameError::InvalidFormat("pillow".to_string())))]
+    fn test_rename_map_within_one_source(
+        #[case] rename: Vec<&str>,
+        #[case] expected: Result<HashMap<String, String>, RenameError>,
+    ) {
+        let config = ExternalDependencyConfig {
+            rename: rename.iter().map(|pair| pair.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(config.rename_map(Path::new(".")), expected);
+    }
 }
@@ -9,22 +9,28 @@ pub mod map;
 pub mod modules;
 pub mod plugins;
 pub mod project;
+pub mod report;
 pub mod respect_gitignore;
 pub mod root_module;
 pub mod rules;
+pub mod schema;
 pub mod utils;
 
-pub use cache::{CacheBackend, CacheConfig};
+pub use cache::{CacheBackend, CacheConfig, CompressionLevel};
 pub use domain::{ConfigLocation, DomainConfig, LocatedDomainConfig};
 pub use edit::ConfigEdit;
-pub use error::ConfigError;
-pub use external::ExternalDependencyConfig;
+pub use error::{ConfigError, RenameError};
+pub use external::{
+    ExternalDependencyConfig, MarkerEnvironment, NameNormalization, PackageTreatment, RenameOrigin,
+};
 pub use interfaces::{InterfaceConfig, InterfaceDataTypes};
 pub use layers::LayerConfig;
 pub use map::MapConfig;
 pub use modules::{DependencyConfig, ModuleConfig, serialize_modules_json};
 pub use plugins::PluginsConfig;
 pub use project::ProjectConfig;
+pub use report::bug_report_config;
 pub use respect_gitignore::RespectGitIgnore;
 pub use root_module::RootModuleTreatment;
-pub use rules::{RuleSetting, RulesConfig};
+pub use rules::{RuleSetting, RulesConfig, RulesOverride};
+pub use schema::config_json_schema;
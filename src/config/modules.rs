@@ -12,6 +12,7 @@ use serde::ser::{Error, SerializeSeq, SerializeStruct};
 use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 
 use super::root_module::ROOT_MODULE_SENTINEL_TAG;
+use super::rules::RulesOverride;
 use crate::filesystem::module_path_is_included_in_paths;
 use crate::resolvers::ModuleGlob;
 
@@ -189,6 +190,13 @@ pub struct ModuleConfig {
     #[serde(default, skip_serializing_if = "Not::not")]
     #[pyo3(get)]
     pub unchecked: bool,
+    /// Whether this module is deprecated, so that its usage from other modules is reported
+    #[serde(default, skip_serializing_if = "Not::not")]
+    #[pyo3(get)]
+    pub deprecated: bool,
+    /// Rule settings which override the project's rules within this module
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<RulesOverride>,
     // Hidden field to track grouping
     #[serde(skip)]
     group_id: Option<usize>,
@@ -210,6 +218,8 @@ impl Default for ModuleConfig {
             utility: Default::default(),
             strict: Default::default(),
             unchecked: Default::default(),
+            deprecated: Default::default(),
+            rules: Default::default(),
             group_id: Default::default(),
             origin: Default::default(),
         }
@@ -239,6 +249,8 @@ impl ModuleConfig {
             utility,
             strict: false,
             unchecked,
+            deprecated: false,
+            rules: None,
             group_id: None,
             origin: None,
         }
@@ -262,6 +274,14 @@ impl ModuleConfig {
         }
     }
 
+    pub fn with_rules(self, rules: Option<RulesOverride>) -> Self {
+        Self { rules, ..self }
+    }
+
+    pub fn with_deprecated(self, deprecated: bool) -> Self {
+        Self { deprecated, ..self }
+    }
+
     pub fn with_copied_origin(self, other: &Self) -> Self {
         Self {
             origin: other.origin.clone(),
@@ -300,6 +320,8 @@ impl ModuleConfig {
             utility: false,
             strict: false,
             unchecked: false,
+            deprecated: false,
+            rules: None,
             group_id: None,
             origin: None,
         }
@@ -413,6 +435,10 @@ struct BulkModule {
     utility: bool,
     #[serde(default, skip_serializing_if = "Not::not")]
     unchecked: bool,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    deprecated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rules: Option<RulesOverride>,
 }
 
 impl TryFrom<&[&ModuleConfig]> for BulkModule {
@@ -434,6 +460,8 @@ impl TryFrom<&[&ModuleConfig]> for BulkModule {
             visibility: first.visibility.clone(),
             utility: first.utility,
             unchecked: first.unchecked,
+            deprecated: first.deprecated,
+            rules: first.rules.clone(),
         };
 
         let mut unique_deps: HashSet<DependencyConfig> = HashSet::new();
@@ -490,6 +518,18 @@ impl TryFrom<&[&ModuleConfig]> for BulkModule {
                     module.path
                 ));
             }
+            if module.deprecated != first.deprecated {
+                return Err(format!(
+                    "Inconsistent deprecated flag in bulk module group for path {}",
+                    module.path
+                ));
+            }
+            if module.rules != first.rules {
+                return Err(format!(
+                    "Inconsistent rules in bulk module group for path {}",
+                    module.path
+                ));
+            }
         }
 
         if !unique_deps.is_empty() {
@@ -568,6 +608,8 @@ where
                     utility: bulk.utility,
                     strict: false,
                     unchecked: bulk.unchecked,
+                    deprecated: bulk.deprecated,
+                    rules: bulk.rules.clone(),
                     group_id: Some(i),
                     origin: None,
                 })
@@ -1,5 +1,6 @@
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::iter;
 use std::ops::Not;
 use std::path::PathBuf;
@@ -19,7 +20,7 @@ use super::map::MapConfig;
 use super::modules::{DependencyConfig, ModuleConfig, deserialize_modules, serialize_modules};
 use super::plugins::PluginsConfig;
 use super::root_module::RootModuleTreatment;
-use super::rules::RulesConfig;
+use super::rules::{RuleSetting, RulesConfig};
 use super::utils;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -70,6 +71,12 @@ pub struct ProjectConfig {
     #[serde(default = "default_source_roots")]
     #[pyo3(get, set)]
     pub source_roots: Vec<PathBuf>,
+    #[serde(
+        default = "default_source_extensions",
+        skip_serializing_if = "is_default_source_extensions"
+    )]
+    #[pyo3(get)]
+    pub source_extensions: Vec<String>,
     #[serde(default, skip_serializing_if = "Not::not")]
     #[pyo3(get)]
     pub exact: bool,
@@ -91,6 +98,12 @@ pub struct ProjectConfig {
     #[serde(default, skip_serializing_if = "utils::is_default")]
     #[pyo3(get)]
     pub respect_gitignore: RespectGitIgnore,
+    #[serde(
+        default = "utils::default_true",
+        skip_serializing_if = "utils::is_true"
+    )]
+    #[pyo3(get)]
+    pub relative_paths: bool,
     #[serde(skip)]
     #[pyo3(get)]
     pub use_regex_matching: bool,
@@ -116,6 +129,14 @@ pub fn default_source_roots() -> Vec<PathBuf> {
     vec![PathBuf::from(".")]
 }
 
+pub fn default_source_extensions() -> Vec<String> {
+    vec!["py".to_string()]
+}
+
+fn is_default_source_extensions(source_extensions: &[String]) -> bool {
+    source_extensions == default_source_extensions().as_slice()
+}
+
 pub const DEFAULT_EXCLUDE_PATHS: [&str; 5] = [
     "**/tests",
     "**/docs",
@@ -137,7 +158,9 @@ impl Default for ProjectConfig {
             // special defaults
             exclude: default_excludes(),
             source_roots: default_source_roots(),
+            source_extensions: default_source_extensions(),
             ignore_type_checking_imports: true,
+            relative_paths: true,
             // normal defaults
             modules: Default::default(),
             interfaces: Default::default(),
@@ -175,7 +198,8 @@ impl ProjectConfig {
             .map(|path| path.parent().unwrap().to_path_buf())
             .ok_or(ConfigError::ConfigDoesNotExist)?;
         let file_walker =
-            filesystem::FSWalker::try_new(&project_root, &self.exclude, self.respect_gitignore)?;
+            filesystem::FSWalker::try_new(&project_root, &self.exclude, self.respect_gitignore)?
+                .with_source_extensions(&self.source_extensions);
         let source_root_resolver = SourceRootResolver::new(&project_root, &file_walker);
         Ok(source_root_resolver.resolve(&self.source_roots)?)
     }
@@ -210,6 +234,43 @@ impl ProjectConfig {
             .chain(self.domains.iter().flat_map(|domain| domain.modules()))
     }
 
+    /// The severity of dependency cycles between modules. A `circular_dependencies` rule applies on its own.
+    /// Unset, cycles are errors when `forbid_circular_dependencies` is set, and are otherwise not checked.
+    pub fn circular_dependencies(&self) -> RuleSetting {
+        match &self.rules.circular_dependencies {
+            Some(setting) => setting.clone(),
+            None if self.forbid_circular_dependencies => RuleSetting::Error,
+            None => RuleSetting::Off,
+        }
+    }
+
+    /// The rules which apply within `module`: the project's rules, with the module's overrides merged over them.
+    /// Checks which need a single rule per dependency should use its accessor (e.g. [`Self::local_imports_for`]) instead.
+    pub fn rules_for_module(&self, module: &ModuleConfig) -> Cow<'_, RulesConfig> {
+        match &module.rules {
+            Some(overrides) => Cow::Owned(self.rules.with_overrides(overrides)),
+            None => Cow::Borrowed(&self.rules),
+        }
+    }
+
+    /// The `local_imports` rule which applies within `module`
+    pub fn local_imports_for<'a>(&'a self, module: &'a ModuleConfig) -> &'a RuleSetting {
+        module
+            .rules
+            .as_ref()
+            .and_then(|overrides| overrides.local_imports.as_ref())
+            .unwrap_or(&self.rules.local_imports)
+    }
+
+    /// The `deprecated_module_usage` rule which applies within `module`
+    pub fn deprecated_module_usage_for<'a>(&'a self, module: &'a ModuleConfig) -> &'a RuleSetting {
+        module
+            .rules
+            .as_ref()
+            .and_then(|overrides| overrides.deprecated_module_usage.as_ref())
+            .unwrap_or(&self.rules.deprecated_module_usage)
+    }
+
     pub fn all_interfaces(&self) -> impl Iterator<Item = &InterfaceConfig> {
         self.interfaces
             .iter()
@@ -544,6 +605,28 @@ impl ProjectConfig {
         })
     }
 
+    /// The project's rules, with `circular_dependencies` resolved by [`Self::circular_dependencies`]
+    pub fn resolved_rules(&self) -> RulesConfig {
+        RulesConfig {
+            circular_dependencies: Some(self.circular_dependencies()),
+            ..self.rules.clone()
+        }
+    }
+
+    /// The rules which apply within the module at `path`, or the project's rules if no such module is configured.
+    /// Like [`Self::resolved_rules`], `circular_dependencies` is resolved.
+    pub fn effective_rules(&self, path: &str) -> RulesConfig {
+        let mut rules = self
+            .all_modules()
+            .find(|module| module.path == path)
+            .map_or_else(
+                || self.rules.clone(),
+                |module| self.rules_for_module(module).into_owned(),
+            );
+        rules.circular_dependencies = Some(self.circular_dependencies());
+        rules
+    }
+
     pub fn module_paths(&self) -> Vec<String> {
         self.all_modules()
             .map(|module| module.path.clone())
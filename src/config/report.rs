@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use toml::Value;
+
+use super::error::ConfigError;
+use super::project::ProjectConfig;
+
+/// Replaces absolute paths outside of the project, which may reveal details of the reporter's machine
+const REDACTED_PATH: &str = "<redacted>";
+
+/// Relativize an absolute path to the project root, or redact it when it lies outside of the project
+fn relativize(project_root: &Path, value: &str) -> String {
+    let path = Path::new(value);
+    if !path.is_absolute() {
+        return value.to_string();
+    }
+    match path.strip_prefix(project_root) {
+        Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+        Ok(relative) => relative.display().to_string(),
+        Err(_) => REDACTED_PATH.to_string(),
+    }
+}
+
+fn relativize_value(project_root: &Path, value: &mut Value) {
+    match value {
+        Value::String(string) => *string = relativize(project_root, string),
+        Value::Array(array) => array
+            .iter_mut()
+            .for_each(|item| relativize_value(project_root, item)),
+        Value::Table(table) => table
+            .values_mut()
+            .for_each(|item| relativize_value(project_root, item)),
+        _ => {}
+    }
+}
+
+/// Serialize the configuration which affects checks (root module treatment, resolved source roots,
+/// rules, cache and external settings) into TOML suitable for pasting into a bug report.
+/// Settings left at their defaults are omitted, and absolute paths are made relative
+/// to the project root, or redacted when they lie outside of it.
+pub fn bug_report_config(project_config: &ProjectConfig) -> Result<String, ConfigError> {
+    let project_root = project_config
+        .location
+        .as_ref()
+        .and_then(|path| path.parent())
+        .ok_or(ConfigError::ConfigDoesNotExist)?;
+    let source_roots = project_config
+        .absolute_source_roots()?
+        .iter()
+        .map(|source_root| Value::String(source_root.display().to_string()))
+        .collect();
+
+    let mut report = toml::Table::new();
+    report.insert(
+        "root_module".to_string(),
+        Value::try_from(project_config.root_module)?,
+    );
+    report.insert("source_roots".to_string(), Value::Array(source_roots));
+    report.insert("rules".to_string(), Value::try_from(&project_config.rules)?);
+    report.insert("cache".to_string(), Value::try_from(&project_config.cache)?);
+    report.insert(
+        "external".to_string(),
+        Value::try_from(&project_config.external)?,
+    );
+
+    let mut report = Value::Table(report);
+    relativize_value(project_root, &mut report);
+    Ok(toml::to_string(&report)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{RuleSetting, RulesConfig};
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bug_report_config_relativizes_paths() {
+        let project = TempDir::with_prefix("tach-test").unwrap();
+        fs::create_dir_all(project.path().join("src")).unwrap();
+        let mut project_config = ProjectConfig {
+            source_roots: vec![PathBuf::from("src")],
+            rules: RulesConfig {
+                unused_ignore_directives: RuleSetting::Error,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        project_config.cache.env_dependencies = vec!["PYTHONPATH".to_string()];
+        project_config.external.pip_install_files = vec![
+            project.path().join("Dockerfile"),
+            PathBuf::from("/somewhere/else/setup.sh"),
+        ];
+        project_config.set_location(project.path().join("tach.toml"));
+
+        let report = bug_report_config(&project_config).unwrap();
+        let report: toml::Table = toml::from_str(&report).unwrap();
+
+        for section in ["root_module", "source_roots", "rules", "cache", "external"] {
+            assert!(report.contains_key(section), "missing '{section}'");
+        }
+        assert_eq!(report["source_roots"], Value::Array(vec!["src".into()]));
+        assert_eq!(
+            report["external"]["pip_install_files"],
+            Value::Array(vec!["Dockerfile".into(), REDACTED_PATH.into()])
+        );
+    }
+}
@@ -1,11 +1,16 @@
 use pyo3::{prelude::*, types::PyString};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use super::error::ConfigError;
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum RuleSetting {
     Error,
     Warn,
+    /// Report findings without ever failing the check
+    Info,
     Off,
 }
 
@@ -23,7 +28,7 @@ impl RuleSetting {
         Self::Error
     }
 
-    fn is_error(&self) -> bool {
+    pub fn is_error(&self) -> bool {
         *self == Self::Error
     }
 
@@ -34,6 +39,15 @@ impl RuleSetting {
     pub fn is_off(&self) -> bool {
         *self == Self::Off
     }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Off => "off",
+        }
+    }
 }
 
 impl<'py> IntoPyObject<'py> for RuleSetting {
@@ -44,11 +58,7 @@ impl<'py> IntoPyObject<'py> for RuleSetting {
         self,
         py: Python<'py>,
     ) -> Result<Self::Output, <RuleSetting as IntoPyObject<'py>>::Error> {
-        match self {
-            Self::Error => "error".into_pyobject(py),
-            Self::Warn => "warn".into_pyobject(py),
-            Self::Off => "off".into_pyobject(py),
-        }
+        self.as_str().into_pyobject(py)
     }
 }
 
@@ -75,6 +85,18 @@ pub struct RulesConfig {
         skip_serializing_if = "RuleSetting::is_error"
     )]
     pub local_imports: RuleSetting,
+    /// Unset, cycles are errors when `forbid_circular_dependencies` is set, and are otherwise not checked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circular_dependencies: Option<RuleSetting>,
+    #[serde(
+        default = "RuleSetting::warn",
+        skip_serializing_if = "RuleSetting::is_warn"
+    )]
+    pub deprecated_module_usage: RuleSetting,
+    /// A regex which the reason of every ignore directive must match,
+    /// when `require_ignore_directive_reasons` is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_directive_reason_pattern: Option<String>,
 }
 
 impl Default for RulesConfig {
@@ -84,6 +106,160 @@ impl Default for RulesConfig {
             require_ignore_directive_reasons: RuleSetting::off(),
             unused_external_dependencies: RuleSetting::error(),
             local_imports: RuleSetting::error(),
+            circular_dependencies: None,
+            deprecated_module_usage: RuleSetting::warn(),
+            ignore_directive_reason_pattern: None,
         }
     }
 }
+
+/// Rule settings which apply within a single module, overriding the project's [`RulesConfig`].
+/// Unset rules inherit the project's setting.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RulesOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unused_ignore_directives: Option<RuleSetting>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_ignore_directive_reasons: Option<RuleSetting>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unused_external_dependencies: Option<RuleSetting>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_imports: Option<RuleSetting>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated_module_usage: Option<RuleSetting>,
+}
+
+impl RulesConfig {
+    /// These rules, with each rule set in `overrides` replacing its setting here
+    pub fn with_overrides(&self, overrides: &RulesOverride) -> Self {
+        let merge = |setting: &RuleSetting, override_setting: &Option<RuleSetting>| {
+            override_setting.clone().unwrap_or_else(|| setting.clone())
+        };
+        Self {
+            unused_ignore_directives: merge(
+                &self.unused_ignore_directives,
+                &overrides.unused_ignore_directives,
+            ),
+            require_ignore_directive_reasons: merge(
+                &self.require_ignore_directive_reasons,
+                &overrides.require_ignore_directive_reasons,
+            ),
+            unused_external_dependencies: merge(
+                &self.unused_external_dependencies,
+                &overrides.unused_external_dependencies,
+            ),
+            local_imports: merge(&self.local_imports, &overrides.local_imports),
+            // Cycles span several modules, so no single module may override this rule
+            circular_dependencies: self.circular_dependencies.clone(),
+            deprecated_module_usage: merge(
+                &self.deprecated_module_usage,
+                &overrides.deprecated_module_usage,
+            ),
+            ignore_directive_reason_pattern: self.ignore_directive_reason_pattern.clone(),
+        }
+    }
+
+    /// Compile `ignore_directive_reason_pattern`, if set
+    pub fn ignore_directive_reason_regex(&self) -> Result<Option<Regex>, ConfigError> {
+        self.ignore_directive_reason_pattern
+            .as_ref()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|source| {
+                    ConfigError::InvalidIgnoreDirectiveReasonPattern {
+                        pattern: pattern.clone(),
+                        source,
+                    }
+                })
+            })
+            .transpose()
+    }
+
+    /// The name of each rule, as written in configuration
+    pub const RULE_NAMES: [&'static str; 6] = [
+        "unused_ignore_directives",
+        "require_ignore_directive_reasons",
+        "unused_external_dependencies",
+        "local_imports",
+        "circular_dependencies",
+        "deprecated_module_usage",
+    ];
+
+    /// Each rule's name, as written in configuration, along with its setting.
+    /// An unset `circular_dependencies` is reported as `off`, since resolving it needs the project's
+    /// `forbid_circular_dependencies`; see `ProjectConfig::resolved_rules`.
+    pub fn settings(&self) -> [(&'static str, &RuleSetting); 6] {
+        [
+            ("unused_ignore_directives", &self.unused_ignore_directives),
+            (
+                "require_ignore_directive_reasons",
+                &self.require_ignore_directive_reasons,
+            ),
+            (
+                "unused_external_dependencies",
+                &self.unused_external_dependencies,
+            ),
+            ("local_imports", &self.local_imports),
+            (
+                "circular_dependencies",
+                self.circular_dependencies
+                    .as_ref()
+                    .unwrap_or(&RuleSetting::Off),
+            ),
+            ("deprecated_module_usage", &self.deprecated_module_usage),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_overrides_inherits_unset_rules() {
+        let overrides: RulesOverride = toml::from_str("local_imports = \"warn\"").unwrap();
+        let rules = RulesConfig {
+            unused_ignore_directives: RuleSetting::Error,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            rules.with_overrides(&overrides),
+            RulesConfig {
+                unused_ignore_directives: RuleSetting::Error,
+                local_imports: RuleSetting::Warn,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_info_setting_round_trips() {
+        let rules: RulesConfig = toml::from_str("local_imports = \"info\"").unwrap();
+        assert_eq!(rules.local_imports, RuleSetting::Info);
+        assert_eq!(
+            toml::to_string(&rules).unwrap().trim(),
+            "local_imports = \"info\""
+        );
+    }
+
+    #[test]
+    fn test_ignore_directive_reason_regex() {
+        let rules = RulesConfig {
+            ignore_directive_reason_pattern: Some(r"^[A-Z]+-\d+".to_string()),
+            ..Default::default()
+        };
+        let pattern = rules.ignore_directive_reason_regex().unwrap().unwrap();
+        assert!(pattern.is_match("PROJ-1234: temporary workaround"));
+        assert!(!pattern.is_match("temporary workaround"));
+
+        let rules = RulesConfig {
+            ignore_directive_reason_pattern: Some("(unclosed".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            rules.ignore_directive_reason_regex(),
+            Err(ConfigError::InvalidIgnoreDirectiveReasonPattern { .. })
+        ));
+    }
+}
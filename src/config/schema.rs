@@ -0,0 +1,709 @@
+use pyo3::prelude::*;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use super::cache::{CacheBackend, CacheConfig, CompressionLevel};
+use super::external::{ExternalDependencyConfig, NameNormalization};
+use super::root_module::RootModuleTreatment;
+use super::rules::{RuleSetting, RulesConfig};
+
+const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+const RULE_SETTINGS: [RuleSetting; 4] = [
+    RuleSetting::Error,
+    RuleSetting::Warn,
+    RuleSetting::Info,
+    RuleSetting::Off,
+];
+
+const ROOT_MODULE_TREATMENTS: [RootModuleTreatment; 4] = [
+    RootModuleTreatment::Allow,
+    RootModuleTreatment::Forbid,
+    RootModuleTreatment::Ignore,
+    RootModuleTreatment::DependenciesOnly,
+];
+
+const NAME_NORMALIZATIONS: [NameNormalization; 2] =
+    [NameNormalization::Import, NameNormalization::Pep503];
+
+/// The value which serde writes for `value`, so the schema matches what configuration may contain
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).expect("configuration values serialize to JSON")
+}
+
+fn enum_schema<T: Serialize>(variants: &[T], default: Option<&T>, description: &str) -> Value {
+    let mut schema = json!({
+        "type": "string",
+        "enum": variants.iter().map(to_value).collect::<Vec<_>>(),
+        "description": description,
+    });
+    if let Some(default) = default {
+        schema["default"] = to_value(default);
+    }
+    schema
+}
+
+fn typed_schema<T: Serialize>(schema_type: &str, default: &T, description: &str) -> Value {
+    json!({
+        "type": schema_type,
+        "default": to_value(default),
+        "description": description,
+    })
+}
+
+fn string_array_schema<T: Serialize>(default: &T, description: &str) -> Value {
+    json!({
+        "type": "array",
+        "items": { "type": "string" },
+        "default": to_value(default),
+        "description": description,
+    })
+}
+
+fn object_schema(properties: Map<String, Value>) -> Value {
+    json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+fn rule_setting_schema(default: Option<&RuleSetting>, description: &str) -> Value {
+    enum_schema(&RULE_SETTINGS, default, description)
+}
+
+/// The JSON Schema of the `rules` table
+pub fn rules_config_schema() -> Value {
+    let defaults = RulesConfig::default();
+    let mut properties: Map<String, Value> = defaults
+        .settings()
+        .into_iter()
+        .map(|(name, setting)| {
+            let schema = if name == "circular_dependencies" {
+                rule_setting_schema(
+                    None,
+                    "The severity of 'circular_dependencies'. Unset, cycles are errors when 'forbid_circular_dependencies' is set",
+                )
+            } else {
+                rule_setting_schema(Some(setting), &format!("The severity of '{name}'"))
+            };
+            (name.to_string(), schema)
+        })
+        .collect();
+    properties.insert(
+        "ignore_directive_reason_pattern".to_string(),
+        json!({
+            "type": "string",
+            "description": "A regex which every ignore directive reason must match, when 'require_ignore_directive_reasons' is enabled",
+        }),
+    );
+    object_schema(properties)
+}
+
+fn cache_backend_schema(default: &CacheBackend) -> Value {
+    let http = object_schema(Map::from_iter([
+        (
+            "url".to_string(),
+            json!({
+                "type": "string",
+                "description": "The base URL of the cache server",
+            }),
+        ),
+        (
+            "strict".to_string(),
+            typed_schema(
+                "boolean",
+                &false,
+                "Fail on network errors instead of treating them as cache misses",
+            ),
+        ),
+        (
+            "auth_header_env".to_string(),
+            json!({
+                "type": "string",
+                "description": "The environment variable holding the value of the 'Authorization' header",
+            }),
+        ),
+    ]));
+    let s3 = object_schema(Map::from_iter([
+        (
+            "bucket".to_string(),
+            json!({
+                "type": "string",
+                "description": "The bucket which holds cached results",
+            }),
+        ),
+        (
+            "prefix".to_string(),
+            typed_schema(
+                "string",
+                &"",
+                "A prefix of the object key of each cached result",
+            ),
+        ),
+        (
+            "region".to_string(),
+            json!({
+                "type": "string",
+                "description": "The region of the bucket",
+            }),
+        ),
+        (
+            "endpoint".to_string(),
+            json!({
+                "type": "string",
+                "description": "The URL of an S3-compatible service to use instead of AWS",
+            }),
+        ),
+    ]));
+    let variant_schema = |name: &str, schema: Value, required: &[&str]| {
+        json!({
+            "type": "object",
+            "properties": { name: with_required(schema, required) },
+            "required": [name],
+            "additionalProperties": false,
+        })
+    };
+    json!({
+        "oneOf": [
+            {
+                "type": "string",
+                "enum": [to_value(&CacheBackend::Disk), to_value(&CacheBackend::Memory)],
+            },
+            variant_schema("http", http, &["url"]),
+            variant_schema("s3", s3, &["bucket", "region"]),
+        ],
+        "default": to_value(default),
+        "description": "Where cached results are stored",
+    })
+}
+
+/// Mark the given properties of an object schema as required
+fn with_required(mut schema: Value, required: &[&str]) -> Value {
+    schema["required"] = json!(required);
+    schema
+}
+
+/// The JSON Schema of the `cache` table
+pub fn cache_config_schema() -> Value {
+    let defaults = CacheConfig::default();
+    let properties = Map::from_iter([
+        (
+            "backend".to_string(),
+            cache_backend_schema(&defaults.backend),
+        ),
+        (
+            "file_dependencies".to_string(),
+            string_array_schema(
+                &defaults.file_dependencies,
+                "Glob patterns of files whose contents affect cached results",
+            ),
+        ),
+        (
+            "env_dependencies".to_string(),
+            string_array_schema(
+                &defaults.env_dependencies,
+                "Environment variables whose values affect cached results",
+            ),
+        ),
+        (
+            "irrelevant_rules".to_string(),
+            json!({
+                "type": "array",
+                "items": { "type": "string", "enum": RulesConfig::RULE_NAMES },
+                "default": to_value(&defaults.irrelevant_rules),
+                "description": "Rules whose severity does not affect cached results",
+            }),
+        ),
+        (
+            "ttl_seconds".to_string(),
+            json!({
+                "type": "integer",
+                "minimum": 0,
+                "description": "How long disk cache entries remain valid, in seconds. Entries never expire when unset",
+            }),
+        ),
+        (
+            "max_size_bytes".to_string(),
+            json!({
+                "type": "integer",
+                "minimum": 0,
+                "description": "The size past which least recently used disk cache entries are evicted. The cache is unbounded when unset",
+            }),
+        ),
+        (
+            "compression".to_string(),
+            json!({
+                "type": "integer",
+                "minimum": CompressionLevel::MIN,
+                "maximum": CompressionLevel::MAX,
+                "description": "The zstd compression level of disk cache entries. Entries are not compressed when unset",
+            }),
+        ),
+        (
+            "interpreter_sensitive".to_string(),
+            typed_schema(
+                "boolean",
+                &defaults.interpreter_sensitive,
+                "Whether the Python interpreter version affects cached results",
+            ),
+        ),
+        (
+            "interpreter_distributions".to_string(),
+            typed_schema(
+                "boolean",
+                &defaults.interpreter_distributions,
+                "Whether the installed distributions also affect cached results, when 'interpreter_sensitive' is set",
+            ),
+        ),
+    ]);
+    object_schema(properties)
+}
+
+/// The JSON Schema of the `external` table
+pub fn external_dependency_config_schema() -> Value {
+    let defaults = ExternalDependencyConfig::default();
+    let boolean = |default: &bool, description: &str| typed_schema("boolean", default, description);
+    let integer =
+        |default: &usize, description: &str| typed_schema("integer", default, description);
+    let properties = Map::from_iter([
+        (
+            "exclude",
+            string_array_schema(
+                &defaults.exclude,
+                "Distributions and modules which are never reported",
+            ),
+        ),
+        (
+            "rename",
+            string_array_schema(
+                &defaults.rename,
+                "Import names of packages, as 'module:package' pairs",
+            ),
+        ),
+        (
+            "renames",
+            json!({
+                "type": "object",
+                "additionalProperties": { "type": "string" },
+                "default": to_value(&defaults.renames),
+                "description": "Packages which provide each import name",
+            }),
+        ),
+        (
+            "rename_files",
+            string_array_schema(
+                &defaults.rename_files,
+                "Files which hold one 'module:package' rename per line",
+            ),
+        ),
+        (
+            "first_party_prefixes",
+            string_array_schema(
+                &defaults.first_party_prefixes,
+                "Module prefixes which are never treated as external",
+            ),
+        ),
+        (
+            "namespace_packages",
+            string_array_schema(
+                &defaults.namespace_packages,
+                "Namespace packages whose members may lie in different packages",
+            ),
+        ),
+        (
+            "detect_namespace_packages",
+            boolean(
+                &defaults.detect_namespace_packages,
+                "Treat top-level directories without an '__init__.py', which contain packages, as namespace packages",
+            ),
+        ),
+        (
+            "include_dependency_groups",
+            json!({
+                "type": ["array", "boolean"],
+                "items": { "type": "string" },
+                "default": to_value(&defaults.include_dependency_groups),
+                "description": "Dependency groups whose dependencies are declared dependencies",
+            }),
+        ),
+        (
+            "single_dependency_source",
+            boolean(
+                &defaults.single_dependency_source,
+                "Require each package to declare its dependencies in a single source",
+            ),
+        ),
+        (
+            "conda_lock",
+            boolean(&defaults.conda_lock, "Treat the packages pinned in 'conda-lock.yml' as declared"),
+        ),
+        (
+            "pipfile_include_dev",
+            boolean(
+                &defaults.pipfile_include_dev,
+                "Include development packages from Pipfiles",
+            ),
+        ),
+        (
+            "allow_transitive_imports",
+            boolean(
+                &defaults.allow_transitive_imports,
+                "Treat packages pinned by a lockfile as declared",
+            ),
+        ),
+        (
+            "validate_renames",
+            boolean(&defaults.validate_renames, "Report renames which match no declared dependency or source module"),
+        ),
+        (
+            "case_insensitive_paths",
+            json!({
+                "type": "boolean",
+                "description": "Match source paths case-insensitively. Detected from the filesystem when unset",
+            }),
+        ),
+        (
+            "unused_check_exempt_groups",
+            string_array_schema(
+                &defaults.unused_check_exempt_groups,
+                "Dependency groups which are never reported as unused",
+            ),
+        ),
+        (
+            "missing_group_severities",
+            json!({
+                "type": "object",
+                "additionalProperties": rule_setting_schema(None, "The severity of importing a package which is only declared in this group"),
+                "default": to_value(&defaults.missing_group_severities),
+                "description": "Severities for imports of packages which are only declared in a dependency group",
+            }),
+        ),
+        (
+            "collapse_unused_groups",
+            boolean(
+                &defaults.collapse_unused_groups,
+                "Report a single diagnostic for a dependency group which is entirely unused",
+            ),
+        ),
+        (
+            "tool_plugins",
+            boolean(&defaults.tool_plugins, "Treat linter plugins listed in 'setup.cfg' or '.flake8' as available"),
+        ),
+        (
+            "uv_support",
+            boolean(&defaults.uv_support, "Treat uv constraint and override dependencies as available"),
+        ),
+        (
+            "use_installed_distributions",
+            boolean(
+                &defaults.use_installed_distributions,
+                "Map imports using the installed distributions",
+            ),
+        ),
+        (
+            "validate_installed_entries",
+            boolean(
+                &defaults.validate_installed_entries,
+                "Report 'rename' and 'exclude' entries which match no declared or installed distribution",
+            ),
+        ),
+        (
+            "pip_install_files",
+            string_array_schema(
+                &defaults.pip_install_files,
+                "Files whose 'pip install' commands declare dependencies",
+            ),
+        ),
+        (
+            "allow_external_source_paths",
+            boolean(
+                &defaults.allow_external_source_paths,
+                "Allow source paths which lie outside of their package",
+            ),
+        ),
+        (
+            "strict_source_paths",
+            boolean(
+                &defaults.strict_source_paths,
+                "Report source paths which lie outside of their package as errors",
+            ),
+        ),
+        (
+            "suggestion_max_distance",
+            integer(
+                &defaults.suggestion_max_distance,
+                "The largest edit distance of suggested dependency names",
+            ),
+        ),
+        (
+            "suggestion_min_length",
+            integer(
+                &defaults.suggestion_min_length,
+                "The shortest import name which receives suggestions",
+            ),
+        ),
+        (
+            "name_normalization",
+            enum_schema(
+                &NAME_NORMALIZATIONS,
+                Some(&defaults.name_normalization),
+                "How distribution names are normalized",
+            ),
+        ),
+        (
+            "max_dependency_group_depth",
+            integer(
+                &defaults.max_dependency_group_depth,
+                "How deeply dependency groups may include each other",
+            ),
+        ),
+        (
+            "target_environment",
+            object_schema(Map::from_iter(
+                ["python_version", "sys_platform", "os_name"].map(|marker| {
+                    (
+                        marker.to_string(),
+                        json!({
+                            "type": "string",
+                            "description": format!("The '{marker}' which environment markers are evaluated against"),
+                        }),
+                    )
+                }),
+            )),
+        ),
+        (
+            "preserve_unknown_chars",
+            boolean(
+                &defaults.preserve_unknown_chars,
+                "Keep characters which are not separators in distribution names, rather than treating them as separators",
+            ),
+        ),
+        (
+            "source_layout_template",
+            json!({
+                "type": "string",
+                "description": "Source paths of workspace members, with '{member}' and '{name}' placeholders",
+            }),
+        ),
+        (
+            "strict_specifiers",
+            boolean(
+                &defaults.strict_specifiers,
+                "Fail when a dependency is declared with conflicting version specifiers",
+            ),
+        ),
+    ]
+    .map(|(name, schema)| (name.to_string(), schema)));
+    object_schema(properties)
+}
+
+/// The JSON Schema of the `root_module` setting
+pub fn root_module_treatment_schema() -> Value {
+    enum_schema(
+        &ROOT_MODULE_TREATMENTS,
+        Some(&RootModuleTreatment::default()),
+        "How the root module is treated",
+    )
+}
+
+/// A JSON Schema describing the rules, cache, external and root module settings of `tach.toml`.
+/// Other settings are not described, and are allowed.
+pub fn config_json_schema() -> Value {
+    json!({
+        "$schema": JSON_SCHEMA_DRAFT,
+        "title": "tach.toml",
+        "type": "object",
+        "properties": {
+            "root_module": root_module_treatment_schema(),
+            "rules": rules_config_schema(),
+            "cache": cache_config_schema(),
+            "external": external_dependency_config_schema(),
+        },
+    })
+}
+
+#[pyfunction(name = "config_json_schema", signature = (pretty_print = false))]
+pub fn config_json_schema_py(pretty_print: bool) -> String {
+    let schema = config_json_schema();
+    if pretty_print {
+        serde_json::to_string_pretty(&schema).unwrap()
+    } else {
+        serde_json::to_string(&schema).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::external::MarkerEnvironment;
+    use serde::de::DeserializeOwned;
+
+    /// Every property's schema default, written explicitly, leaves the section at its defaults
+    fn assert_defaults_match<T: DeserializeOwned + Default + PartialEq + std::fmt::Debug>(
+        schema: &Value,
+    ) {
+        for (name, property) in schema["properties"].as_object().unwrap() {
+            let Some(default) = property.get("default") else {
+                continue;
+            };
+            let section =
+                toml::Table::from_iter([(name.clone(), toml::Value::try_from(default).unwrap())]);
+            let config: T = toml::Value::Table(section).try_into().unwrap();
+            assert_eq!(config, T::default(), "default of '{name}'");
+        }
+    }
+
+    /// Every field of `value`, as serialized, is a property described by `schema`. Nested
+    /// objects are checked against their property's schema, and `oneOf` against a variant.
+    fn assert_fields_described(value: &Value, schema: &Value, path: &str) {
+        let Some(fields) = value.as_object() else {
+            return;
+        };
+        if let Some(variants) = schema.get("oneOf").and_then(Value::as_array) {
+            let variant = variants
+                .iter()
+                .find(|variant| {
+                    fields
+                        .keys()
+                        .all(|name| variant["properties"].get(name).is_some())
+                })
+                .unwrap_or_else(|| panic!("no variant of '{path}' describes {fields:?}"));
+            return assert_fields_described(value, variant, path);
+        }
+        // Tables keyed by user-chosen names (such as 'renames') have no properties
+        let Some(properties) = schema.get("properties") else {
+            return;
+        };
+        for (name, field) in fields {
+            let property = properties
+                .get(name)
+                .unwrap_or_else(|| panic!("'{path}.{name}' is not described by the schema"));
+            assert_fields_described(field, property, &format!("{path}.{name}"));
+        }
+    }
+
+    // These configs set every field to a non-default value, so that each is serialized.
+    // They deliberately avoid '..Default::default()', so a new field must be added here.
+
+    #[test]
+    fn test_schema_describes_every_rules_field() {
+        let rules = RulesConfig {
+            unused_ignore_directives: RuleSetting::Error,
+            require_ignore_directive_reasons: RuleSetting::Warn,
+            unused_external_dependencies: RuleSetting::Warn,
+            local_imports: RuleSetting::Off,
+            circular_dependencies: Some(RuleSetting::Warn),
+            deprecated_module_usage: RuleSetting::Error,
+            ignore_directive_reason_pattern: Some("^TICKET-".to_string()),
+        };
+        assert_fields_described(&to_value(&rules), &rules_config_schema(), "rules");
+    }
+
+    #[test]
+    fn test_schema_describes_every_cache_field() {
+        let backends = [
+            CacheBackend::Memory,
+            CacheBackend::Http {
+                url: "https://cache.example.com".to_string(),
+                strict: true,
+                auth_header_env: Some("TACH_CACHE_AUTH".to_string()),
+            },
+            CacheBackend::S3 {
+                bucket: "ci-artifacts".to_string(),
+                prefix: "tach/".to_string(),
+                region: "auto".to_string(),
+                endpoint: Some("https://minio.internal:9000".to_string()),
+            },
+        ];
+        for backend in backends {
+            let cache = CacheConfig {
+                backend,
+                file_dependencies: vec!["uv.lock".to_string()],
+                env_dependencies: vec!["CI".to_string()],
+                irrelevant_rules: vec!["unused_ignore_directives".to_string()],
+                ttl_seconds: Some(3600),
+                max_size_bytes: Some(1 << 30),
+                compression: Some(CompressionLevel::try_from(3).unwrap()),
+                interpreter_sensitive: false,
+                interpreter_distributions: true,
+            };
+            assert_fields_described(&to_value(&cache), &cache_config_schema(), "cache");
+        }
+    }
+
+    #[test]
+    fn test_schema_describes_every_external_field() {
+        let external = ExternalDependencyConfig {
+            exclude: vec!["pytest".to_string()],
+            rename: vec!["PIL:pillow".to_string()],
+            renames: [("yaml".to_string(), "pyyaml".to_string())].into(),
+            rename_files: vec!["renames.txt".into()],
+            first_party_prefixes: vec!["myorg".to_string()],
+            namespace_packages: vec!["myorg".to_string()],
+            detect_namespace_packages: true,
+            include_dependency_groups: vec!["docs".to_string()],
+            single_dependency_source: true,
+            conda_lock: true,
+            pipfile_include_dev: true,
+            allow_transitive_imports: true,
+            validate_renames: true,
+            case_insensitive_paths: Some(true),
+            unused_check_exempt_groups: vec!["dev".to_string()],
+            missing_group_severities: [("dev".to_string(), RuleSetting::Warn)].into(),
+            collapse_unused_groups: true,
+            tool_plugins: true,
+            uv_support: true,
+            use_installed_distributions: true,
+            validate_installed_entries: true,
+            pip_install_files: vec!["Dockerfile".into()],
+            allow_external_source_paths: true,
+            strict_source_paths: true,
+            suggestion_max_distance: 3,
+            suggestion_min_length: 6,
+            name_normalization: NameNormalization::Pep503,
+            max_dependency_group_depth: 8,
+            target_environment: MarkerEnvironment {
+                python_version: Some("3.12".to_string()),
+                sys_platform: Some("linux".to_string()),
+                os_name: Some("posix".to_string()),
+            },
+            preserve_unknown_chars: false,
+            source_layout_template: Some("src/{name}".to_string()),
+            strict_specifiers: true,
+        };
+        assert_fields_described(
+            &to_value(&external),
+            &external_dependency_config_schema(),
+            "external",
+        );
+    }
+
+    #[test]
+    fn test_schema_defaults_match_config_defaults() {
+        assert_defaults_match::<RulesConfig>(&rules_config_schema());
+        assert_defaults_match::<CacheConfig>(&cache_config_schema());
+        assert_defaults_match::<ExternalDependencyConfig>(&external_dependency_config_schema());
+    }
+
+    #[test]
+    fn test_schema_enum_values() {
+        let schema = config_json_schema();
+        assert_eq!(
+            schema["properties"]["rules"]["properties"]["local_imports"]["enum"],
+            json!(["error", "warn", "info", "off"])
+        );
+        let backend = &schema["properties"]["cache"]["properties"]["backend"];
+        assert_eq!(backend["default"], "disk");
+        assert_eq!(backend["oneOf"][0]["enum"], json!(["disk", "memory"]));
+        assert_eq!(
+            backend["oneOf"][1]["properties"]["http"]["required"],
+            json!(["url"])
+        );
+        assert_eq!(
+            backend["oneOf"][2]["properties"]["s3"]["required"],
+            json!(["bucket", "region"])
+        );
+        assert_eq!(schema["properties"]["root_module"]["default"], "ignore");
+    }
+}
@@ -129,7 +129,8 @@ impl DependentMap {
             project_root,
             &project_config.exclude,
             project_config.respect_gitignore,
-        )?;
+        )?
+        .with_source_extensions(&project_config.source_extensions);
         let source_root_resolver = SourceRootResolver::new(project_root, &file_walker);
         let source_roots = source_root_resolver.resolve(&project_config.source_roots)?;
         let extra_deps = ExtraDependencyMatcher::new(
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use ruff_text_size::TextSize;
 
@@ -114,7 +114,7 @@ where
                             package
                                 .name
                                 .as_ref()
-                                .map(|name| normalize_package_name(name))
+                                .map(|name| package_resolver.normalize_package_name(name))
                                 .unwrap_or_else(|| top_level_module_name.clone()),
                         ]
                     }
@@ -127,7 +127,7 @@ where
                 .map(|dist_names| {
                     dist_names
                         .iter()
-                        .map(|dist_name| normalize_package_name(dist_name))
+                        .map(|dist_name| package_resolver.normalize_package_name(dist_name))
                         .collect()
                 })
                 .unwrap_or(default_distribution_names);
@@ -139,3 +139,72 @@ where
         })
         .collect()
 }
+
+/// Compute the top-level import names provided by a set of declared distributions.
+///
+/// `renames` are 'module:distribution' pairs from the configuration, and `module_mappings`
+/// maps top-level modules to the distributions which provide them (e.g. 'PIL' to 'pillow').
+/// Distributions which are not mapped by either provide their own normalized name.
+pub fn provided_import_names(
+    dependencies: &HashSet<String>,
+    renames: &[String],
+    module_mappings: &HashMap<String, Vec<String>>,
+) -> HashSet<String> {
+    let dependencies: HashSet<String> = dependencies
+        .iter()
+        .map(|dep| normalize_package_name(dep))
+        .collect();
+    let mut mapped_dependencies = HashSet::new();
+    let mut import_names = HashSet::new();
+
+    let mappings = module_mappings
+        .iter()
+        .flat_map(|(module, dist_names)| {
+            dist_names
+                .iter()
+                .map(move |dist_name| (module.as_str(), dist_name.as_str()))
+        })
+        .chain(
+            renames
+                .iter()
+                .filter_map(|rename_pair| rename_pair.split_once(':')),
+        );
+    for (module, dist_name) in mappings {
+        let dist_name = normalize_package_name(dist_name);
+        if dependencies.contains(&dist_name) {
+            import_names.insert(module.to_string());
+            mapped_dependencies.insert(dist_name);
+        }
+    }
+
+    import_names.extend(dependencies.difference(&mapped_dependencies).cloned());
+    import_names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provided_import_names() {
+        let dependencies = HashSet::from([
+            "Pillow".to_string(),
+            "python-dateutil".to_string(),
+            "requests".to_string(),
+        ]);
+        let module_mappings = HashMap::from([
+            ("PIL".to_string(), vec!["pillow".to_string()]),
+            ("yaml".to_string(), vec!["PyYAML".to_string()]),
+        ]);
+        let renames = vec!["dateutil:python-dateutil".to_string()];
+
+        assert_eq!(
+            provided_import_names(&dependencies, &renames, &module_mappings),
+            HashSet::from([
+                "PIL".to_string(),
+                "dateutil".to_string(),
+                "requests".to_string()
+            ])
+        );
+    }
+}
@@ -1,16 +1,21 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 use pyo3::prelude::*;
 use serde::Serialize;
 use thiserror::Error;
 
-use crate::config::RuleSetting;
+use crate::config::{RuleSetting, RulesConfig};
 
 #[derive(Debug, Copy, Clone, Eq, PartialOrd, Ord, Serialize, PartialEq)]
 #[pyclass(eq, eq_int, module = "tach.extension")]
 pub enum Severity {
     Error,
     Warning,
+    Info,
 }
 
 impl Display for Severity {
@@ -18,6 +23,7 @@ impl Display for Severity {
         match self {
             Severity::Error => write!(f, "Error"),
             Severity::Warning => write!(f, "Warning"),
+            Severity::Info => write!(f, "Info"),
         }
     }
 }
@@ -29,6 +35,7 @@ impl TryFrom<&RuleSetting> for Severity {
         match setting {
             RuleSetting::Error => Ok(Self::Error),
             RuleSetting::Warn => Ok(Self::Warning),
+            RuleSetting::Info => Ok(Self::Info),
             RuleSetting::Off => Err(()),
         }
     }
@@ -65,6 +72,22 @@ pub enum ConfigurationDiagnostic {
 
     #[error("Skipped '{file_path}' due to an unknown error.")]
     SkippedUnknownError { file_path: String },
+
+    #[error("{message}")]
+    DependencyParsingWarning { kind: String, message: String },
+
+    #[error(
+        "Rename '{rename}' does not match any declared dependency or source module. It may be stale."
+    )]
+    DanglingRename { rename: String },
+
+    #[error(
+        "'{entry}' in external '{field}' does not match any declared or installed distribution. It may be a typo or a removed dependency."
+    )]
+    UnmatchedExternalEntry { field: String, entry: String },
+
+    #[error("Circular dependency detected: {}.", .cycle.join(" -> "))]
+    CircularDependency { cycle: Vec<String> },
 }
 
 #[derive(Error, Debug, Clone, Serialize, PartialEq)]
@@ -107,6 +130,15 @@ pub enum CodeDiagnostic {
         definition_module: String,
     },
 
+    #[error(
+        "Module '{usage_module}' uses '{dependency}' from deprecated module '{definition_module}'."
+    )]
+    DeprecatedModuleUsage {
+        dependency: String,
+        usage_module: String,
+        definition_module: String,
+    },
+
     #[error(
         "Cannot use '{dependency}'. Module '{usage_module}' cannot depend on '{definition_module}'."
     )]
@@ -148,10 +180,17 @@ pub enum CodeDiagnostic {
     #[error("Ignore directive is missing a reason.")]
     MissingIgnoreDirectiveReason(),
 
-    #[error("Dependency '{dependency}' is not declared in package '{package_name}'.")]
+    #[error("Ignore directive reason '{reason}' does not match the required pattern '{pattern}'.")]
+    IgnoreDirectiveReasonMismatch { reason: String, pattern: String },
+
+    #[error(
+        "Dependency '{dependency}' is not declared in package '{package_name}'.{}",
+        .suggestion.as_ref().map(|suggestion| format!(" Did you mean '{suggestion}'?")).unwrap_or_default()
+    )]
     UndeclaredExternalDependency {
         dependency: String,
         package_name: String,
+        suggestion: Option<String>,
     },
 
     #[error("External package '{package_module_name}' is not used in package '{package_name}'.")]
@@ -160,6 +199,16 @@ pub enum CodeDiagnostic {
         package_name: String,
     },
 
+    #[error(
+        "All packages in dependency group '{group}' are not used in package '{package_name}': {}.",
+        .members.join(", ")
+    )]
+    UnusedDependencyGroup {
+        group: String,
+        members: Vec<String>,
+        package_name: String,
+    },
+
     #[error(
         "Module '{usage_module}' does not declare a dependency on external package '{dependency}'."
     )]
@@ -181,21 +230,39 @@ impl CodeDiagnostic {
             self,
             CodeDiagnostic::UnusedIgnoreDirective()
                 | CodeDiagnostic::MissingIgnoreDirectiveReason()
+                | CodeDiagnostic::IgnoreDirectiveReasonMismatch { .. }
         )
     }
 
+    /// The name of the `RulesConfig` field which governs this diagnostic, if any
+    pub fn rule_name(&self) -> Option<&'static str> {
+        match self {
+            CodeDiagnostic::UnusedIgnoreDirective() => Some("unused_ignore_directives"),
+            CodeDiagnostic::MissingIgnoreDirectiveReason()
+            | CodeDiagnostic::IgnoreDirectiveReasonMismatch { .. } => {
+                Some("require_ignore_directive_reasons")
+            }
+            CodeDiagnostic::UnusedExternalDependency { .. }
+            | CodeDiagnostic::UnusedDependencyGroup { .. } => Some("unused_external_dependencies"),
+            CodeDiagnostic::DeprecatedModuleUsage { .. } => Some("deprecated_module_usage"),
+            _ => None,
+        }
+    }
+
     pub fn dependency(&self) -> Option<&str> {
         match self {
             CodeDiagnostic::PrivateDependency { dependency, .. }
             | CodeDiagnostic::InvalidDataTypeExport { dependency, .. }
             | CodeDiagnostic::UndeclaredDependency { dependency, .. }
             | CodeDiagnostic::DeprecatedDependency { dependency, .. }
+            | CodeDiagnostic::DeprecatedModuleUsage { dependency, .. }
             | CodeDiagnostic::ForbiddenDependency { dependency, .. }
             | CodeDiagnostic::LayerViolation { dependency, .. }
             | CodeDiagnostic::ClosedLayerViolation { dependency, .. }
             | CodeDiagnostic::UnnecessarilyIgnoredDependency { dependency, .. } => Some(dependency),
             CodeDiagnostic::UnusedIgnoreDirective() => None,
             CodeDiagnostic::MissingIgnoreDirectiveReason() => None,
+            CodeDiagnostic::IgnoreDirectiveReasonMismatch { .. } => None,
             CodeDiagnostic::UndeclaredExternalDependency { dependency, .. }
             | CodeDiagnostic::ModuleUndeclaredExternalDependency { dependency, .. }
             | CodeDiagnostic::ModuleForbiddenExternalDependency { dependency, .. } => {
@@ -205,6 +272,7 @@ impl CodeDiagnostic {
                 package_module_name,
                 ..
             } => Some(package_module_name),
+            CodeDiagnostic::UnusedDependencyGroup { .. } => None,
         }
     }
 
@@ -214,6 +282,7 @@ impl CodeDiagnostic {
             | CodeDiagnostic::InvalidDataTypeExport { usage_module, .. }
             | CodeDiagnostic::UndeclaredDependency { usage_module, .. }
             | CodeDiagnostic::DeprecatedDependency { usage_module, .. }
+            | CodeDiagnostic::DeprecatedModuleUsage { usage_module, .. }
             | CodeDiagnostic::ForbiddenDependency { usage_module, .. }
             | CodeDiagnostic::LayerViolation { usage_module, .. }
             | CodeDiagnostic::ClosedLayerViolation { usage_module, .. }
@@ -239,6 +308,9 @@ impl CodeDiagnostic {
             | CodeDiagnostic::DeprecatedDependency {
                 definition_module, ..
             }
+            | CodeDiagnostic::DeprecatedModuleUsage {
+                definition_module, ..
+            }
             | CodeDiagnostic::ForbiddenDependency {
                 definition_module, ..
             }
@@ -302,6 +374,9 @@ pub enum Diagnostic {
     Global {
         severity: Severity,
         details: DiagnosticDetails,
+        // Whether the severity comes from the `local_imports` rule
+        #[serde(skip)]
+        local_import: bool,
     },
     Located {
         file_path: PathBuf,
@@ -309,12 +384,18 @@ pub enum Diagnostic {
         original_line_number: Option<usize>, // Optional line number to point to the origin of the diagnostic
         severity: Severity,
         details: DiagnosticDetails,
+        #[serde(skip)]
+        local_import: bool,
     },
 }
 
 impl Diagnostic {
     pub fn new_global(severity: Severity, details: DiagnosticDetails) -> Self {
-        Self::Global { severity, details }
+        Self::Global {
+            severity,
+            details,
+            local_import: false,
+        }
     }
 
     pub fn new_located(
@@ -330,6 +411,7 @@ impl Diagnostic {
             file_path,
             line_number,
             original_line_number,
+            local_import: false,
         }
     }
 
@@ -345,6 +427,7 @@ impl Diagnostic {
             original_line_number,
             severity: Severity::Error,
             details,
+            local_import: false,
         }
     }
 
@@ -360,6 +443,7 @@ impl Diagnostic {
             original_line_number,
             severity: Severity::Warning,
             details,
+            local_import: false,
         }
     }
 
@@ -367,6 +451,7 @@ impl Diagnostic {
         Self::Global {
             severity: Severity::Error,
             details,
+            local_import: false,
         }
     }
 
@@ -374,6 +459,78 @@ impl Diagnostic {
         Self::Global {
             severity: Severity::Warning,
             details,
+            local_import: false,
+        }
+    }
+
+    /// Resolve the file path of a located diagnostic against the project root.
+    /// Paths which are already absolute are unchanged.
+    pub fn with_absolute_path(self, project_root: &Path) -> Self {
+        match self {
+            Self::Located {
+                file_path,
+                line_number,
+                original_line_number,
+                severity,
+                details,
+                local_import,
+            } => Self::Located {
+                file_path: project_root.join(file_path),
+                line_number,
+                original_line_number,
+                severity,
+                details,
+                local_import,
+            },
+            global => global,
+        }
+    }
+
+    /// Mark the diagnostic as reported for an import outside of the module's global scope,
+    /// so it is attributed to the `local_imports` rule
+    pub fn with_local_import(self) -> Self {
+        match self {
+            Self::Global {
+                severity, details, ..
+            } => Self::Global {
+                severity,
+                details,
+                local_import: true,
+            },
+            Self::Located {
+                file_path,
+                line_number,
+                original_line_number,
+                severity,
+                details,
+                ..
+            } => Self::Located {
+                file_path,
+                line_number,
+                original_line_number,
+                severity,
+                details,
+                local_import: true,
+            },
+        }
+    }
+
+    /// The name of the `RulesConfig` field which governs this diagnostic, if any
+    pub fn rule_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Global {
+                local_import: true, ..
+            }
+            | Self::Located {
+                local_import: true, ..
+            } => Some("local_imports"),
+            _ => match self.details() {
+                DiagnosticDetails::Code(details) => details.rule_name(),
+                DiagnosticDetails::Configuration(ConfigurationDiagnostic::CircularDependency {
+                    ..
+                }) => Some("circular_dependencies"),
+                _ => None,
+            },
         }
     }
 
@@ -512,6 +669,10 @@ impl Diagnostic {
         matches!(self.severity(), Severity::Warning)
     }
 
+    pub fn is_info(&self) -> bool {
+        matches!(self.severity(), Severity::Info)
+    }
+
     #[pyo3(name = "to_string")]
     pub fn to_pystring(&self) -> String {
         self.message()
@@ -535,3 +696,239 @@ pub fn serialize_diagnostics_json(diagnostics: Vec<Diagnostic>, pretty_print: bo
         serde_json::to_string(&diagnostics).unwrap()
     }
 }
+
+/// A non-fatal issue found while reading configuration or dependency manifests
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StructuredWarning {
+    pub kind: String,
+    pub message: String,
+}
+
+/// Diagnostics separated into parsing warnings and rule findings
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CheckOutput {
+    pub warnings: Vec<StructuredWarning>,
+    pub findings: Vec<Diagnostic>,
+}
+
+impl CheckOutput {
+    pub fn from_diagnostics(diagnostics: Vec<Diagnostic>) -> Self {
+        let mut warnings = Vec::new();
+        let mut findings = Vec::new();
+        for diagnostic in diagnostics {
+            match diagnostic.details() {
+                DiagnosticDetails::Configuration(
+                    ConfigurationDiagnostic::DependencyParsingWarning { kind, message },
+                ) => warnings.push(StructuredWarning {
+                    kind: kind.clone(),
+                    message: message.clone(),
+                }),
+                _ => findings.push(diagnostic),
+            }
+        }
+        Self { warnings, findings }
+    }
+}
+
+#[pyfunction(signature = (diagnostics, pretty_print = false))]
+pub fn serialize_check_output_json(diagnostics: Vec<Diagnostic>, pretty_print: bool) -> String {
+    let output = CheckOutput::from_diagnostics(diagnostics);
+    if pretty_print {
+        serde_json::to_string_pretty(&output).unwrap()
+    } else {
+        serde_json::to_string(&output).unwrap()
+    }
+}
+
+/// The effective severity of a rule, and how many diagnostics it produced
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RuleSummary {
+    pub severity: RuleSetting,
+    pub count: usize,
+}
+
+/// Aggregate diagnostics by the rule which governs them, keyed by `RulesConfig` field name.
+/// Every rule is present, even with no findings.
+pub fn summarize_rules(
+    diagnostics: &[Diagnostic],
+    rules: &RulesConfig,
+) -> BTreeMap<&'static str, RuleSummary> {
+    let mut summary: BTreeMap<&'static str, RuleSummary> = rules
+        .settings()
+        .into_iter()
+        .map(|(name, setting)| {
+            (
+                name,
+                RuleSummary {
+                    severity: setting.clone(),
+                    count: 0,
+                },
+            )
+        })
+        .collect();
+
+    for diagnostic in diagnostics {
+        if let Some(rule_summary) = diagnostic
+            .rule_name()
+            .and_then(|rule_name| summary.get_mut(rule_name))
+        {
+            rule_summary.count += 1;
+        }
+    }
+
+    summary
+}
+
+#[pyfunction(signature = (diagnostics, rules, pretty_print = false))]
+pub fn serialize_rule_summary_json(
+    diagnostics: Vec<Diagnostic>,
+    rules: RulesConfig,
+    pretty_print: bool,
+) -> String {
+    let summary = summarize_rules(&diagnostics, &rules);
+    if pretty_print {
+        serde_json::to_string_pretty(&summary).unwrap()
+    } else {
+        serde_json::to_string(&summary).unwrap()
+    }
+}
+
+/// Exit code bits for errors governed by each rule, in `RulesConfig::RULE_NAMES` order
+/// (2, 4, 8, ...). Errors which no rule governs set the bit following the rules.
+/// The lowest bit is left unset, since an exit code of 1 means the check itself failed.
+pub fn rule_exit_code(diagnostics: &[Diagnostic]) -> i32 {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_error())
+        .fold(0, |exit_code, diagnostic| {
+            let bit = diagnostic
+                .rule_name()
+                .and_then(|rule_name| {
+                    RulesConfig::RULE_NAMES
+                        .iter()
+                        .position(|name| *name == rule_name)
+                })
+                .unwrap_or(RulesConfig::RULE_NAMES.len());
+            exit_code | (1 << (bit + 1))
+        })
+}
+
+#[pyfunction(name = "rule_exit_code")]
+pub fn rule_exit_code_py(diagnostics: Vec<Diagnostic>) -> i32 {
+    rule_exit_code(&diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_rules() {
+        let rules = RulesConfig {
+            unused_ignore_directives: RuleSetting::Error,
+            circular_dependencies: Some(RuleSetting::Error),
+            ..Default::default()
+        };
+        let unused_dependency = || {
+            Diagnostic::new_global_warning(DiagnosticDetails::Code(
+                CodeDiagnostic::UnusedExternalDependency {
+                    package_module_name: "unused".to_string(),
+                    package_name: "example".to_string(),
+                },
+            ))
+        };
+        let diagnostics = vec![
+            unused_dependency(),
+            unused_dependency(),
+            Diagnostic::new_located_error(
+                PathBuf::from("example.py"),
+                1,
+                None,
+                DiagnosticDetails::Code(CodeDiagnostic::UnusedIgnoreDirective()),
+            ),
+            Diagnostic::new_global_warning(DiagnosticDetails::Configuration(
+                ConfigurationDiagnostic::NoFirstPartyImportsFound(),
+            )),
+            Diagnostic::new_located_error(
+                PathBuf::from("example.py"),
+                3,
+                None,
+                DiagnosticDetails::Code(CodeDiagnostic::PrivateDependency {
+                    dependency: "a.b".to_string(),
+                    usage_module: "c".to_string(),
+                    definition_module: "a".to_string(),
+                }),
+            )
+            .with_local_import(),
+            Diagnostic::new_global_warning(DiagnosticDetails::Configuration(
+                ConfigurationDiagnostic::CircularDependency {
+                    cycle: vec!["a".to_string(), "b".to_string(), "a".to_string()],
+                },
+            )),
+        ];
+
+        let summary = serde_json::to_value(summarize_rules(&diagnostics, &rules)).unwrap();
+        assert_eq!(
+            summary,
+            serde_json::json!({
+                "unused_ignore_directives": {"severity": "error", "count": 1},
+                "require_ignore_directive_reasons": {"severity": "off", "count": 0},
+                "unused_external_dependencies": {"severity": "error", "count": 2},
+                "local_imports": {"severity": "error", "count": 1},
+                "circular_dependencies": {"severity": "error", "count": 1},
+                "deprecated_module_usage": {"severity": "warn", "count": 0},
+            })
+        );
+    }
+
+    #[test]
+    fn test_rule_exit_code() {
+        let private_dependency = || {
+            Diagnostic::new_located_error(
+                PathBuf::from("example.py"),
+                3,
+                None,
+                DiagnosticDetails::Code(CodeDiagnostic::PrivateDependency {
+                    dependency: "a.b".to_string(),
+                    usage_module: "c".to_string(),
+                    definition_module: "a".to_string(),
+                }),
+            )
+        };
+        assert_eq!(rule_exit_code(&[]), 0);
+        assert_eq!(
+            rule_exit_code(&[
+                private_dependency().with_local_import(),
+                Diagnostic::new_located_warning(
+                    PathBuf::from("example.py"),
+                    1,
+                    None,
+                    DiagnosticDetails::Code(CodeDiagnostic::UnusedIgnoreDirective()),
+                ),
+            ]),
+            16
+        );
+        assert_eq!(
+            rule_exit_code(&[
+                private_dependency().with_local_import(),
+                private_dependency()
+            ]),
+            16 | 128
+        );
+        assert_eq!(
+            rule_exit_code(&[Diagnostic::new_global_error(
+                DiagnosticDetails::Configuration(ConfigurationDiagnostic::CircularDependency {
+                    cycle: vec!["a".to_string(), "b".to_string(), "a".to_string()],
+                }),
+            )]),
+            32
+        );
+        assert_eq!(
+            rule_exit_code(&[Diagnostic::new_global(
+                Severity::Info,
+                DiagnosticDetails::Code(CodeDiagnostic::UnusedIgnoreDirective()),
+            )]),
+            0
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::parsing::{ProjectInfo, normalize_package_name};
+
+const REVIEW_MARKER: &str = "# REVIEW:";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ImportMapping {
+    /// The import is provided by a single known distribution
+    Known(String),
+    /// Several distributions provide the import, so the first is only a guess
+    Ambiguous(Vec<String>),
+    /// No known distribution provides the import
+    Unmapped,
+}
+
+pub(super) fn map_import(
+    import: &str,
+    project_info: &ProjectInfo,
+    module_mappings: &HashMap<String, Vec<String>>,
+) -> ImportMapping {
+    let normalized_import = normalize_package_name(import);
+    if let Some(dependency) = project_info
+        .dependencies
+        .iter()
+        .find(|dep| normalize_package_name(dep) == normalized_import)
+    {
+        return ImportMapping::Known(dependency.clone());
+    }
+
+    match module_mappings.get(import) {
+        Some(dist_names) => {
+            let dist_names: BTreeSet<&String> = dist_names.iter().collect();
+            match dist_names.len() {
+                0 => ImportMapping::Unmapped,
+                1 => ImportMapping::Known(dist_names.into_iter().next().unwrap().clone()),
+                _ => ImportMapping::Ambiguous(dist_names.into_iter().cloned().collect()),
+            }
+        }
+        None => ImportMapping::Unmapped,
+    }
+}
+
+/// Build suggested configuration text declaring the distributions behind `observed_imports`.
+///
+/// `observed_imports` are the top-level names of external imports found in the project's
+/// source paths, and `module_mappings` maps top-level modules to the installed distributions
+/// which provide them. The output holds a `[project]` dependencies block for 'pyproject.toml'
+/// and, when an import name differs from its distribution, a `[external]` rename block for
+/// 'tach.toml'. Imports without a single known distribution are marked with '# REVIEW:'.
+pub fn suggest_dependency_config(
+    project_info: &ProjectInfo,
+    observed_imports: &BTreeSet<String>,
+    module_mappings: &HashMap<String, Vec<String>>,
+) -> String {
+    let project_name = project_info.name.as_deref().map(normalize_package_name);
+    let mut dependencies: BTreeMap<String, Option<String>> = BTreeMap::new();
+    let mut unmapped: Vec<&str> = Vec::new();
+    let mut renames: BTreeSet<String> = BTreeSet::new();
+
+    for import in observed_imports {
+        if project_name.as_deref() == Some(normalize_package_name(import).as_str()) {
+            continue;
+        }
+        let (dist_name, review_note) = match map_import(import, project_info, module_mappings) {
+            ImportMapping::Known(dist_name) => (dist_name, None),
+            ImportMapping::Ambiguous(dist_names) => (
+                dist_names[0].clone(),
+                Some(format!(
+                    "'{}' is provided by multiple distributions: {}",
+                    import,
+                    dist_names.join(", ")
+                )),
+            ),
+            ImportMapping::Unmapped => {
+                unmapped.push(import);
+                continue;
+            }
+        };
+        if normalize_package_name(&dist_name) != normalize_package_name(import) {
+            renames.insert(format!("{import}:{dist_name}"));
+        }
+        let entry = dependencies.entry(dist_name).or_default();
+        if entry.is_none() {
+            *entry = review_note;
+        }
+    }
+
+    let mut output = String::from("[project]\ndependencies = [\n");
+    for (dist_name, review_note) in &dependencies {
+        if let Some(review_note) = review_note {
+            output.push_str(&format!("    {REVIEW_MARKER} {review_note}\n"));
+        }
+        output.push_str(&format!("    \"{dist_name}\",\n"));
+    }
+    for import in &unmapped {
+        output.push_str(&format!(
+            "    {REVIEW_MARKER} no known distribution provides '{import}'\n    # \"{import}\",\n"
+        ));
+    }
+    output.push_str("]\n");
+
+    if !renames.is_empty() {
+        output.push_str("\n[external]\nrename = [\n");
+        for rename in &renames {
+            output.push_str(&format!("    \"{rename}\",\n"));
+        }
+        output.push_str("]\n");
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn project_info(name: &str, dependencies: &[&str]) -> ProjectInfo {
+        ProjectInfo {
+            name: Some(name.to_string()),
+            dependencies: dependencies.iter().map(|dep| dep.to_string()).collect(),
+            dependency_sources: HashMap::new(),
+            dependency_order: dependencies.iter().map(|dep| dep.to_string()).collect(),
+            source_paths: Vec::new(),
+            import_roots: HashMap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_suggest_dependency_config() {
+        let project_info = project_info("myproject", &["requests"]);
+        let observed_imports = BTreeSet::from([
+            "PIL".to_string(),
+            "myproject".to_string(),
+            "requests".to_string(),
+            "totally_unknown".to_string(),
+        ]);
+        let module_mappings = HashMap::from([("PIL".to_string(), vec!["pillow".to_string()])]);
+
+        let suggestion =
+            suggest_dependency_config(&project_info, &observed_imports, &module_mappings);
+
+        assert_eq!(
+            suggestion,
+            "[project]\n\
+             dependencies = [\n    \
+             \"pillow\",\n    \
+             \"requests\",\n    \
+             # REVIEW: no known distribution provides 'totally_unknown'\n    \
+             # \"totally_unknown\",\n\
+             ]\n\
+             \n\
+             [external]\n\
+             rename = [\n    \
+             \"PIL:pillow\",\n\
+             ]\n"
+        );
+    }
+
+    #[test]
+    fn test_suggest_dependency_config_flags_ambiguous_mapping() {
+        let project_info = project_info("myproject", &[]);
+        let observed_imports = BTreeSet::from(["yaml".to_string()]);
+        let module_mappings = HashMap::from([(
+            "yaml".to_string(),
+            vec!["pyyaml".to_string(), "PyYAML-ft".to_string()],
+        )]);
+
+        let suggestion =
+            suggest_dependency_config(&project_info, &observed_imports, &module_mappings);
+        let flagged: HashSet<&str> = suggestion
+            .lines()
+            .filter(|line| line.trim_start().starts_with(REVIEW_MARKER))
+            .collect();
+
+        assert_eq!(
+            flagged,
+            HashSet::from([
+                "    # REVIEW: 'yaml' is provided by multiple distributions: PyYAML-ft, pyyaml"
+            ])
+        );
+        assert!(suggestion.contains("    \"PyYAML-ft\",\n"));
+    }
+}
@@ -0,0 +1,166 @@
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use super::allowlist::{ImportMapping, map_import};
+use super::parsing::ProjectInfo;
+
+/// How an import was resolved
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "classification", rename_all = "lowercase")]
+pub enum ImportClassification {
+    /// Provided by a module within the project, found beneath `source_path`
+    Internal { source_path: PathBuf },
+    /// Provided by a distribution. When several distributions provide the module, all are listed.
+    External { distributions: Vec<String> },
+    /// Neither found in the source paths nor provided by a known distribution
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClassifiedImport {
+    pub import: String,
+    #[serde(flatten)]
+    pub classification: ImportClassification,
+}
+
+/// Classify each discovered import (a full module path, such as `myproject.utils.helpers`).
+///
+/// Imports which resolve to a module in `project_info`'s source paths are internal, and report the
+/// owning source path. Otherwise the top-level module is looked up among the declared dependencies and
+/// in `module_mappings` (top-level modules to the distributions which provide them), and is unknown
+/// if neither has it. Imports are reported in sorted order.
+pub fn classify_imports(
+    project_info: &ProjectInfo,
+    imports: &BTreeSet<String>,
+    module_mappings: &HashMap<String, Vec<String>>,
+) -> Vec<ClassifiedImport> {
+    imports
+        .iter()
+        .map(|import| {
+            let classification = match project_info
+                .resolve_module(import)
+                .and_then(|path| project_info.owning_source_path(&path).cloned())
+            {
+                Some(source_path) => ImportClassification::Internal { source_path },
+                None => {
+                    let top_level_module = import.split('.').next().unwrap_or(import);
+                    match map_import(top_level_module, project_info, module_mappings) {
+                        ImportMapping::Known(distribution) => ImportClassification::External {
+                            distributions: vec![distribution],
+                        },
+                        ImportMapping::Ambiguous(distributions) => {
+                            ImportClassification::External { distributions }
+                        }
+                        ImportMapping::Unmapped => ImportClassification::Unknown,
+                    }
+                }
+            };
+            ClassifiedImport {
+                import: import.clone(),
+                classification,
+            }
+        })
+        .collect()
+}
+
+/// Render classified imports as an aligned plain-text table, one import per line
+pub fn format_import_classifications(classified_imports: &[ClassifiedImport]) -> String {
+    let width = classified_imports
+        .iter()
+        .map(|classified| classified.import.len())
+        .max()
+        .unwrap_or_default();
+    classified_imports
+        .iter()
+        .map(|classified| {
+            let details = match &classified.classification {
+                ImportClassification::Internal { source_path } => {
+                    format!("Internal  {}", source_path.display())
+                }
+                ImportClassification::External { distributions } => {
+                    format!("External  {}", distributions.join(", "))
+                }
+                ImportClassification::Unknown => "Unknown".to_string(),
+            };
+            format!("{:<width$}  {details}\n", classified.import)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ExternalDependencyConfig;
+    use crate::external::parsing::parse_project_root;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_classify_imports() {
+        let project = TempDir::with_prefix("tach-test").unwrap();
+        fs::write(
+            project.path().join("pyproject.toml"),
+            "[project]\nname = \"myproject\"\ndependencies = [\"requests\"]\n",
+        )
+        .unwrap();
+        fs::create_dir_all(project.path().join("src/myproject")).unwrap();
+        fs::write(project.path().join("src/myproject/__init__.py"), "").unwrap();
+        fs::write(project.path().join("src/myproject/utils.py"), "").unwrap();
+
+        let project_info = parse_project_root(project.path(), &ExternalDependencyConfig::default())
+            .unwrap()
+            .unwrap();
+        let imports = BTreeSet::from([
+            "myproject.utils".to_string(),
+            "requests.adapters".to_string(),
+            "totally_unknown".to_string(),
+        ]);
+
+        let classified = classify_imports(&project_info, &imports, &HashMap::new());
+        assert_eq!(
+            classified
+                .iter()
+                .map(|classified| &classified.classification)
+                .collect::<Vec<_>>(),
+            vec![
+                &ImportClassification::Internal {
+                    source_path: project.path().join("src")
+                },
+                &ImportClassification::External {
+                    distributions: vec!["requests".to_string()]
+                },
+                &ImportClassification::Unknown,
+            ]
+        );
+        assert!(
+            format_import_classifications(&classified).contains("totally_unknown    Unknown\n")
+        );
+    }
+
+    #[test]
+    fn test_classify_editable_install_as_internal() {
+        let project = TempDir::with_prefix("tach-test").unwrap();
+        fs::write(
+            project.path().join("requirements.txt"),
+            "-e ./libs/mypkg\nrequests\n",
+        )
+        .unwrap();
+        fs::create_dir_all(project.path().join("libs/mypkg/mypkg")).unwrap();
+        fs::write(project.path().join("libs/mypkg/mypkg/__init__.py"), "").unwrap();
+        fs::write(project.path().join("libs/mypkg/mypkg/core.py"), "").unwrap();
+
+        let project_info = parse_project_root(project.path(), &ExternalDependencyConfig::default())
+            .unwrap()
+            .unwrap();
+        assert!(!project_info.dependencies.contains("mypkg"));
+
+        let imports = BTreeSet::from(["mypkg.core".to_string()]);
+        assert_eq!(
+            classify_imports(&project_info, &imports, &HashMap::new())[0].classification,
+            ImportClassification::Internal {
+                source_path: project.path().join("libs/mypkg")
+            }
+        );
+    }
+}
@@ -1,4 +1,7 @@
+use itertools::Itertools;
+use serde::Serialize;
 use std::io;
+use std::path::PathBuf;
 use thiserror::Error;
 
 use crate::filesystem::FileSystemError;
@@ -11,6 +14,117 @@ pub enum ParsingError {
     Filesystem(#[from] FileSystemError),
     #[error("TOML parsing error: {0}")]
     TomlParse(#[from] toml::de::Error),
+    #[error("JSON parsing error: {0}")]
+    JsonParse(#[from] serde_json::Error),
     #[error("Missing field in TOML: {0}")]
     MissingField(String),
+    #[error(
+        "Dependency group '{group}' is included {depth} levels deep, which exceeds the configured 'max_dependency_group_depth'."
+    )]
+    GroupNestingTooDeep { group: String, depth: usize },
+    #[error(
+        "Source path '{path}' is outside of the project root. Set 'allow_external_source_paths' if this is intended."
+    )]
+    SourcePathOutsideProject { path: String },
+    #[error("Requirements file '{path}' includes itself through '-r' or '-c'.")]
+    RequirementsIncludeCycle { path: String },
+    #[error("Python parsing error: {0}")]
+    PythonParse(#[from] crate::python::error::ParsingError),
+    #[error("No 'setup(...)' call was found in '{path}'.")]
+    SetupCallNotFound { path: String },
+    #[error(
+        "'{path}' lists '{field}' in 'project.dynamic', but they could not be resolved. Declare them under '[tool.setuptools.dynamic]', or list them statically."
+    )]
+    DynamicDependenciesUnresolved { field: String, path: String },
+    #[error(
+        "Dependency '{package}' is declared with conflicting version specifiers: {}.",
+        .declarations.join(", ")
+    )]
+    ConflictingDependencySpecifiers {
+        package: String,
+        declarations: Vec<String>,
+    },
+}
+
+/// A workspace member whose dependency manifest could not be parsed
+#[derive(Error, Debug)]
+#[error("Failed to parse workspace member '{}': {error}", member.display())]
+pub struct MemberParsingError {
+    pub member: PathBuf,
+    #[source]
+    pub error: ParsingError,
+}
+
+/// Every workspace member which failed to parse, in the order the members were given
+#[derive(Error, Debug)]
+#[error("{}", .0.iter().map(ToString::to_string).join("\n"))]
+pub struct WorkspaceParsingError(pub Vec<MemberParsingError>);
+
+/// Non-fatal issues found while extracting dependency information
+#[derive(Error, Debug, Clone, Serialize, PartialEq)]
+pub enum ParsingWarning {
+    #[error(
+        "Dependencies are declared in both '{pyproject_path}' and '{requirements_path}'. Consider keeping a single source of truth."
+    )]
+    MultipleDependencySources {
+        pyproject_path: String,
+        requirements_path: String,
+    },
+    #[error(
+        "Both project dependencies and Poetry dependencies detected. Using project dependencies."
+    )]
+    ProjectAndPoetryDependencies,
+    #[error(
+        "'[tool.poetry.dev-dependencies]' is deprecated. Use '[tool.poetry.group.dev.dependencies]' instead."
+    )]
+    PoetryLegacyDevDependencies,
+    #[error("Skipping dependency entry '{entry}', which does not name a package.")]
+    EmptyDependency { entry: String },
+    #[error(
+        "Source path '{path}' is outside of the project root. Set 'allow_external_source_paths' if this is intended."
+    )]
+    SourcePathOutsideProject { path: String },
+    #[error(
+        "Dependency entry '{entry}' is a nested array. Its dependencies were read, but the manifest should list them directly."
+    )]
+    NestedDependencyArray { entry: String },
+    #[error(
+        "Dependency '{package}' is declared with conflicting version specifiers: {}.",
+        .declarations.join(", ")
+    )]
+    ConflictingSpecifiers {
+        package: String,
+        declarations: Vec<String>,
+    },
+    #[error(
+        "'{pyproject_path}' sets 'tool.tach.external_dependencies', so its dependencies were not extracted automatically."
+    )]
+    DependenciesOverridden { pyproject_path: String },
+    #[error("Dynamic dependency file '{path}' does not exist, so its dependencies are skipped.")]
+    DynamicDependencyFileMissing { path: String },
+    #[error(
+        "'{field}' are listed in 'project.dynamic' and are only declared by 'setup.py', which Tach does not read them from."
+    )]
+    DynamicDependenciesNotRead { field: String },
+    #[error("Skipping '{argument}' of 'setup(...)', which is not a literal value.")]
+    DynamicSetupArgument { argument: String },
+}
+
+impl ParsingWarning {
+    /// A stable identifier for the kind of warning, for structured output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::MultipleDependencySources { .. } => "multiple_dependency_sources",
+            Self::ProjectAndPoetryDependencies => "project_and_poetry_dependencies",
+            Self::PoetryLegacyDevDependencies => "poetry_legacy_dev_dependencies",
+            Self::EmptyDependency { .. } => "empty_dependency",
+            Self::SourcePathOutsideProject { .. } => "source_path_outside_project",
+            Self::NestedDependencyArray { .. } => "nested_dependency_array",
+            Self::DynamicSetupArgument { .. } => "dynamic_setup_argument",
+            Self::ConflictingSpecifiers { .. } => "conflicting_specifiers",
+            Self::DependenciesOverridden { .. } => "dependencies_overridden",
+            Self::DynamicDependencyFileMissing { .. } => "dynamic_dependency_file_missing",
+            Self::DynamicDependenciesNotRead { .. } => "dynamic_dependencies_not_read",
+        }
+    }
 }
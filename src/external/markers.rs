@@ -0,0 +1,291 @@
+use std::cmp::Ordering;
+
+use crate::config::MarkerEnvironment;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(String),
+    Variable(String),
+    Operator(String),
+    And,
+    Or,
+    OpenParen,
+    CloseParen,
+}
+
+fn tokenize(marker: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = marker.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' || c == ')' {
+            chars.next();
+            tokens.push(if c == '(' {
+                Token::OpenParen
+            } else {
+                Token::CloseParen
+            });
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let literal: String = chars.by_ref().take_while(|&next| next != c).collect();
+            tokens.push(Token::Literal(literal));
+        } else if "<>=!~".contains(c) {
+            let mut operator = String::new();
+            while let Some(&next) = chars.peek().filter(|next| "<>=!~".contains(**next)) {
+                operator.push(next);
+                chars.next();
+            }
+            tokens.push(Token::Operator(operator));
+        } else if c.is_alphanumeric() || c == '_' {
+            let mut word = String::new();
+            while let Some(&next) = chars
+                .peek()
+                .filter(|next| next.is_alphanumeric() || **next == '_' || **next == '.')
+            {
+                word.push(next);
+                chars.next();
+            }
+            let token = match word.as_str() {
+                "and" => Token::And,
+                "or" => Token::Or,
+                "in" => Token::Operator("in".to_string()),
+                "not" => Token::Operator("not".to_string()),
+                _ => Token::Variable(word),
+            };
+            // `not` only appears as part of `not in`
+            if token == Token::Operator("in".to_string())
+                && tokens.last() == Some(&Token::Operator("not".to_string()))
+            {
+                tokens.pop();
+                tokens.push(Token::Operator("not in".to_string()));
+            } else {
+                tokens.push(token);
+            }
+        } else {
+            return None;
+        }
+    }
+    Some(tokens)
+}
+
+/// The value of a marker variable in the target environment, or `None` when it is unknown
+fn variable_value(name: &str, environment: &MarkerEnvironment) -> Option<String> {
+    match name {
+        "python_version" => environment.python_version.as_deref().map(|version| {
+            // Only the major and minor components are part of `python_version`
+            version.split('.').take(2).collect::<Vec<_>>().join(".")
+        }),
+        "python_full_version" => environment
+            .python_version
+            .clone()
+            .filter(|version| version.split('.').count() >= 3),
+        "sys_platform" => environment.sys_platform.clone(),
+        "os_name" => environment.os_name.clone(),
+        _ => None,
+    }
+}
+
+pub(super) fn version_components(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map_while(|component| {
+            let digits: String = component.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse().ok()
+        })
+        .collect()
+}
+
+pub(super) fn compare_versions(lhs: &[u64], rhs: &[u64]) -> Ordering {
+    let len = lhs.len().max(rhs.len());
+    let padded = |components: &[u64]| {
+        let mut components = components.to_vec();
+        components.resize(len, 0);
+        components
+    };
+    padded(lhs).cmp(&padded(rhs))
+}
+
+fn compare_version_strings(lhs: &str, operator: &str, rhs: &str) -> Option<bool> {
+    let lhs_components = version_components(lhs);
+    if let Some(prefix) = rhs.strip_suffix(".*") {
+        let prefix = version_components(prefix);
+        let matches = lhs_components.starts_with(&prefix);
+        return match operator {
+            "==" => Some(matches),
+            "!=" => Some(!matches),
+            _ => None,
+        };
+    }
+    let rhs_components = version_components(rhs);
+    if lhs_components.is_empty() || rhs_components.is_empty() {
+        return None;
+    }
+    let ordering = compare_versions(&lhs_components, &rhs_components);
+    match operator {
+        "==" => Some(ordering == Ordering::Equal),
+        "!=" => Some(ordering != Ordering::Equal),
+        "<" => Some(ordering == Ordering::Less),
+        "<=" => Some(ordering != Ordering::Greater),
+        ">" => Some(ordering == Ordering::Greater),
+        ">=" => Some(ordering != Ordering::Less),
+        "~=" => {
+            // Compatible release: at least `rhs`, with the same prefix up to its last component
+            let prefix = &rhs_components[..rhs_components.len().saturating_sub(1)];
+            Some(ordering != Ordering::Less && lhs_components.starts_with(prefix))
+        }
+        "===" => Some(lhs == rhs),
+        _ => None,
+    }
+}
+
+fn compare(lhs: &str, operator: &str, rhs: &str, is_version: bool) -> Option<bool> {
+    match operator {
+        "in" => Some(rhs.contains(lhs)),
+        "not in" => Some(!rhs.contains(lhs)),
+        _ if is_version => compare_version_strings(lhs, operator, rhs),
+        "==" | "===" => Some(lhs == rhs),
+        "!=" => Some(lhs != rhs),
+        _ => None,
+    }
+}
+
+/// A recursive descent parser over marker tokens, using three-valued logic:
+/// `Some(result)` when the marker can be decided, `None` when it depends on an unknown variable
+struct MarkerParser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    environment: &'a MarkerEnvironment,
+}
+
+impl MarkerParser<'_> {
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn parse_or(&mut self) -> Option<Option<bool>> {
+        let mut result = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            let rhs = self.parse_and()?;
+            result = match (result, rhs) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            };
+        }
+        Some(result)
+    }
+
+    fn parse_and(&mut self) -> Option<Option<bool>> {
+        let mut result = self.parse_atom()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            let rhs = self.parse_atom()?;
+            result = match (result, rhs) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            };
+        }
+        Some(result)
+    }
+
+    fn parse_atom(&mut self) -> Option<Option<bool>> {
+        if self.peek() == Some(&Token::OpenParen) {
+            self.position += 1;
+            let result = self.parse_or()?;
+            return (self.next() == Some(&Token::CloseParen)).then_some(result);
+        }
+        let (lhs, lhs_is_version) = self.parse_operand()?;
+        let Some(Token::Operator(operator)) = self.next().cloned() else {
+            return None;
+        };
+        let (rhs, rhs_is_version) = self.parse_operand()?;
+        Some(match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => {
+                compare(&lhs, &operator, &rhs, lhs_is_version || rhs_is_version)
+            }
+            _ => None,
+        })
+    }
+
+    /// The operand's value (if known), and whether it is compared as a version
+    fn parse_operand(&mut self) -> Option<(Option<String>, bool)> {
+        match self.next()?.clone() {
+            Token::Literal(literal) => Some((Some(literal), false)),
+            Token::Variable(name) => {
+                let is_version = name == "python_version" || name == "python_full_version";
+                Some((variable_value(&name, self.environment), is_version))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whether a dependency with the PEP 508 environment `marker` should be kept for `environment`.
+/// Only markers which evaluate to false exclude a dependency, so markers referencing variables
+/// outside of the configured environment (including `extra`), or which fail to parse, are kept.
+pub fn marker_matches(marker: &str, environment: &MarkerEnvironment) -> bool {
+    if environment.is_empty() {
+        return true;
+    }
+    let Some(tokens) = tokenize(marker) else {
+        return true;
+    };
+    let mut parser = MarkerParser {
+        tokens: &tokens,
+        position: 0,
+        environment,
+    };
+    match parser.parse_or() {
+        Some(result) if parser.position == tokens.len() => result != Some(false),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn environment() -> MarkerEnvironment {
+        MarkerEnvironment {
+            python_version: Some("3.12".to_string()),
+            sys_platform: Some("linux".to_string()),
+            os_name: None,
+        }
+    }
+
+    #[rstest]
+    #[case(r#"python_version < "3.11""#, false)]
+    #[case(r#"python_version >= "3.8""#, true)]
+    #[case(r#"python_version ~= "3.10""#, true)]
+    #[case(r#"python_version == "3.*""#, true)]
+    #[case(r#"sys_platform == "win32""#, false)]
+    #[case(r#"sys_platform != "win32" and python_version > '3.9'"#, true)]
+    #[case(r#"(sys_platform == "win32" or sys_platform == "darwin")"#, false)]
+    #[case(r#"sys_platform == "win32" or os_name == "nt""#, true)]
+    #[case(r#"sys_platform == "win32" and os_name == "nt""#, false)]
+    #[case(r#"extra == "docs""#, true)]
+    #[case(r#""linux" in sys_platform"#, true)]
+    #[case(r#"sys_platform not in "win32 cygwin""#, true)]
+    #[case("python_version <", true)]
+    fn test_marker_matches(#[case] marker: &str, #[case] expected: bool) {
+        assert_eq!(marker_matches(marker, &environment()), expected);
+    }
+
+    #[test]
+    fn test_marker_matches_keeps_everything_by_default() {
+        assert!(marker_matches(
+            r#"python_version < "3.0""#,
+            &MarkerEnvironment::default()
+        ));
+    }
+}
@@ -1,4 +1,11 @@
+pub mod allowlist;
+pub mod classify;
 pub mod error;
+pub mod markers;
 pub mod parsing;
+pub mod setup_py;
+pub mod specifiers;
+pub mod suggest;
+pub mod watch;
 
-pub use error::ParsingError;
+pub use error::{ParsingError, ParsingWarning};
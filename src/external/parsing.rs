@@ -1,62 +1,630 @@
-use std::collections::HashSet;
+use pyo3::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use toml::Value;
 
-use super::error;
+use super::error::{self, ParsingWarning};
+use super::markers::marker_matches;
+use super::setup_py::find_setup_call;
+use super::specifiers::{poetry_constraint_specifier, requirement_specifier, specifiers_conflict};
+use crate::config::external::is_dependency_group_selected;
+use crate::config::{ExternalDependencyConfig, MarkerEnvironment};
 
 pub type Result<T> = std::result::Result<T, error::ParsingError>;
 
+#[pyclass(module = "tach.extension")]
 pub struct ProjectInfo {
+    #[pyo3(get)]
     pub name: Option<String>,
+    #[pyo3(get)]
     pub dependencies: HashSet<String>,
+    pub dependency_sources: DependencySources,
+    /// Normalized dependency names, in the order they were first declared
+    #[pyo3(get)]
+    pub dependency_order: Vec<String>,
+    #[pyo3(get)]
     pub source_paths: Vec<PathBuf>,
+    /// The package which each relocated source path is installed beneath, such as `dist` for a
+    /// poetry package with `to = "dist"`. Source paths without an entry are installed at the top level.
+    pub import_roots: HashMap<PathBuf, String>,
+    pub warnings: Vec<ParsingWarning>,
 }
 
-pub fn parse_pyproject_toml(pyproject_path: &Path) -> Result<ProjectInfo> {
+#[pymethods]
+impl ProjectInfo {
+    #[new]
+    fn new(name: Option<String>, dependencies: Vec<String>, source_paths: Vec<PathBuf>) -> Self {
+        let mut dependency_order: Vec<String> = Vec::new();
+        for dep in dependencies.iter().map(|dep| normalize_package_name(dep)) {
+            if !dependency_order.contains(&dep) {
+                dependency_order.push(dep);
+            }
+        }
+        let dependencies: HashSet<String> = dependency_order.iter().cloned().collect();
+        let dependency_sources = dependencies
+            .iter()
+            .map(|dep| (dep.clone(), HashSet::from([DependencySource::Project])))
+            .collect();
+        Self {
+            name,
+            dependencies,
+            dependency_sources,
+            dependency_order,
+            source_paths,
+            import_roots: HashMap::new(),
+            warnings: vec![],
+        }
+    }
+}
+
+/// How a source path provides modules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceRoot {
+    /// A directory which is either a package itself, or contains packages and modules
+    Directory(PathBuf),
+    /// A single top-level module file, such as an entry of setuptools' `py-modules`
+    SingleModule { module: String, path: PathBuf },
+}
+
+impl SourceRoot {
+    fn from_source_path(source_path: &Path) -> Self {
+        let is_module_file = source_path
+            .extension()
+            .is_some_and(|extension| extension == "py" || extension == "pyi");
+        match source_path.file_stem().and_then(|stem| stem.to_str()) {
+            Some(module) if is_module_file && !source_path.is_dir() => Self::SingleModule {
+                module: module.to_string(),
+                path: source_path.to_path_buf(),
+            },
+            _ => Self::Directory(source_path.to_path_buf()),
+        }
+    }
+}
+
+fn resolve_module_in_directory(directory: &Path, components: &[&str]) -> Option<PathBuf> {
+    let (last, parents) = components.split_last()?;
+    let parent_dir = parents
+        .iter()
+        .fold(directory.to_path_buf(), |path, part| path.join(part));
+    [
+        parent_dir.join(format!("{last}.py")),
+        parent_dir.join(format!("{last}.pyi")),
+        parent_dir.join(last).join("__init__.py"),
+        parent_dir.join(last),
+    ]
+    .into_iter()
+    .find(|candidate| candidate.exists())
+}
+
+impl ProjectInfo {
+    /// The source paths, distinguishing single-module files from directories
+    pub fn source_roots(&self) -> Vec<SourceRoot> {
+        self.source_paths
+            .iter()
+            .map(|source_path| SourceRoot::from_source_path(source_path))
+            .collect()
+    }
+
+    /// Find the file or package directory which provides `module_path` within the source paths.
+    /// Relocated source paths only provide modules beneath their import root.
+    pub fn resolve_module(&self, module_path: &str) -> Option<PathBuf> {
+        let module_components: Vec<&str> = module_path.split('.').collect();
+        self.source_paths
+            .iter()
+            .zip(self.source_roots())
+            .find_map(|(source_path, source_root)| {
+                let components = match self.import_roots.get(source_path) {
+                    Some(import_root) => {
+                        let prefix: Vec<&str> = import_root.split('.').collect();
+                        match module_components.strip_prefix(prefix.as_slice()) {
+                            Some(components) if !components.is_empty() => components,
+                            _ => return None,
+                        }
+                    }
+                    None => module_components.as_slice(),
+                };
+                match source_root {
+                    SourceRoot::SingleModule { module, path } => {
+                        (components == [module.as_str()]).then_some(path)
+                    }
+                    SourceRoot::Directory(directory) => {
+                        // A directory named after the top-level module is that package itself
+                        let is_package = directory
+                            .file_name()
+                            .is_some_and(|name| name == components[0]);
+                        if is_package {
+                            if components.len() == 1 {
+                                return Some(directory);
+                            }
+                            if let Some(path) =
+                                resolve_module_in_directory(&directory, &components[1..])
+                            {
+                                return Some(path);
+                            }
+                        }
+                        resolve_module_in_directory(&directory, components)
+                    }
+                }
+            })
+    }
+
+    /// The most specific source path which contains the given file, if any.
+    /// Paths are canonicalized where possible, so symlinks and relative segments are resolved.
+    pub fn owning_source_path(&self, file: &Path) -> Option<&PathBuf> {
+        let canonicalize = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let file = canonicalize(file);
+        self.source_paths
+            .iter()
+            .map(|source_path| (source_path, canonicalize(source_path)))
+            .filter(|(_, canonical_source_path)| file.starts_with(canonical_source_path))
+            .max_by_key(|(_, canonical_source_path)| canonical_source_path.components().count())
+            .map(|(source_path, _)| source_path)
+    }
+}
+
+pub fn parse_pyproject_toml(
+    pyproject_path: &Path,
+    config: &ExternalDependencyConfig,
+) -> Result<ProjectInfo> {
+    parse_pyproject_toml_with(pyproject_path, config, &mut config.name_normalizer())
+}
+
+/// Parse a pyproject.toml like [`parse_pyproject_toml`], with a normalizer which may be
+/// shared with the other manifests of a workspace
+pub fn parse_pyproject_toml_with(
+    pyproject_path: &Path,
+    config: &ExternalDependencyConfig,
+    normalizer: &mut NameNormalizer,
+) -> Result<ProjectInfo> {
     let content = fs::read_to_string(pyproject_path)?;
-    let toml_value: Value = toml::from_str(&content)?;
+    parse_pyproject_toml_detailed_str(
+        &content,
+        pyproject_path.parent().unwrap(),
+        config,
+        normalizer,
+    )
+    .map(|detailed| detailed.project_info)
+}
+
+/// Parse in-memory pyproject.toml content, as if it were located in `base_dir`
+pub fn parse_pyproject_toml_str(
+    content: &str,
+    base_dir: &Path,
+    config: &ExternalDependencyConfig,
+) -> Result<ProjectInfo> {
+    parse_pyproject_toml_detailed_str(content, base_dir, config, &mut config.name_normalizer())
+        .map(|detailed| detailed.project_info)
+}
+
+/// A parsed pyproject.toml, along with the version specifier of each declaration
+pub struct DetailedProjectInfo {
+    pub project_info: ProjectInfo,
+    /// The version specifiers declared for each normalized dependency name, and where they were declared.
+    /// Poetry constraints are translated into PEP 440 specifiers, so `^2.1` is recorded as `>=2.1,<3`.
+    /// Declarations without a specifier, or with an environment marker, are omitted.
+    pub specifiers: HashMap<String, Vec<(DependencySource, String)>>,
+}
+
+/// Parse a pyproject.toml like [`parse_pyproject_toml`], keeping the version specifiers of its declarations
+pub fn parse_pyproject_toml_detailed(
+    pyproject_path: &Path,
+    config: &ExternalDependencyConfig,
+) -> Result<DetailedProjectInfo> {
+    let content = fs::read_to_string(pyproject_path)?;
+    parse_pyproject_toml_detailed_str(
+        &content,
+        pyproject_path.parent().unwrap(),
+        config,
+        &mut config.name_normalizer(),
+    )
+}
+
+fn parse_pyproject_toml_detailed_str(
+    content: &str,
+    base_dir: &Path,
+    config: &ExternalDependencyConfig,
+    normalizer: &mut NameNormalizer,
+) -> Result<DetailedProjectInfo> {
+    let toml_value: Value = toml::from_str(content)?;
     let name = extract_project_name(&toml_value);
-    let dependencies = extract_dependencies(&toml_value);
-    let source_paths = extract_source_paths(&toml_value, pyproject_path.parent().unwrap());
-    Ok(ProjectInfo {
-        name,
-        dependencies,
-        source_paths,
+    let mut warnings = Vec::new();
+    let extracted = match explicit_dependency_names(&toml_value) {
+        Some(names) => {
+            warnings.push(ParsingWarning::DependenciesOverridden {
+                pyproject_path: base_dir.join("pyproject.toml").display().to_string(),
+            });
+            let mut extracted = ExtractedDependencies::new(normalizer);
+            for name in names {
+                let pkg_name = extracted.normalizer.normalize(name);
+                extracted.insert(&pkg_name, &DependencySource::Project);
+            }
+            extracted
+        }
+        None => {
+            let mut extracted =
+                extract_dependency_sources(&toml_value, config, normalizer, &mut warnings)?;
+            extract_dynamic_dependencies(
+                &toml_value,
+                base_dir,
+                config,
+                &mut extracted,
+                &mut warnings,
+            )?;
+            extracted
+        }
+    };
+    let ExtractedDependencies {
+        sources: dependency_sources,
+        order: dependency_order,
+        specifiers,
+        ..
+    } = extracted;
+    let dependencies: HashSet<String> = dependency_sources.keys().cloned().collect();
+    let mut source_paths = extract_source_paths_with_layout(
+        &toml_value,
+        base_dir,
+        config.source_layout_template.as_deref(),
+    );
+    let mut import_roots: HashMap<PathBuf, String> = poetry_wheel_packages(&toml_value, base_dir)
+        .into_iter()
+        .filter(|(source_path, _)| source_paths.contains(source_path))
+        .filter_map(|(source_path, import_root)| Some((source_path, import_root?)))
+        .collect();
+    if config.is_case_insensitive_fs() {
+        source_paths = source_paths
+            .iter()
+            .map(|source_path| resolve_on_disk_case(base_dir, source_path))
+            .collect();
+        import_roots = import_roots
+            .into_iter()
+            .map(|(source_path, import_root)| {
+                (resolve_on_disk_case(base_dir, &source_path), import_root)
+            })
+            .collect();
+    }
+
+    warnings.extend(validate_source_paths(base_dir, &source_paths, config)?);
+
+    if config.single_dependency_source && !dependencies.is_empty() {
+        let requirements_path = base_dir.join("requirements.txt");
+        if requirements_path.is_file()
+            && !parse_requirements_txt(&requirements_path, normalizer)?.is_empty()
+        {
+            warnings.push(ParsingWarning::MultipleDependencySources {
+                pyproject_path: base_dir.join("pyproject.toml").display().to_string(),
+                requirements_path: requirements_path.display().to_string(),
+            });
+        }
+    }
+
+    Ok(DetailedProjectInfo {
+        project_info: ProjectInfo {
+            name,
+            dependencies,
+            dependency_sources,
+            dependency_order,
+            source_paths,
+            import_roots,
+            warnings,
+        },
+        specifiers,
     })
 }
 
+/// Parse the dependency manifest at the root of a project, preferring pyproject.toml
+/// over a setuptools setup.cfg, and either over requirements.txt. Returns `None` if the project has none.
+pub fn parse_project_root(
+    project_root: &Path,
+    config: &ExternalDependencyConfig,
+) -> Result<Option<ProjectInfo>> {
+    parse_project_root_with(project_root, config, &mut config.name_normalizer())
+}
+
+fn parse_project_root_with(
+    project_root: &Path,
+    config: &ExternalDependencyConfig,
+    normalizer: &mut NameNormalizer,
+) -> Result<Option<ProjectInfo>> {
+    let pyproject_path = project_root.join("pyproject.toml");
+    if pyproject_path.is_file() {
+        return parse_pyproject_toml_with(&pyproject_path, config, normalizer).map(Some);
+    }
+
+    let setup_cfg_path = project_root.join("setup.cfg");
+    if is_setuptools_setup_cfg(&setup_cfg_path) {
+        return parse_setup_cfg_with(&setup_cfg_path, config, normalizer).map(Some);
+    }
+
+    let requirements_path = project_root.join("requirements.txt");
+    if requirements_path.is_file() {
+        let requirements = parse_requirements(&requirements_path, normalizer)?;
+        let mut source_paths = vec![project_root.to_path_buf()];
+        source_paths.extend(
+            requirements
+                .editable_paths
+                .into_iter()
+                .filter(|path| path != &normalize_lexically(project_root)),
+        );
+        return Ok(Some(ProjectInfo::new(
+            None,
+            requirements.dependencies.into_iter().collect(),
+            source_paths,
+        )));
+    }
+
+    Ok(None)
+}
+
+/// Parse the dependency manifest of each workspace member with [`parse_project_root`].
+///
+/// Members without a manifest are skipped. By default every member is parsed and all failures
+/// are reported together; with `fail_fast`, parsing stops at the first member which fails.
+pub fn parse_workspace(
+    member_roots: &[PathBuf],
+    config: &ExternalDependencyConfig,
+    fail_fast: bool,
+) -> std::result::Result<Vec<(PathBuf, ProjectInfo)>, error::WorkspaceParsingError> {
+    let mut members = Vec::new();
+    let mut failures = Vec::new();
+    // Members tend to declare the same dependencies, so they share one normalizer
+    let mut normalizer = config.name_normalizer();
+    for member_root in member_roots {
+        match parse_project_root_with(member_root, config, &mut normalizer) {
+            Ok(Some(project_info)) => members.push((member_root.clone(), project_info)),
+            Ok(None) => {}
+            Err(error) => {
+                failures.push(error::MemberParsingError {
+                    member: member_root.clone(),
+                    error,
+                });
+                if fail_fast {
+                    break;
+                }
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(members)
+    } else {
+        Err(error::WorkspaceParsingError(failures))
+    }
+}
+
+/// The manifest files which declare each dependency, keyed by normalized package name
+pub type DependencyOrigins = HashMap<String, BTreeSet<PathBuf>>;
+
+/// Parse every dependency manifest at the root of a project (`pyproject.toml` and any
+/// `requirements*.txt`), recording which files declare each dependency. This is meant for
+/// debugging drift between manifests, so unlike `parse_project_root` no manifest is preferred.
+pub fn parse_dependency_origins(
+    project_root: &Path,
+    config: &ExternalDependencyConfig,
+) -> Result<DependencyOrigins> {
+    let mut manifests: Vec<(PathBuf, HashSet<String>)> = Vec::new();
+    let mut normalizer = config.name_normalizer();
+
+    let pyproject_path = project_root.join("pyproject.toml");
+    if pyproject_path.is_file() {
+        let dependencies =
+            parse_pyproject_toml_with(&pyproject_path, config, &mut normalizer)?.dependencies;
+        manifests.push((pyproject_path, dependencies));
+    }
+
+    let mut requirements_paths: Vec<PathBuf> = fs::read_dir(project_root)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("requirements") && name.ends_with(".txt"))
+        })
+        .collect();
+    requirements_paths.sort();
+    for requirements_path in requirements_paths {
+        let dependencies = parse_requirements_txt(&requirements_path, &mut normalizer)?;
+        manifests.push((requirements_path, dependencies));
+    }
+
+    let mut origins = DependencyOrigins::new();
+    for (manifest_path, dependencies) in manifests {
+        for dependency in dependencies {
+            origins
+                .entry(dependency)
+                .or_default()
+                .insert(manifest_path.clone());
+        }
+    }
+    Ok(origins)
+}
+
+/// Compare two sets of dependencies, returning the sorted (added, removed) names
+pub fn diff_dependencies(
+    base: &HashSet<String>,
+    head: &HashSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    let mut added: Vec<String> = head.difference(base).cloned().collect();
+    let mut removed: Vec<String> = base.difference(head).cloned().collect();
+    added.sort();
+    removed.sort();
+    (added, removed)
+}
+
+/// Compare the dependencies declared by two versions of a pyproject.toml,
+/// returning the sorted (added, removed) normalized names
+pub fn diff_pyproject(base: &Path, head: &Path) -> Result<(Vec<String>, Vec<String>)> {
+    let config = ExternalDependencyConfig::default();
+    let base_info = parse_pyproject_toml(base, &config)?;
+    let head_info = parse_pyproject_toml(head, &config)?;
+    Ok(diff_dependencies(
+        &base_info.dependencies,
+        &head_info.dependencies,
+    ))
+}
+
 fn extract_project_name(toml_value: &Value) -> Option<String> {
     toml_value
         .get("project")
         .and_then(|p| p.get("name"))
+        .or_else(|| {
+            // Fall back to the name of a pure Poetry project
+            toml_value
+                .get("tool")
+                .and_then(|t| t.get("poetry"))
+                .and_then(|p| p.get("name"))
+        })
         .and_then(|n| n.as_str())
         .map(|s| s.to_string())
 }
 
-fn extract_dependencies(toml_value: &Value) -> HashSet<String> {
-    let mut dependencies = HashSet::new();
+/// Where a declared dependency came from within a package's manifest
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub enum DependencySource {
+    /// The main dependency list (e.g. `[project.dependencies]`)
+    Project,
+    /// A named dependency group or environment, by normalized name
+    Group(String),
+    /// An optional dependency set (extra), by normalized name
+    Extra(String),
+}
+
+impl DependencySource {
+    /// A short description of where a dependency was declared, for messages
+    fn describe(&self) -> String {
+        match self {
+            Self::Project => "project dependencies".to_string(),
+            Self::Group(group) => format!("group '{group}'"),
+            Self::Extra(extra) => format!("extra '{extra}'"),
+        }
+    }
+}
+
+/// The sources of each declared dependency, keyed by normalized package name
+pub type DependencySources = HashMap<String, HashSet<DependencySource>>;
+
+/// Dependencies extracted from a manifest, along with the order they were first declared in
+#[derive(Debug)]
+struct ExtractedDependencies<'n> {
+    sources: DependencySources,
+    order: Vec<String>,
+    /// The version specifiers of unconditional declarations, along with where they were declared
+    specifiers: HashMap<String, Vec<(DependencySource, String)>>,
+    /// Shared by every manifest of a parse, so that repeated names are only normalized once
+    normalizer: &'n mut NameNormalizer,
+}
+
+impl<'n> ExtractedDependencies<'n> {
+    fn new(normalizer: &'n mut NameNormalizer) -> Self {
+        Self {
+            sources: DependencySources::new(),
+            order: Vec::new(),
+            specifiers: HashMap::new(),
+            normalizer,
+        }
+    }
+
+    fn insert(&mut self, pkg_name: &str, source: &DependencySource) {
+        if let Some(sources) = self.sources.get_mut(pkg_name) {
+            sources.insert(source.clone());
+            return;
+        }
+        self.order.push(pkg_name.to_string());
+        self.sources
+            .insert(pkg_name.to_string(), HashSet::from([source.clone()]));
+    }
+
+    fn remove(&mut self, pkg_name: &str) {
+        if self.sources.remove(pkg_name).is_some() {
+            self.order.retain(|name| name != pkg_name);
+        }
+        self.specifiers.remove(pkg_name);
+    }
+
+    /// Dependencies whose declarations have version specifiers which no single version satisfies
+    fn conflicting_specifiers(&self) -> Vec<ParsingWarning> {
+        self.order
+            .iter()
+            .filter_map(|pkg_name| {
+                let declarations = self.specifiers.get(pkg_name)?;
+                let specifiers: Vec<&str> = declarations
+                    .iter()
+                    .map(|(_, specifier)| specifier.as_str())
+                    .collect();
+                specifiers_conflict(&specifiers).then(|| ParsingWarning::ConflictingSpecifiers {
+                    package: pkg_name.clone(),
+                    declarations: declarations
+                        .iter()
+                        .map(|(source, specifier)| format!("'{specifier}' ({})", source.describe()))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn extract_dependency_sources<'n>(
+    toml_value: &Value,
+    config: &ExternalDependencyConfig,
+    normalizer: &'n mut NameNormalizer,
+    warnings: &mut Vec<ParsingWarning>,
+) -> Result<ExtractedDependencies<'n>> {
+    let mut dependencies = ExtractedDependencies::new(normalizer);
 
     // Extract dependencies from standard pyproject.toml format
     let has_project_deps = toml_value
         .get("project")
         .and_then(|p| p.get("dependencies"))
         .is_some_and(|deps| {
-            extract_deps_from_value(&mut dependencies, deps);
+            extract_deps_from_value(
+                &mut dependencies,
+                deps,
+                &DependencySource::Project,
+                &config.target_environment,
+                warnings,
+            );
             true
         });
 
+    // Extract dependencies from selected extras
+    let optional_dependencies = toml_value
+        .get("project")
+        .and_then(|p| p.get("optional-dependencies"))
+        .and_then(|o| o.as_table());
+    let project_name = extract_project_name(toml_value).map(|name| normalize_package_name(&name));
+    if let Some(extras) = optional_dependencies {
+        for extra_name in extras.keys() {
+            if config.is_dependency_group_included(extra_name) {
+                let source = DependencySource::Extra(normalize_package_name(extra_name));
+                let deps = expand_self_extras(
+                    extras,
+                    extra_name,
+                    project_name.as_deref(),
+                    &mut HashSet::new(),
+                );
+                extract_deps_from_value(
+                    &mut dependencies,
+                    &Value::Array(deps),
+                    &source,
+                    &config.target_environment,
+                    warnings,
+                );
+            }
+        }
+    }
+
     let has_poetry_deps = toml_value
         .get("tool")
         .and_then(|t| t.get("poetry"))
         .and_then(|p| p.get("dependencies"))
         .is_some();
 
-    // Print warning if both formats are detected
     if has_project_deps && has_poetry_deps {
-        eprintln!(
-            "Warning: Both project dependencies and Poetry dependencies detected. Using project dependencies."
-        );
+        warnings.push(ParsingWarning::ProjectAndPoetryDependencies);
     } else if has_poetry_deps {
         // Extract Poetry dependencies only if project dependencies are not present
         if let Some(deps) = toml_value
@@ -64,134 +632,4216 @@ fn extract_dependencies(toml_value: &Value) -> HashSet<String> {
             .and_then(|tool| tool.get("poetry"))
             .and_then(|poetry| poetry.get("dependencies"))
         {
-            extract_deps_from_value(&mut dependencies, deps)
+            extract_deps_from_value(
+                &mut dependencies,
+                deps,
+                &DependencySource::Project,
+                &config.target_environment,
+                warnings,
+            )
         }
     }
 
-    dependencies
-}
+    // Extract dependencies from the legacy Flit metadata table, which predates PEP 621
+    if let Some(metadata) = toml_value
+        .get("tool")
+        .and_then(|t| t.get("flit"))
+        .and_then(|f| f.get("metadata"))
+    {
+        if let Some(deps) = metadata.get("requires") {
+            extract_deps_from_value(
+                &mut dependencies,
+                deps,
+                &DependencySource::Project,
+                &config.target_environment,
+                warnings,
+            );
+        }
+        if let Some(extras) = metadata.get("requires-extra").and_then(|e| e.as_table()) {
+            for (extra_name, deps) in extras {
+                if config.is_dependency_group_included(extra_name) {
+                    let source = DependencySource::Extra(normalize_package_name(extra_name));
+                    extract_deps_from_value(
+                        &mut dependencies,
+                        deps,
+                        &source,
+                        &config.target_environment,
+                        warnings,
+                    );
+                }
+            }
+        }
+    }
 
-fn extract_deps_from_value(dependencies: &mut HashSet<String>, deps: &Value) {
-    const EXCLUDED_DEPS: [&str; 3] = ["python", "poetry", "poetry-core"];
+    // Extract dependencies from the legacy Poetry dev table, which predates dependency groups
+    if let Some(deps) = toml_value
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("dev-dependencies"))
+    {
+        warnings.push(ParsingWarning::PoetryLegacyDevDependencies);
+        if config.is_dependency_group_included("dev") {
+            extract_deps_from_value(
+                &mut dependencies,
+                deps,
+                &DependencySource::Group("dev".to_string()),
+                &config.target_environment,
+                warnings,
+            );
+        }
+    }
 
-    match deps {
-        Value::Array(deps_array) => {
-            for dep_str in deps_array.iter().filter_map(|dep| dep.as_str()) {
-                let pkg_name = normalize_package_name(&extract_package_name(dep_str));
-                if !EXCLUDED_DEPS.contains(&pkg_name.as_str()) {
-                    dependencies.insert(pkg_name);
-                }
+    // Extract dependencies from selected Poetry dependency groups
+    if let Some(groups) = toml_value
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("group"))
+        .and_then(|g| g.as_table())
+    {
+        for (group_name, group) in groups {
+            if !config.is_dependency_group_included(group_name) {
+                continue;
+            }
+            if let Some(deps) = group.get("dependencies") {
+                extract_deps_from_value(
+                    &mut dependencies,
+                    deps,
+                    &DependencySource::Group(normalize_package_name(group_name)),
+                    &config.target_environment,
+                    warnings,
+                );
             }
         }
-        Value::Table(deps_table) => {
-            for dep_name in deps_table.keys() {
-                let pkg_name = normalize_package_name(&extract_package_name(dep_name));
-                if !EXCLUDED_DEPS.contains(&pkg_name.as_str()) {
-                    dependencies.insert(pkg_name);
+    }
+
+    // Extract dependencies from selected Hatch environments
+    if let Some(envs) = toml_value
+        .get("tool")
+        .and_then(|t| t.get("hatch"))
+        .and_then(|h| h.get("envs"))
+        .and_then(|e| e.as_table())
+    {
+        for (env_name, env) in envs {
+            if !config.is_dependency_group_included(env_name) {
+                continue;
+            }
+            let source = DependencySource::Group(normalize_package_name(env_name));
+            for key in ["dependencies", "extra-dependencies"] {
+                if let Some(deps) = env.get(key) {
+                    extract_deps_from_value(
+                        &mut dependencies,
+                        deps,
+                        &source,
+                        &config.target_environment,
+                        warnings,
+                    );
                 }
             }
+            // Environments can activate the project's extras by name
+            let features = env
+                .get("features")
+                .and_then(|f| f.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|feature| feature.as_str());
+            for feature in features {
+                let deps = optional_dependencies
+                    .map(|extras| {
+                        expand_self_extras(
+                            extras,
+                            feature,
+                            project_name.as_deref(),
+                            &mut HashSet::new(),
+                        )
+                    })
+                    .unwrap_or_default();
+                extract_deps_from_value(
+                    &mut dependencies,
+                    &Value::Array(deps),
+                    &source,
+                    &config.target_environment,
+                    warnings,
+                );
+            }
         }
-        _ => {}
     }
-}
 
-fn extract_package_name(dep_str: &str) -> String {
-    // Split on common separators and take the first part
-    dep_str
-        .split(&[' ', '=', '<', '>', '~', ';', '['][..])
-        .next()
-        .unwrap_or(dep_str)
-        .to_string()
-}
+    // Extract dependencies from selected PEP 735 dependency groups
+    if let Some(groups) = toml_value
+        .get("dependency-groups")
+        .and_then(|g| g.as_table())
+    {
+        extract_selected_groups(&mut dependencies, groups, config, warnings)?;
+    }
 
-/// This normalizes a Python distribution name according to PyPI standards
-pub fn normalize_package_name(name: &str) -> String {
-    name.to_lowercase()
-        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<&str>>()
-        .join("_")
+    // Extract dependencies from selected PDM dev dependency groups
+    if let Some(groups) = toml_value
+        .get("tool")
+        .and_then(|t| t.get("pdm"))
+        .and_then(|p| p.get("dev-dependencies"))
+        .and_then(|g| g.as_table())
+    {
+        let groups: toml::Table = groups
+            .iter()
+            .map(|(group_name, entries)| (group_name.clone(), pdm_group_entries(entries)))
+            .collect();
+        extract_selected_groups(&mut dependencies, &groups, config, warnings)?;
+    }
+
+    // Workspace members and local path sources are first-party code, not external dependencies
+    for local_source in uv_local_sources(toml_value, dependencies.normalizer) {
+        dependencies.remove(&local_source);
+    }
+    let conflicts = dependencies.conflicting_specifiers();
+    if config.strict_specifiers
+        && let Some(ParsingWarning::ConflictingSpecifiers {
+            package,
+            declarations,
+        }) = conflicts.first().cloned()
+    {
+        return Err(error::ParsingError::ConflictingDependencySpecifiers {
+            package,
+            declarations,
+        });
+    }
+    warnings.extend(conflicts);
+
+    Ok(dependencies)
 }
 
-fn extract_source_paths(toml_value: &Value, project_root: &Path) -> Vec<PathBuf> {
-    let mut source_paths = Vec::new();
+/// The dependencies listed in `[tool.tach] external_dependencies`, which replace those extracted
+/// from the rest of the manifest. Returns `None` when the list is not set.
+fn explicit_dependency_names(toml_value: &Value) -> Option<Vec<&str>> {
+    let names = toml_value
+        .get("tool")
+        .and_then(|t| t.get("tach"))
+        .and_then(|tach| tach.get("external_dependencies"))
+        .and_then(|e| e.as_array())?;
+    Some(names.iter().filter_map(|name| name.as_str()).collect())
+}
 
-    // Check for setuptools configuration
-    if let Some(packages) = toml_value
+/// Resolve the fields of `project.dynamic` which hold dependencies, since they are declared in a
+/// build-backend-specific location rather than the `[project]` table.
+///
+/// Dependencies are read from the `file` requirements of `[tool.setuptools.dynamic]`, or otherwise
+/// from the `setup(...)` call of a `setup.py` beside the pyproject.toml. When neither is present,
+/// [`DynamicDependenciesUnresolved`](error::ParsingError::DynamicDependenciesUnresolved) is returned.
+fn extract_dynamic_dependencies(
+    toml_value: &Value,
+    base_dir: &Path,
+    config: &ExternalDependencyConfig,
+    dependencies: &mut ExtractedDependencies,
+    warnings: &mut Vec<ParsingWarning>,
+) -> Result<()> {
+    let dynamic: Vec<&str> = toml_value
+        .get("project")
+        .and_then(|p| p.get("dynamic"))
+        .and_then(|d| d.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|field| field.as_str())
+        .collect();
+    let setuptools_dynamic = toml_value
         .get("tool")
         .and_then(|t| t.get("setuptools"))
-        .and_then(|setuptools| setuptools.get("packages"))
-        .and_then(|p| p.as_array())
-    {
-        for package_name in packages.iter().filter_map(|package| package.as_str()) {
-            source_paths.push(project_root.join(package_name));
+        .and_then(|s| s.get("dynamic"));
+    let setup_py_path = base_dir.join("setup.py");
+    let unresolved = |field: &str| error::ParsingError::DynamicDependenciesUnresolved {
+        field: field.to_string(),
+        path: base_dir.join("pyproject.toml").display().to_string(),
+    };
+
+    if dynamic.contains(&"dependencies") {
+        if let Some(spec) = setuptools_dynamic.and_then(|d| d.get("dependencies")) {
+            extract_dynamic_files(
+                spec,
+                base_dir,
+                dependencies,
+                &DependencySource::Project,
+                warnings,
+            )?;
+        } else if setup_py_path.is_file() {
+            match parse_setup_py_with(&setup_py_path, config, dependencies.normalizer) {
+                Ok(setup_info) => {
+                    for dependency in setup_info.dependency_order {
+                        dependencies.insert(&dependency, &DependencySource::Project);
+                    }
+                    warnings.extend(setup_info.warnings);
+                }
+                Err(error::ParsingError::SetupCallNotFound { .. }) => {
+                    return Err(unresolved("dependencies"));
+                }
+                Err(err) => return Err(err),
+            }
+        } else {
+            return Err(unresolved("dependencies"));
         }
     }
 
-    // Check for poetry configuration
-    if let Some(packages) = toml_value
-        .get("tool")
-        .and_then(|t| t.get("poetry"))
-        .and_then(|p| p.get("packages"))
-        .and_then(|p| p.as_array())
-    {
-        for package in packages {
-            if let Some(include) = package.get("include").and_then(|i| i.as_str()) {
-                let from = package.get("from").and_then(|f| f.as_str()).unwrap_or("");
-                source_paths.push(project_root.join(from).join(include));
+    if dynamic.contains(&"optional-dependencies") {
+        if let Some(extras) = setuptools_dynamic
+            .and_then(|d| d.get("optional-dependencies"))
+            .and_then(|e| e.as_table())
+        {
+            for (extra_name, spec) in extras {
+                if config.is_dependency_group_included(extra_name) {
+                    let source = DependencySource::Extra(normalize_package_name(extra_name));
+                    extract_dynamic_files(spec, base_dir, dependencies, &source, warnings)?;
+                }
             }
+        } else if setup_py_path.is_file() {
+            // `extras_require` is not read from `setup.py`
+            warnings.push(ParsingWarning::DynamicDependenciesNotRead {
+                field: "optional-dependencies".to_string(),
+            });
+        } else {
+            return Err(unresolved("optional-dependencies"));
         }
     }
+    Ok(())
+}
 
-    // Check for maturin configuration
-    if let Some(python_source) = toml_value
-        .get("tool")
-        .and_then(|t| t.get("maturin"))
-        .and_then(|m| m.get("python-source"))
-        .and_then(|ps| ps.as_str())
-    {
-        source_paths.push(project_root.join(python_source));
+/// Read the requirements files of a `[tool.setuptools.dynamic]` entry, such as `{ file = ["requirements.in"] }`
+fn extract_dynamic_files(
+    spec: &Value,
+    base_dir: &Path,
+    dependencies: &mut ExtractedDependencies,
+    source: &DependencySource,
+    warnings: &mut Vec<ParsingWarning>,
+) -> Result<()> {
+    let files: Vec<&str> = match spec.get("file") {
+        Some(Value::String(file)) => vec![file.as_str()],
+        Some(Value::Array(files)) => files.iter().filter_map(|file| file.as_str()).collect(),
+        _ => vec![],
+    };
+    for file in files {
+        let path = base_dir.join(file);
+        if !path.is_file() {
+            warnings.push(ParsingWarning::DynamicDependencyFileMissing {
+                path: path.display().to_string(),
+            });
+            continue;
+        }
+        let mut requirements: Vec<String> = parse_requirements(&path, dependencies.normalizer)?
+            .dependencies
+            .into_iter()
+            .collect();
+        // Requirements are unordered, so declare them in a stable order
+        requirements.sort();
+        for requirement in requirements {
+            dependencies.insert(&requirement, source);
+        }
     }
+    Ok(())
+}
 
-    // If no specific configuration found, use conventional locations
-    if source_paths.is_empty() {
-        let src_dir = project_root.join("src");
-        if src_dir.exists() {
-            source_paths.push(src_dir);
-        } else {
-            source_paths.push(project_root.to_path_buf());
+/// Normalized names of the `[tool.uv.sources]` entries which point at a workspace member
+/// (`{ workspace = true }`) or a local `path`, rather than at an index, URL or repository
+fn uv_local_sources(toml_value: &Value, normalizer: &mut NameNormalizer) -> Vec<Arc<str>> {
+    let is_local_source = |source: &Value| {
+        source.get("workspace").and_then(Value::as_bool) == Some(true)
+            || source.get("path").is_some()
+    };
+    toml_value
+        .get("tool")
+        .and_then(|t| t.get("uv"))
+        .and_then(|uv| uv.get("sources"))
+        .and_then(|s| s.as_table())
+        .into_iter()
+        .flatten()
+        .filter(|(_, source)| match source {
+            // Sources may be split by environment marker
+            Value::Array(sources) => sources.iter().any(is_local_source),
+            source => is_local_source(source),
+        })
+        .map(|(name, _)| normalizer.normalize(name))
+        .collect()
+}
+
+/// Extract the dependencies of every group in `groups` which is selected by `include_dependency_groups`
+fn extract_selected_groups(
+    dependencies: &mut ExtractedDependencies,
+    groups: &toml::Table,
+    config: &ExternalDependencyConfig,
+    warnings: &mut Vec<ParsingWarning>,
+) -> Result<()> {
+    for group_name in groups.keys() {
+        if config.is_dependency_group_included(group_name) {
+            let source = DependencySource::Group(normalize_package_name(group_name));
+            extract_group_deps(
+                dependencies,
+                groups,
+                group_name,
+                &source,
+                &config.target_environment,
+                &mut GroupTraversal {
+                    visited: HashSet::new(),
+                    depth: 0,
+                    max_depth: config.max_dependency_group_depth,
+                },
+                warnings,
+            )?;
         }
     }
+    Ok(())
+}
 
-    source_paths
+/// The name of the package installed by a PDM editable entry, such as
+/// `-e file:///${PROJECT_ROOT}/libs/core#egg=core` or `-e core @ file:///${PROJECT_ROOT}/libs/core`
+fn pdm_editable_name(entry: &str) -> Option<String> {
+    let target = strip_requirements_option(entry, "-e", "--editable")?;
+    if let Some((name, _url)) = target.split_once(" @ ") {
+        return Some(name.trim().to_string());
+    }
+    extract_url_package_name(target)
+        .map(str::to_string)
+        .or_else(|| {
+            // A local project directory, which is expected to be named after its package
+            target
+                .split(['#', '?'])
+                .next()?
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+        })
 }
 
-const REQUIREMENTS_TXT_EXCLUDED_DEPS: [&str; 3] = ["python", "poetry", "poetry-core"];
+/// The entries of a PDM dev dependency group, with editable entries replaced by the package they install
+fn pdm_group_entries(entries: &Value) -> Value {
+    let Some(entries) = entries.as_array() else {
+        return entries.clone();
+    };
+    Value::Array(
+        entries
+            .iter()
+            .filter_map(|entry| match entry.as_str() {
+                Some(dep_str) if dep_str.starts_with('-') => {
+                    pdm_editable_name(dep_str).map(Value::String)
+                }
+                _ => Some(entry.clone()),
+            })
+            .collect(),
+    )
+}
 
-pub fn parse_requirements_txt(requirements_path: &Path) -> Result<HashSet<String>> {
-    let content = fs::read_to_string(requirements_path)?;
-    let mut dependencies = HashSet::new();
+/// The entries of an extra in `project.optional-dependencies`, with references to the project's
+/// own extras (such as `mypkg[docs,cli]` in an aggregating `all` extra) replaced by the entries
+/// of the referenced extras. `visited` guards against extras which reference each other.
+fn expand_self_extras(
+    extras: &toml::Table,
+    extra_name: &str,
+    project_name: Option<&str>,
+    visited: &mut HashSet<String>,
+) -> Vec<Value> {
+    let normalized_extra_name = normalize_package_name(extra_name);
+    if !visited.insert(normalized_extra_name.clone()) {
+        return vec![];
+    }
+    let Some(entries) = extras
+        .iter()
+        .find(|(name, _)| normalize_package_name(name) == normalized_extra_name)
+        .and_then(|(_, entries)| entries.as_array())
+    else {
+        return vec![];
+    };
 
-    for line in content.lines() {
-        // Skip comments and empty lines
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
+    let mut expanded = Vec::new();
+    for entry in entries {
+        let referenced_extras = entry.as_str().and_then(|dep_str| {
+            let requirement = dep_str.split(';').next().unwrap_or(dep_str).trim();
+            let (name, rest) = requirement.split_once('[')?;
+            let is_self_reference = project_name
+                .is_some_and(|project_name| normalize_package_name(name) == project_name);
+            is_self_reference.then(|| rest.split(']').next().unwrap_or_default())
+        });
+        match referenced_extras {
+            Some(referenced_extras) => {
+                for referenced_extra in referenced_extras.split(',').map(str::trim) {
+                    expanded.extend(expand_self_extras(
+                        extras,
+                        referenced_extra,
+                        project_name,
+                        visited,
+                    ));
+                }
+            }
+            None => expanded.push(entry.clone()),
         }
+    }
+    expanded
+}
 
-        // Skip options (lines starting with -)
-        if line.starts_with('-') {
-            continue;
+/// Resolve which groups in a dependency groups table are selected by `selection`,
+/// after applying `"all"`, negations, wildcards and aliases. Names are sorted and normalized.
+pub fn resolve_dependency_group_selection(
+    groups: &toml::Table,
+    selection: &[String],
+) -> Vec<String> {
+    let mut resolved: Vec<String> = groups
+        .keys()
+        .filter(|group_name| is_dependency_group_selected(selection, group_name))
+        .map(|group_name| normalize_package_name(group_name))
+        .collect();
+    resolved.sort();
+    resolved.dedup();
+    resolved
+}
+
+/// State carried through nested `include-group` references
+struct GroupTraversal {
+    visited: HashSet<String>,
+    depth: usize,
+    max_depth: usize,
+}
+
+/// Extract the dependencies of a PEP 735 dependency group, following `include-group` references.
+/// Includes may be written as `{include-group = "name"}` tables, or as bare `":name"` strings.
+/// Included dependencies are attributed to the group which was selected.
+fn extract_group_deps(
+    dependencies: &mut ExtractedDependencies,
+    groups: &toml::Table,
+    group_name: &str,
+    source: &DependencySource,
+    environment: &MarkerEnvironment,
+    traversal: &mut GroupTraversal,
+    warnings: &mut Vec<ParsingWarning>,
+) -> Result<()> {
+    let normalized_group_name = normalize_package_name(group_name);
+    if !traversal.visited.insert(normalized_group_name.clone()) {
+        // Already extracted, or an include cycle
+        return Ok(());
+    }
+    if traversal.depth > traversal.max_depth {
+        return Err(error::ParsingError::GroupNestingTooDeep {
+            group: group_name.to_string(),
+            depth: traversal.depth,
+        });
+    }
+
+    let Some(entries) = groups
+        .iter()
+        .find(|(name, _)| normalize_package_name(name) == normalized_group_name)
+        .and_then(|(_, entries)| entries.as_array())
+    else {
+        return Ok(());
+    };
+
+    let mut package_entries = Vec::new();
+    for entry in entries {
+        let included_group = match entry {
+            Value::Table(table) => table.get("include-group").and_then(|g| g.as_str()),
+            Value::String(dep_str) => dep_str.strip_prefix(':'),
+            _ => None,
+        };
+        match included_group {
+            Some(included_group) => {
+                traversal.depth += 1;
+                extract_group_deps(
+                    dependencies,
+                    groups,
+                    included_group,
+                    source,
+                    environment,
+                    traversal,
+                    warnings,
+                )?;
+                traversal.depth -= 1;
+            }
+            None => package_entries.push(entry.clone()),
         }
+    }
+    extract_deps_from_value(
+        dependencies,
+        &Value::Array(package_entries),
+        source,
+        environment,
+        warnings,
+    );
+    Ok(())
+}
 
-        // Extract package name
-        let package_name = extract_package_name(line);
-        let normalized_name = normalize_package_name(&package_name);
+fn extract_deps_from_value(
+    dependencies: &mut ExtractedDependencies,
+    deps: &Value,
+    source: &DependencySource,
+    environment: &MarkerEnvironment,
+    warnings: &mut Vec<ParsingWarning>,
+) {
+    // Each entry's name, along with its environment marker and version specifier
+    let dep_entries: Vec<(&str, Option<&str>, Option<String>)> = match deps {
+        Value::Array(deps_array) => deps_array
+            .iter()
+            .flat_map(|dep| match dep {
+                // Malformed generators may nest the array, so flatten one level rather than
+                // silently dropping the dependencies inside it
+                Value::Array(nested) => {
+                    warnings.push(ParsingWarning::NestedDependencyArray {
+                        entry: dep.to_string(),
+                    });
+                    nested.iter().filter_map(Value::as_str).collect()
+                }
+                _ => dep.as_str().into_iter().collect::<Vec<_>>(),
+            })
+            .map(|dep| {
+                let (requirement, marker) = match dep.split_once(';') {
+                    Some((requirement, marker)) => (requirement, Some(marker)),
+                    None => (dep, None),
+                };
+                let specifier = requirement_specifier(requirement).map(str::to_string);
+                (requirement, marker, specifier)
+            })
+            .collect(),
+        // Poetry tables map names to a constraint, or to a table which may hold one under `version`
+        Value::Table(deps_table) => deps_table
+            .iter()
+            .map(|(name, spec)| {
+                let marker = spec.get("markers").and_then(|m| m.as_str());
+                let constraint = match spec {
+                    Value::String(constraint) => Some(constraint.as_str()),
+                    // A python restriction makes the declaration conditional, like a marker
+                    Value::Table(spec) if !spec.contains_key("python") => {
+                        spec.get("version").and_then(|v| v.as_str())
+                    }
+                    _ => None,
+                };
+                let specifier = constraint.and_then(poetry_constraint_specifier);
+                (name.as_str(), marker, specifier)
+            })
+            .collect(),
+        _ => vec![],
+    };
 
-        if !REQUIREMENTS_TXT_EXCLUDED_DEPS.contains(&normalized_name.as_str()) {
-            dependencies.insert(normalized_name);
+    for (dep_name, marker, specifier) in dep_entries {
+        if marker.is_some_and(|marker| !marker_matches(marker, environment)) {
+            continue;
+        }
+        let pkg_name = dependencies
+            .normalizer
+            .normalize(extract_package_name(dep_name.trim()));
+        if pkg_name.is_empty() {
+            // Generated manifests may contain blank entries, which name no package
+            warnings.push(ParsingWarning::EmptyDependency {
+                entry: dep_name.to_string(),
+            });
+            continue;
+        }
+        if !is_excluded_dependency(&pkg_name) {
+            // Conditional declarations may apply to different environments, so they never conflict
+            if marker.is_none()
+                && let Some(specifier) = specifier
+            {
+                dependencies
+                    .specifiers
+                    .entry(pkg_name.to_string())
+                    .or_default()
+                    .push((source.clone(), specifier));
+            }
+            dependencies.insert(&pkg_name, source);
         }
     }
+}
 
-    Ok(dependencies)
+/// The normalized names of packages which are declared by manifests without being importable
+const EXCLUDED_DEPS: [&str; 3] = ["python", "poetry", "poetry_core"];
+
+/// Whether the normalized package name is one of [`EXCLUDED_DEPS`]
+fn is_excluded_dependency(pkg_name: &str) -> bool {
+    EXCLUDED_DEPS.contains(&pkg_name)
+}
+
+fn extract_package_name(dep_str: &str) -> &str {
+    // Split on common separators and take the first part
+    dep_str
+        .split(&[' ', '=', '<', '>', '~', ';', '['][..])
+        .next()
+        .unwrap_or(dep_str)
+}
+
+/// Extract the package name from a conda match spec, such as `numpy=1.26` or `conda-forge::pandas`
+fn extract_conda_package_name(spec: &str) -> &str {
+    let package = spec
+        .rsplit_once("::")
+        .map_or(spec, |(_channel, package)| package);
+    extract_package_name(package)
+}
+
+/// This normalizes a Python distribution name strictly according to PEP 503,
+/// lowercasing and replacing each run of '-', '_' and '.' with a single '-'
+pub fn normalize_package_name_pep503(name: &str) -> String {
+    name.to_lowercase()
+        .split(['-', '_', '.'])
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+/// This normalizes a Python distribution name according to PyPI standards, so that runs of
+/// `-`, `_` and `.` compare equal (e.g. `Zope.Interface` and `zope-interface`).
+/// Characters outside of the PEP 503 set (such as `+`) are kept.
+pub fn normalize_package_name(name: &str) -> String {
+    normalize_package_name_with(name, true)
+}
+
+/// Caches the normalized form of each raw name, since the manifests of a large workspace repeat
+/// the same names many times. Names are normalized as by [`normalize_package_name_with`].
+#[derive(Debug)]
+pub struct NameNormalizer {
+    preserve_unknown_chars: bool,
+    cache: HashMap<String, Arc<str>>,
+}
+
+impl NameNormalizer {
+    pub fn new(preserve_unknown_chars: bool) -> Self {
+        Self {
+            preserve_unknown_chars,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn normalize(&mut self, name: &str) -> Arc<str> {
+        if let Some(normalized) = self.cache.get(name) {
+            return normalized.clone();
+        }
+        let normalized: Arc<str> =
+            normalize_package_name_with(name, self.preserve_unknown_chars).into();
+        self.cache.insert(name.to_string(), normalized.clone());
+        normalized
+    }
+}
+
+impl Default for NameNormalizer {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+/// Normalize a distribution name like [`normalize_package_name`]. Unless `preserve_unknown_chars`
+/// is set, characters outside of the PEP 503 set are treated as separators.
+pub fn normalize_package_name_with(name: &str, preserve_unknown_chars: bool) -> String {
+    let is_separator = |c: char| {
+        c.is_whitespace()
+            || c == '-'
+            || c == '_'
+            || c == '.'
+            || (!preserve_unknown_chars && !c.is_ascii_alphanumeric())
+    };
+    name.to_lowercase()
+        .split(is_separator)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>()
+        .join("_")
+}
+
+fn extract_source_paths(toml_value: &Value, project_root: &Path) -> Vec<PathBuf> {
+    extract_source_paths_with_layout(toml_value, project_root, None)
+}
+
+/// Find source paths like [`extract_source_paths`]. When `layout_template` is set, the source paths
+/// of uv workspace members are expanded from it rather than detected from each member's manifest.
+fn extract_source_paths_with_layout(
+    toml_value: &Value,
+    project_root: &Path,
+    layout_template: Option<&str>,
+) -> Vec<PathBuf> {
+    // Explicitly configured source roots bypass build backend detection entirely
+    if let Some(source_roots) = toml_value
+        .get("tool")
+        .and_then(|t| t.get("tach"))
+        .and_then(|tach| tach.get("source_roots"))
+        .and_then(|s| s.as_array())
+    {
+        return source_roots
+            .iter()
+            .filter_map(|source_root| source_root.as_str())
+            .map(|source_root| project_root.join(source_root))
+            .collect();
+    }
+
+    let mut source_paths = Vec::new();
+
+    // Check for setuptools configuration. `package-dir` maps package names to directories,
+    // with the empty name standing for the directory which holds every other package.
+    let setuptools = toml_value.get("tool").and_then(|t| t.get("setuptools"));
+    let package_dir = setuptools
+        .and_then(|setuptools| setuptools.get("package-dir"))
+        .and_then(|p| p.as_table());
+    let package_root = project_root.join(
+        package_dir
+            .and_then(|package_dir| package_dir.get(""))
+            .and_then(|root| root.as_str())
+            .unwrap_or(""),
+    );
+    let packages = setuptools.and_then(|setuptools| setuptools.get("packages"));
+    if let Some(find) = packages.and_then(|p| p.get("find")) {
+        source_paths.extend(find_setuptools_packages(find, project_root));
+    } else {
+        match packages.and_then(|p| p.as_array()) {
+            Some(packages) => {
+                for package_name in packages.iter().filter_map(|package| package.as_str()) {
+                    source_paths.push(setuptools_package_path(
+                        package_name,
+                        package_dir,
+                        project_root,
+                    ));
+                }
+            }
+            // Discovered packages live beneath the mapped root directory
+            None if package_root != project_root => source_paths.push(package_root.clone()),
+            None => {}
+        }
+    }
+
+    // Single-module layouts list top-level modules rather than packages
+    if let Some(py_modules) = setuptools
+        .and_then(|setuptools| setuptools.get("py-modules"))
+        .and_then(|p| p.as_array())
+    {
+        for module_name in py_modules.iter().filter_map(|module| module.as_str()) {
+            source_paths.push(package_root.join(format!("{module_name}.py")));
+        }
+    }
+
+    // Check for poetry configuration. A package's 'to' changes its import root, not its source path.
+    for (source_path, _) in poetry_wheel_packages(toml_value, project_root) {
+        if !source_paths.contains(&source_path) {
+            source_paths.push(source_path);
+        }
+    }
+
+    // Check for maturin configuration
+    if let Some(maturin) = toml_value.get("tool").and_then(|t| t.get("maturin")) {
+        source_paths.extend(maturin_source_paths(maturin, project_root));
+    }
+
+    // Check for flit configuration
+    if let Some(module_path) = flit_module_path(toml_value, project_root) {
+        source_paths.push(module_path);
+    }
+
+    // Check for scikit-build-core configuration. Packages are listed by their source directory.
+    // 'wheel.install-dir' is the install prefix of the CMake build, which does not apply to them.
+    if let Some(packages) = toml_value
+        .get("tool")
+        .and_then(|t| t.get("scikit-build"))
+        .and_then(|s| s.get("wheel"))
+        .and_then(|w| w.get("packages"))
+        .and_then(|p| p.as_array())
+    {
+        for package_path in packages.iter().filter_map(|package| package.as_str()) {
+            source_paths.push(project_root.join(package_path));
+        }
+    }
+
+    // If no specific configuration found, use conventional locations
+    if source_paths.is_empty() {
+        let src_dir = project_root.join("src");
+        if src_dir.exists() {
+            source_paths.push(src_dir);
+        } else {
+            source_paths.push(project_root.to_path_buf());
+        }
+    }
+
+    let member_roots = uv_workspace_members(toml_value, project_root);
+    if let Some(layout_template) = layout_template {
+        for member_source_path in
+            expand_source_layout_template(layout_template, project_root, &member_roots)
+        {
+            if !source_paths.contains(&member_source_path) {
+                source_paths.push(member_source_path);
+            }
+        }
+        return source_paths;
+    }
+
+    // Each uv workspace member contributes the source paths of its own pyproject.toml
+    for member_root in member_roots {
+        let member_value = fs::read_to_string(member_root.join("pyproject.toml"))
+            .ok()
+            .and_then(|content| toml::from_str::<Value>(&content).ok());
+        if let Some(member_value) = member_value {
+            for member_source_path in extract_source_paths(&member_value, &member_root) {
+                if !source_paths.contains(&member_source_path) {
+                    source_paths.push(member_source_path);
+                }
+            }
+        }
+    }
+
+    source_paths
+}
+
+/// The source paths of a maturin project. `python-source` holds the Python packages, including those
+/// listed in `python-packages`. Without it, mixed projects keep the Python package named by the first
+/// component of `module-name` in the project root.
+fn maturin_source_paths(maturin: &Value, project_root: &Path) -> Vec<PathBuf> {
+    let python_source = maturin.get("python-source").and_then(|ps| ps.as_str());
+    let python_root = project_root.join(python_source.unwrap_or(""));
+    let mut source_paths = Vec::new();
+    if python_source.is_some() {
+        source_paths.push(python_root.clone());
+    } else if let Some(module_name) = maturin.get("module-name").and_then(|m| m.as_str()) {
+        let package_dir = project_root.join(module_name.split('.').next().unwrap_or(module_name));
+        if package_dir.is_dir() {
+            source_paths.push(package_dir);
+        }
+    }
+    for package in maturin
+        .get("python-packages")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|package| package.as_str())
+    {
+        let package_dir = python_root.join(package);
+        if !source_paths.contains(&package_dir) {
+            source_paths.push(package_dir);
+        }
+    }
+    source_paths
+}
+
+/// The package directory or module file of a flit project's module, named by `[tool.flit.module] name`
+/// (or the older `[tool.flit.metadata] module`). As in flit, the module is looked for in the project
+/// root and then in `src`, and a dotted name refers to a package nested within namespace packages.
+fn flit_module_path(toml_value: &Value, project_root: &Path) -> Option<PathBuf> {
+    let flit = toml_value.get("tool").and_then(|t| t.get("flit"))?;
+    let module_name = flit
+        .get("module")
+        .and_then(|m| m.get("name"))
+        .or_else(|| flit.get("metadata").and_then(|m| m.get("module")))
+        .and_then(|name| name.as_str())?;
+    let relative_path: PathBuf = module_name.split('.').collect();
+    [project_root.to_path_buf(), project_root.join("src")]
+        .into_iter()
+        .find_map(|base| {
+            let package_dir = base.join(&relative_path);
+            let module_file = package_dir.with_extension("py");
+            if package_dir.is_dir() {
+                Some(package_dir)
+            } else {
+                module_file.is_file().then_some(module_file)
+            }
+        })
+}
+
+/// The source path of each poetry `packages` entry which is built into wheels, with the package it is
+/// installed beneath. Poetry installs `{ include = "mypkg", from = "src", to = "dist" }` as `dist/mypkg`,
+/// so it is imported as `dist.mypkg`. Packages which are only built into sdists are never installed.
+fn poetry_wheel_packages(
+    toml_value: &Value,
+    project_root: &Path,
+) -> Vec<(PathBuf, Option<String>)> {
+    let Some(packages) = toml_value
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("packages"))
+        .and_then(|p| p.as_array())
+    else {
+        return Vec::new();
+    };
+    packages
+        .iter()
+        .filter(|package| is_poetry_wheel_package(package))
+        .filter_map(|package| {
+            let include = package.get("include").and_then(|i| i.as_str())?;
+            let from = package.get("from").and_then(|f| f.as_str()).unwrap_or("");
+            let import_root = package
+                .get("to")
+                .and_then(|t| t.as_str())
+                .map(|to| {
+                    to.split('/')
+                        .filter(|part| !part.is_empty() && *part != ".")
+                        .collect::<Vec<&str>>()
+                        .join(".")
+                })
+                .filter(|import_root| !import_root.is_empty());
+            Some((project_root.join(from).join(include), import_root))
+        })
+        .collect()
+}
+
+/// Whether a poetry `packages` entry is built into wheels. Without a `format`, it is built into both
+/// sdists and wheels.
+fn is_poetry_wheel_package(package: &Value) -> bool {
+    match package.get("format") {
+        Some(Value::String(format)) => format == "wheel",
+        Some(Value::Array(formats)) => formats
+            .iter()
+            .any(|format| format.as_str() == Some("wheel")),
+        _ => true,
+    }
+}
+
+/// The directory of a (possibly dotted) package under a setuptools `package-dir` mapping.
+/// As in setuptools, the longest mapped prefix of the package name wins, and the remaining
+/// components are nested beneath it, so `app.api` lives in `lib/app/api` given `app = "lib/app"`.
+fn setuptools_package_path(
+    package_name: &str,
+    package_dir: Option<&toml::Table>,
+    project_root: &Path,
+) -> PathBuf {
+    let components: Vec<&str> = package_name.split('.').collect();
+    let mapped_prefix = (0..=components.len()).rev().find_map(|prefix_len| {
+        let prefix = components[..prefix_len].join(".");
+        package_dir
+            .and_then(|package_dir| package_dir.get(&prefix))
+            .and_then(|path| path.as_str())
+            .map(|path| (prefix_len, project_root.join(path)))
+    });
+    let (prefix_len, base) = mapped_prefix.unwrap_or_else(|| (0, project_root.to_path_buf()));
+    components[prefix_len..]
+        .iter()
+        .fold(base, |path, component| path.join(component))
+}
+
+/// Discover packages the way setuptools does for a `[tool.setuptools.packages.find]` table.
+///
+/// Each of the `where` directories (by default, the project root) is walked for packages whose dotted
+/// names match an `include` pattern (by default, any) and no `exclude` pattern. Directories without an
+/// `__init__.py` are only packages when `namespaces` is enabled, as it is by default. The directory of
+/// each top-level package containing a match is returned, so that (namespace) subpackages such as
+/// `myorg.utils` still resolve from the `myorg` directory.
+fn find_setuptools_packages(find: &Value, project_root: &Path) -> Vec<PathBuf> {
+    let patterns = |key: &str| -> Option<Vec<glob::Pattern>> {
+        find.get(key).and_then(|p| p.as_array()).map(|patterns| {
+            patterns
+                .iter()
+                .filter_map(|pattern| pattern.as_str())
+                .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+                .collect()
+        })
+    };
+    let include = patterns("include");
+    let exclude = patterns("exclude").unwrap_or_default();
+    let namespaces = find
+        .get("namespaces")
+        .and_then(|n| n.as_bool())
+        .unwrap_or(true);
+    let is_selected = |package_name: &str| {
+        include
+            .as_ref()
+            .is_none_or(|include| include.iter().any(|pattern| pattern.matches(package_name)))
+            && !exclude.iter().any(|pattern| pattern.matches(package_name))
+    };
+
+    fn discover(
+        directory: &Path,
+        parent_package: Option<&str>,
+        namespaces: bool,
+        is_selected: &dyn Fn(&str) -> bool,
+        packages: &mut Vec<String>,
+    ) {
+        let Ok(entries) = fs::read_dir(directory) else {
+            return;
+        };
+        let mut children: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        children.sort();
+        for child in children {
+            let Some(name) = child.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            // Only identifiers can be imported as packages
+            if name.starts_with('.') || name.contains(['.', '-', ' ']) || name == "__pycache__" {
+                continue;
+            }
+            if !namespaces && !child.join("__init__.py").is_file() {
+                continue;
+            }
+            let package_name = match parent_package {
+                Some(parent_package) => format!("{parent_package}.{name}"),
+                None => name.to_string(),
+            };
+            if is_selected(&package_name) {
+                packages.push(package_name);
+            } else {
+                discover(
+                    &child,
+                    Some(&package_name),
+                    namespaces,
+                    is_selected,
+                    packages,
+                );
+            }
+        }
+    }
+
+    let where_directories: Vec<&str> = find
+        .get("where")
+        .and_then(|w| w.as_array())
+        .map(|directories| directories.iter().filter_map(|d| d.as_str()).collect())
+        .unwrap_or_else(|| vec!["."]);
+    let mut source_paths = Vec::new();
+    for where_directory in where_directories {
+        let directory = normalize_lexically(&project_root.join(where_directory));
+        let mut packages = Vec::new();
+        discover(&directory, None, namespaces, &is_selected, &mut packages);
+        for package in packages {
+            let top_level = package.split('.').next().unwrap_or(&package);
+            let source_path = directory.join(top_level);
+            if !source_paths.contains(&source_path) {
+                source_paths.push(source_path);
+            }
+        }
+    }
+    source_paths
+}
+
+/// Expand a source layout template such as `"{member}/src"` into a source path for each member.
+/// `{member}` is replaced with the member's path relative to the project root, and `{name}` with
+/// the name of its directory, so `"{member}/src/myorg/{name}"` fits a `<member>/src/<namespace>/<member>` layout.
+pub fn expand_source_layout_template(
+    template: &str,
+    project_root: &Path,
+    member_roots: &[PathBuf],
+) -> Vec<PathBuf> {
+    let mut source_paths = Vec::new();
+    for member_root in member_roots {
+        let relative_root = member_root
+            .strip_prefix(project_root)
+            .unwrap_or(member_root);
+        let name = member_root
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let source_path = project_root.join(
+            template
+                .replace("{member}", &relative_root.to_string_lossy())
+                .replace("{name}", &name),
+        );
+        if !source_paths.contains(&source_path) {
+            source_paths.push(source_path);
+        }
+    }
+    source_paths
+}
+
+/// The member directories of a uv workspace, found by expanding the `members` globs of
+/// `[tool.uv.workspace]` relative to the project root, less those matched by its `exclude` globs.
+/// Only directories containing a pyproject.toml are members.
+fn uv_workspace_members(toml_value: &Value, project_root: &Path) -> Vec<PathBuf> {
+    let Some(workspace) = toml_value
+        .get("tool")
+        .and_then(|t| t.get("uv"))
+        .and_then(|uv| uv.get("workspace"))
+    else {
+        return vec![];
+    };
+    let globs = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(|g| g.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|pattern| pattern.as_str())
+            .map(|pattern| project_root.join(pattern).display().to_string())
+            .collect()
+    };
+    let excludes: Vec<glob::Pattern> = globs("exclude")
+        .iter()
+        .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+        .collect();
+
+    let mut members: Vec<PathBuf> = globs("members")
+        .iter()
+        .filter_map(|pattern| glob::glob(pattern).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.join("pyproject.toml").is_file())
+        .filter(|path| !excludes.iter().any(|exclude| exclude.matches_path(path)))
+        .collect();
+    members.sort();
+    members.dedup();
+    members
+}
+
+/// Resolve `.` and `..` components without touching the filesystem
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Check that each source path stays within `project_root`, since a path escaping it
+/// (via `..` or an absolute path) would make Tach scan unexpected directories.
+/// Escaping paths are reported as warnings, or as an error with `strict_source_paths`.
+/// Nothing is reported when `allow_external_source_paths` is set.
+pub fn validate_source_paths(
+    project_root: &Path,
+    source_paths: &[PathBuf],
+    config: &ExternalDependencyConfig,
+) -> Result<Vec<ParsingWarning>> {
+    if config.allow_external_source_paths {
+        return Ok(vec![]);
+    }
+
+    let project_root = normalize_lexically(project_root);
+    let mut warnings = Vec::new();
+    for source_path in source_paths {
+        if normalize_lexically(&project_root.join(source_path)).starts_with(&project_root) {
+            continue;
+        }
+        let path = source_path.display().to_string();
+        if config.strict_source_paths {
+            return Err(error::ParsingError::SourcePathOutsideProject { path });
+        }
+        warnings.push(ParsingWarning::SourcePathOutsideProject { path });
+    }
+    Ok(warnings)
+}
+
+/// Resolve each component of `path` below `root` to the case used on disk.
+/// Components which do not exist are kept as written.
+fn resolve_on_disk_case(root: &Path, path: &Path) -> PathBuf {
+    let Ok(relative_path) = path.strip_prefix(root) else {
+        return path.to_path_buf();
+    };
+
+    let mut resolved = root.to_path_buf();
+    let mut components = relative_path.components();
+    for component in components.by_ref() {
+        let name = component.as_os_str();
+        let on_disk_name = fs::read_dir(&resolved).ok().and_then(|entries| {
+            let entries: Vec<_> = entries.filter_map(|entry| entry.ok()).collect();
+            entries
+                .iter()
+                .find(|entry| entry.file_name() == name)
+                .or_else(|| {
+                    entries.iter().find(|entry| {
+                        entry
+                            .file_name()
+                            .to_string_lossy()
+                            .eq_ignore_ascii_case(&name.to_string_lossy())
+                    })
+                })
+                .map(|entry| entry.file_name())
+        });
+        match on_disk_name {
+            Some(on_disk_name) => resolved.push(on_disk_name),
+            None => {
+                resolved.push(name);
+                break;
+            }
+        }
+    }
+    resolved.extend(components);
+    resolved
+}
+
+/// Join lines ending with a backslash continuation (e.g. pip-compile hashes) into single lines
+fn join_continued_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for line in content.lines() {
+        if let Some(continued) = line.trim_end().strip_suffix('\\') {
+            current.push_str(continued);
+            current.push(' ');
+        } else {
+            current.push_str(line);
+            lines.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Names found in a requirements file and the files it includes
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RequirementsInfo {
+    /// Dependencies declared directly or through `-r` includes
+    pub dependencies: HashSet<String>,
+    /// Names which only appear in `-c` constraint files. These pin versions without declaring a dependency.
+    pub constraints: HashSet<String>,
+    /// Local directories installed with `-e`, which hold packages developed alongside the project.
+    /// These are not dependencies: the package resolver treats their modules as first-party.
+    pub editable_paths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RequirementsInclude {
+    Requirement,
+    Constraint,
+}
+
+/// The value of an option line such as `-r base.txt`, `-rbase.txt`, `--requirement base.txt`
+/// or `--requirement=base.txt`, without any trailing comment
+fn strip_requirements_option<'a>(line: &'a str, short: &str, long: &str) -> Option<&'a str> {
+    let value = if let Some(rest) = line.strip_prefix(long) {
+        match rest.strip_prefix('=') {
+            Some(value) => value,
+            None if rest.starts_with(char::is_whitespace) => rest,
+            None => return None,
+        }
+    } else {
+        line.strip_prefix(short)?
+    };
+    let value = value.split(" #").next().unwrap_or_default().trim();
+    (!value.is_empty()).then_some(value)
+}
+
+/// Parse an include directive such as `-r base.txt` or `--constraint=constraints.txt`
+fn parse_requirements_include(line: &str) -> Option<(RequirementsInclude, &str)> {
+    strip_requirements_option(line, "-r", "--requirement")
+        .map(|target| (RequirementsInclude::Requirement, target))
+        .or_else(|| {
+            strip_requirements_option(line, "-c", "--constraint")
+                .map(|target| (RequirementsInclude::Constraint, target))
+        })
+}
+
+/// The project name in the `#egg=` fragment of a VCS or direct URL
+fn egg_fragment_name(url: &str) -> Option<&str> {
+    let (_, fragment) = url.split_once("#egg=")?;
+    fragment.split('&').next().filter(|name| !name.is_empty())
+}
+
+fn is_url_requirement(requirement: &str) -> bool {
+    requirement.contains("://") || requirement.starts_with("file:")
+}
+
+/// The distribution name of a VCS or direct URL requirement, from its `#egg=` fragment
+/// or from the wheel filename it points to
+fn extract_url_package_name(url: &str) -> Option<&str> {
+    if let Some(egg_name) = egg_fragment_name(url) {
+        return Some(egg_name);
+    }
+    let path = url.split(['#', '?']).next().unwrap_or(url);
+    let filename = path.rsplit('/').next()?;
+    // PEP 427: the distribution name is everything before the first '-' of the wheel filename
+    filename
+        .strip_suffix(".whl")
+        .and_then(|stem| stem.split_once('-'))
+        .map(|(name, _)| name)
+}
+
+pub fn parse_requirements_txt(
+    requirements_path: &Path,
+    normalizer: &mut NameNormalizer,
+) -> Result<HashSet<String>> {
+    Ok(parse_requirements(requirements_path, normalizer)?.dependencies)
+}
+
+/// Parse a requirements file, following `-r` and `-c` includes relative to the including file
+pub fn parse_requirements(
+    requirements_path: &Path,
+    normalizer: &mut NameNormalizer,
+) -> Result<RequirementsInfo> {
+    let mut info = RequirementsInfo::default();
+    let mut visited = HashSet::new();
+    parse_requirements_file(
+        requirements_path,
+        RequirementsInclude::Requirement,
+        &mut visited,
+        normalizer,
+        &mut info,
+    )?;
+    info.constraints
+        .retain(|name| !info.dependencies.contains(name));
+    Ok(info)
+}
+
+/// Parse in-memory requirements.txt content, as if it were located in `base_dir`.
+/// Includes are resolved relative to `base_dir`.
+pub fn parse_requirements_str(
+    content: &str,
+    base_dir: &Path,
+    normalizer: &mut NameNormalizer,
+) -> Result<HashSet<String>> {
+    let mut info = RequirementsInfo::default();
+    let mut visited = HashSet::new();
+    parse_requirements_content(
+        content,
+        base_dir,
+        RequirementsInclude::Requirement,
+        &mut visited,
+        normalizer,
+        &mut info,
+    )?;
+    Ok(info.dependencies)
+}
+
+fn parse_requirements_file(
+    requirements_path: &Path,
+    kind: RequirementsInclude,
+    visited: &mut HashSet<PathBuf>,
+    normalizer: &mut NameNormalizer,
+    info: &mut RequirementsInfo,
+) -> Result<()> {
+    let canonical_path = requirements_path.canonicalize()?;
+    // `visited` only holds the files currently being parsed, so a file may be included from several places
+    if !visited.insert(canonical_path.clone()) {
+        return Err(error::ParsingError::RequirementsIncludeCycle {
+            path: requirements_path.display().to_string(),
+        });
+    }
+    let content = fs::read_to_string(&canonical_path)?;
+    let base_dir = requirements_path.parent().unwrap_or(Path::new("."));
+    parse_requirements_content(&content, base_dir, kind, visited, normalizer, info)?;
+    visited.remove(&canonical_path);
+    Ok(())
+}
+
+fn parse_requirements_content(
+    content: &str,
+    base_dir: &Path,
+    kind: RequirementsInclude,
+    visited: &mut HashSet<PathBuf>,
+    normalizer: &mut NameNormalizer,
+    info: &mut RequirementsInfo,
+) -> Result<()> {
+    for line in join_continued_lines(content) {
+        // Skip comments and empty lines
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let package_name = if let Some(target) = strip_requirements_option(line, "-e", "--editable")
+        {
+            if !is_url_requirement(target) {
+                let editable_path = normalize_lexically(&base_dir.join(target));
+                if !info.editable_paths.contains(&editable_path) {
+                    info.editable_paths.push(editable_path);
+                }
+                continue;
+            }
+            match extract_url_package_name(target) {
+                Some(package_name) => package_name,
+                None => continue,
+            }
+        } else if line.starts_with('-') {
+            if let Some((include_kind, target)) = parse_requirements_include(line) {
+                // Anything included from a constraint file is also a constraint
+                let include_kind = if kind == RequirementsInclude::Constraint {
+                    RequirementsInclude::Constraint
+                } else {
+                    include_kind
+                };
+                parse_requirements_file(
+                    &base_dir.join(target),
+                    include_kind,
+                    visited,
+                    normalizer,
+                    info,
+                )?;
+            }
+            // Skip other options
+            continue;
+        } else if let Some(url) = line
+            .split_whitespace()
+            .next()
+            .filter(|r| is_url_requirement(r))
+        {
+            // A bare URL, as opposed to a PEP 508 `name @ url` requirement
+            match extract_url_package_name(url) {
+                Some(package_name) => package_name,
+                None => continue,
+            }
+        } else {
+            extract_package_name(line)
+        };
+        let normalized_name = normalizer.normalize(package_name);
+
+        if !is_excluded_dependency(&normalized_name) {
+            let names = match kind {
+                RequirementsInclude::Requirement => &mut info.dependencies,
+                RequirementsInclude::Constraint => &mut info.constraints,
+            };
+            if !names.contains(&*normalized_name) {
+                names.insert(normalized_name.to_string());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The name of a Pipfile entry, preferring the `#egg=` name of a VCS URL over the key
+fn pipfile_entry_name(key: &str, value: &Value, normalizer: &mut NameNormalizer) -> String {
+    let egg_name = ["git", "hg", "svn", "bzr", "file"]
+        .iter()
+        .filter_map(|vcs| value.get(vcs).and_then(|url| url.as_str()))
+        .find_map(egg_fragment_name);
+    normalizer.normalize(egg_name.unwrap_or(key)).to_string()
+}
+
+/// Extract package names from the `[packages]` table of a pipenv `Pipfile`,
+/// and from `[dev-packages]` when `include_dev` is set.
+pub fn parse_pipfile(
+    pipfile_path: &Path,
+    include_dev: bool,
+    normalizer: &mut NameNormalizer,
+) -> Result<HashSet<String>> {
+    let content = fs::read_to_string(pipfile_path)?;
+    let toml_value: Value = toml::from_str(&content)?;
+    let sections: &[&str] = if include_dev {
+        &["packages", "dev-packages"]
+    } else {
+        &["packages"]
+    };
+
+    Ok(sections
+        .iter()
+        .filter_map(|section| toml_value.get(section).and_then(|s| s.as_table()))
+        .flatten()
+        .map(|(key, value)| pipfile_entry_name(key, value, normalizer))
+        .filter(|pkg_name| !pkg_name.is_empty())
+        .collect())
+}
+
+/// Extract package names from the `default` object of a pipenv `Pipfile.lock`,
+/// and from `develop` when `include_dev` is set.
+pub fn parse_pipfile_lock(
+    lock_path: &Path,
+    include_dev: bool,
+    normalizer: &mut NameNormalizer,
+) -> Result<HashSet<String>> {
+    let content = fs::read_to_string(lock_path)?;
+    let json_value: serde_json::Value = serde_json::from_str(&content)?;
+    let sections: &[&str] = if include_dev {
+        &["default", "develop"]
+    } else {
+        &["default"]
+    };
+
+    Ok(sections
+        .iter()
+        .filter_map(|section| json_value.get(section).and_then(|s| s.as_object()))
+        .flat_map(|packages| packages.keys())
+        .map(|pkg_name| normalizer.normalize(pkg_name).to_string())
+        .filter(|pkg_name| !pkg_name.is_empty())
+        .collect())
+}
+
+/// Extract package names from a `conda-lock.yml` file.
+///
+/// Only the `package` section is inspected, which is either a list of package entries
+/// (unified layout) or a mapping from platform to such a list (per-platform layout).
+/// This is a line-based scan rather than a full YAML parse, since only the `name` key
+/// of each entry is needed.
+pub fn parse_conda_lock(
+    lock_path: &Path,
+    normalizer: &mut NameNormalizer,
+) -> Result<HashSet<String>> {
+    let content = fs::read_to_string(lock_path)?;
+    let mut dependencies = HashSet::new();
+    let mut in_package_section = false;
+    let mut entry_key_indent = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+
+        // A new top-level key ends the package section (list items may sit at column 0)
+        if indent == 0 && !trimmed.starts_with('-') {
+            in_package_section = trimmed.trim_end() == "package:";
+            entry_key_indent = None;
+            continue;
+        }
+        if !in_package_section {
+            continue;
+        }
+
+        let (key_indent, key_value) = match trimmed.strip_prefix("- ") {
+            Some(rest) => {
+                let key_indent = indent + 2 + (rest.len() - rest.trim_start().len());
+                entry_key_indent = Some(key_indent);
+                (key_indent, rest.trim_start())
+            }
+            None => (indent, trimmed),
+        };
+
+        if entry_key_indent != Some(key_indent) {
+            continue;
+        }
+        if let Some(name) = key_value.strip_prefix("name:") {
+            let name = name.trim().trim_matches(|c| c == '"' || c == '\'');
+            if !name.is_empty() {
+                dependencies.insert(normalizer.normalize(name).to_string());
+            }
+        }
+    }
+
+    Ok(dependencies)
+}
+
+/// Parse the dependencies of a conda `environment.yml`.
+///
+/// This reads the conda packages of the top-level `dependencies:` list, along with the pip
+/// requirements nested under its `- pip:` entry. The interpreter and pip itself are not
+/// importable dependencies, so they are skipped, as are pip options such as `-r`.
+pub fn parse_conda_environment(
+    environment_path: &Path,
+    normalizer: &mut NameNormalizer,
+) -> Result<HashSet<String>> {
+    const EXCLUDED_DEPS: [&str; 2] = ["python", "pip"];
+
+    let content = fs::read_to_string(environment_path)?;
+    let mut dependencies = HashSet::new();
+    let mut in_dependencies_section = false;
+    // The indentation of the `- pip:` entry, while reading its nested requirements
+    let mut pip_entry_indent = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+
+        // A new top-level key ends the dependencies section (list items may sit at column 0)
+        if indent == 0 && !trimmed.starts_with('-') {
+            in_dependencies_section = trimmed.trim_end() == "dependencies:";
+            pip_entry_indent = None;
+            continue;
+        }
+        if !in_dependencies_section {
+            continue;
+        }
+        let Some(entry) = trimmed.strip_prefix('-') else {
+            continue;
+        };
+        let entry = entry
+            .split(" #")
+            .next()
+            .unwrap_or(entry)
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'');
+
+        let is_pip_requirement = pip_entry_indent.is_some_and(|pip_indent| indent > pip_indent);
+        let name = if is_pip_requirement {
+            if entry.starts_with('-') {
+                continue;
+            }
+            normalizer.normalize(extract_package_name(entry))
+        } else if entry == "pip:" {
+            pip_entry_indent = Some(indent);
+            continue;
+        } else {
+            pip_entry_indent = None;
+            normalizer.normalize(extract_conda_package_name(entry))
+        };
+        if !name.is_empty() && !EXCLUDED_DEPS.contains(&&*name) {
+            dependencies.insert(name.to_string());
+        }
+    }
+
+    Ok(dependencies)
+}
+
+/// Extract packages named by `constraint-dependencies` and `override-dependencies` in `[tool.uv]`.
+///
+/// These pin or replace versions anywhere in the resolution, so they are known to be
+/// installed without being direct dependencies of the project.
+pub fn parse_uv_constraints(
+    pyproject_path: &Path,
+    normalizer: &mut NameNormalizer,
+) -> Result<HashSet<String>> {
+    let content = fs::read_to_string(pyproject_path)?;
+    let toml_value: Value = toml::from_str(&content)?;
+    let Some(uv) = toml_value.get("tool").and_then(|tool| tool.get("uv")) else {
+        return Ok(HashSet::new());
+    };
+
+    Ok(["constraint-dependencies", "override-dependencies"]
+        .iter()
+        .filter_map(|key| uv.get(key).and_then(|deps| deps.as_array()))
+        .flatten()
+        .filter_map(|dep| dep.as_str())
+        .map(|dep| {
+            normalizer
+                .normalize(extract_package_name(dep.trim()))
+                .to_string()
+        })
+        .filter(|pkg_name| !pkg_name.is_empty())
+        .collect())
+}
+
+/// Options of `pip install` which consume the following argument
+const PIP_INSTALL_OPTIONS_WITH_VALUE: [&str; 10] = [
+    "-r",
+    "--requirement",
+    "-c",
+    "--constraint",
+    "-e",
+    "--editable",
+    "-i",
+    "--index-url",
+    "--extra-index-url",
+    "--target",
+];
+
+/// Extract packages named by `pip install` commands mentioned anywhere in `content`,
+/// such as in a comment of a script or a `RUN` instruction of a Dockerfile.
+/// Options, paths and URLs are skipped, and a command ends at a shell separator or comment.
+pub fn parse_pip_install_mentions(
+    content: &str,
+    normalizer: &mut NameNormalizer,
+) -> HashSet<String> {
+    let mut packages = HashSet::new();
+    for line in content.lines() {
+        let mut remainder = line;
+        while let Some(index) = remainder.find("pip install ") {
+            remainder = &remainder[index + "pip install ".len()..];
+            let command = remainder
+                .split([';', '&', '|', '#'])
+                .next()
+                .unwrap_or_default();
+            let mut skip_next = false;
+            for token in command.split_whitespace() {
+                let token = token.trim_matches(|c| c == '"' || c == '\'');
+                if std::mem::take(&mut skip_next) {
+                    continue;
+                }
+                if token.starts_with('-') {
+                    skip_next = PIP_INSTALL_OPTIONS_WITH_VALUE.contains(&token);
+                    continue;
+                }
+                if token.contains(['/', '\\', ':']) || token.starts_with('.') {
+                    continue;
+                }
+                let pkg_name = normalizer.normalize(extract_package_name(token));
+                if !pkg_name.is_empty() {
+                    packages.insert(pkg_name.to_string());
+                }
+            }
+        }
+    }
+    packages
+}
+
+/// The options of each section of an INI-style file such as `setup.cfg`, keyed by lowercased
+/// section and option names. Continuation lines are joined to their option's value with newlines.
+fn parse_ini_sections(content: &str) -> HashMap<String, Vec<(String, String)>> {
+    let mut sections: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    let mut section = String::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if let Some((_, value)) = sections
+                .get_mut(&section)
+                .and_then(|options| options.last_mut())
+            {
+                value.push('\n');
+                value.push_str(trimmed);
+            }
+        } else if let Some(name) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            section = name.trim().to_lowercase();
+            sections.entry(section.clone()).or_default();
+        } else if let Some((key, value)) = trimmed.split_once(['=', ':']) {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .push((key.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    sections
+}
+
+fn ini_option<'a>(
+    sections: &'a HashMap<String, Vec<(String, String)>>,
+    section: &str,
+    key: &str,
+) -> Option<&'a str> {
+    sections
+        .get(section)?
+        .iter()
+        .find(|(option, _)| option == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Split a `setup.cfg` list, written either on continuation lines or separated by commas
+fn ini_list(value: &str) -> Vec<&str> {
+    let separator = if value.contains('\n') { '\n' } else { ',' };
+    value
+        .split(separator)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Split a `setup.cfg` list of requirements. Requirements may contain commas (e.g. `a>=1,<2`),
+/// so a single-line value is one requirement unless it is separated by semicolons.
+fn ini_requirements(value: &str) -> Vec<Value> {
+    let requirements: Vec<&str> = if value.contains('\n') {
+        value.lines().collect()
+    } else {
+        vec![value]
+    };
+    requirements
+        .into_iter()
+        .map(str::trim)
+        .filter(|requirement| !requirement.is_empty() && !requirement.starts_with('#'))
+        .map(|requirement| Value::String(requirement.to_string()))
+        .collect()
+}
+
+/// Whether the `setup.cfg` holds setuptools metadata rather than only tool configuration
+pub fn is_setuptools_setup_cfg(setup_cfg_path: &Path) -> bool {
+    fs::read_to_string(setup_cfg_path).is_ok_and(|content| {
+        let sections = parse_ini_sections(&content);
+        sections.contains_key("metadata") || sections.contains_key("options")
+    })
+}
+
+pub fn parse_setup_cfg(
+    setup_cfg_path: &Path,
+    config: &ExternalDependencyConfig,
+) -> Result<ProjectInfo> {
+    parse_setup_cfg_with(setup_cfg_path, config, &mut config.name_normalizer())
+}
+
+/// Parse a `setup.cfg` like [`parse_setup_cfg`], with a normalizer which may be shared
+/// with the other manifests of a workspace
+pub fn parse_setup_cfg_with(
+    setup_cfg_path: &Path,
+    config: &ExternalDependencyConfig,
+    normalizer: &mut NameNormalizer,
+) -> Result<ProjectInfo> {
+    let content = fs::read_to_string(setup_cfg_path)?;
+    parse_setup_cfg_content(
+        &content,
+        setup_cfg_path.parent().unwrap(),
+        config,
+        normalizer,
+    )
+}
+
+/// Parse in-memory setuptools `setup.cfg` content, as if it were located in `base_dir`.
+///
+/// This reads `[metadata] name`, `install_requires`, `packages` and `package_dir` from `[options]`,
+/// and the selected extras of `[options.extras_require]`. Source paths are found the same way
+/// as for the equivalent `[tool.setuptools]` table of a pyproject.toml.
+pub fn parse_setup_cfg_str(
+    content: &str,
+    base_dir: &Path,
+    config: &ExternalDependencyConfig,
+) -> Result<ProjectInfo> {
+    parse_setup_cfg_content(content, base_dir, config, &mut config.name_normalizer())
+}
+
+fn parse_setup_cfg_content(
+    content: &str,
+    base_dir: &Path,
+    config: &ExternalDependencyConfig,
+    normalizer: &mut NameNormalizer,
+) -> Result<ProjectInfo> {
+    let sections = parse_ini_sections(content);
+    let name = ini_option(&sections, "metadata", "name").map(str::to_string);
+    let mut warnings = Vec::new();
+
+    let mut dependencies = ExtractedDependencies::new(normalizer);
+    if let Some(install_requires) = ini_option(&sections, "options", "install_requires") {
+        extract_deps_from_value(
+            &mut dependencies,
+            &Value::Array(ini_requirements(install_requires)),
+            &DependencySource::Project,
+            &config.target_environment,
+            &mut warnings,
+        );
+    }
+    for (extra_name, requirements) in sections.get("options.extras_require").into_iter().flatten() {
+        if config.is_dependency_group_included(extra_name) {
+            extract_deps_from_value(
+                &mut dependencies,
+                &Value::Array(ini_requirements(requirements)),
+                &DependencySource::Extra(normalize_package_name(extra_name)),
+                &config.target_environment,
+                &mut warnings,
+            );
+        }
+    }
+
+    // Translate the options into a `[tool.setuptools]` table for `extract_source_paths`
+    let mut setuptools = toml::Table::new();
+    if let Some(packages) = ini_option(&sections, "options", "packages")
+        .filter(|packages| !packages.starts_with("find"))
+    {
+        setuptools.insert(
+            "packages".to_string(),
+            Value::Array(ini_list(packages).into_iter().map(Value::from).collect()),
+        );
+    }
+    if let Some(package_dir) = ini_option(&sections, "options", "package_dir") {
+        let package_dir: toml::Table = ini_list(package_dir)
+            .into_iter()
+            .filter_map(|mapping| mapping.split_once('='))
+            .map(|(package, path)| (package.trim().to_string(), Value::from(path.trim())))
+            .collect();
+        setuptools.insert("package-dir".to_string(), Value::Table(package_dir));
+    }
+    if let Some(py_modules) = ini_option(&sections, "options", "py_modules") {
+        setuptools.insert(
+            "py-modules".to_string(),
+            Value::Array(ini_list(py_modules).into_iter().map(Value::from).collect()),
+        );
+    }
+    let source_paths = setuptools_source_paths(setuptools, base_dir);
+    warnings.extend(validate_source_paths(base_dir, &source_paths, config)?);
+
+    Ok(ProjectInfo {
+        name,
+        dependencies: dependencies.sources.keys().cloned().collect(),
+        dependency_sources: dependencies.sources,
+        dependency_order: dependencies.order,
+        source_paths,
+        warnings,
+    })
+}
+
+/// Find source paths from setuptools options, as if they were the `[tool.setuptools]` table of a pyproject.toml
+fn setuptools_source_paths(setuptools: toml::Table, base_dir: &Path) -> Vec<PathBuf> {
+    let tool = toml::Table::from_iter([("setuptools".to_string(), Value::Table(setuptools))]);
+    let toml_value = Value::Table(toml::Table::from_iter([(
+        "tool".to_string(),
+        Value::Table(tool),
+    )]));
+    extract_source_paths(&toml_value, base_dir)
+}
+
+pub fn parse_setup_py(
+    setup_py_path: &Path,
+    config: &ExternalDependencyConfig,
+) -> Result<ProjectInfo> {
+    parse_setup_py_with(setup_py_path, config, &mut config.name_normalizer())
+}
+
+/// Parse a `setup.py` like [`parse_setup_py`], with a normalizer which may be shared
+/// with the other manifests of a workspace
+pub fn parse_setup_py_with(
+    setup_py_path: &Path,
+    config: &ExternalDependencyConfig,
+    normalizer: &mut NameNormalizer,
+) -> Result<ProjectInfo> {
+    let content = fs::read_to_string(setup_py_path)?;
+    parse_setup_py_content(&content, setup_py_path, config, normalizer)
+}
+
+/// Statically parse `setup.py` source located at `setup_py_path`.
+///
+/// This reads the literal `name`, `install_requires`, `packages`, `package_dir` and `py_modules`
+/// arguments of the first `setup(...)` call. Arguments built at runtime are skipped with a warning,
+/// and [`SetupCallNotFound`](error::ParsingError::SetupCallNotFound) is returned when there is no `setup(...)` call.
+pub fn parse_setup_py_str(
+    content: &str,
+    setup_py_path: &Path,
+    config: &ExternalDependencyConfig,
+) -> Result<ProjectInfo> {
+    parse_setup_py_content(
+        content,
+        setup_py_path,
+        config,
+        &mut config.name_normalizer(),
+    )
+}
+
+fn parse_setup_py_content(
+    content: &str,
+    setup_py_path: &Path,
+    config: &ExternalDependencyConfig,
+    normalizer: &mut NameNormalizer,
+) -> Result<ProjectInfo> {
+    let base_dir = setup_py_path.parent().unwrap();
+    let setup_call =
+        find_setup_call(content)?.ok_or_else(|| error::ParsingError::SetupCallNotFound {
+            path: setup_py_path.display().to_string(),
+        })?;
+    let mut warnings: Vec<ParsingWarning> = setup_call
+        .dynamic_arguments
+        .into_iter()
+        .map(|argument| ParsingWarning::DynamicSetupArgument { argument })
+        .collect();
+
+    let mut dependencies = ExtractedDependencies::new(normalizer);
+    extract_deps_from_value(
+        &mut dependencies,
+        &Value::Array(
+            setup_call
+                .install_requires
+                .into_iter()
+                .map(Value::from)
+                .collect(),
+        ),
+        &DependencySource::Project,
+        &config.target_environment,
+        &mut warnings,
+    );
+
+    let mut setuptools = toml::Table::new();
+    if let Some(packages) = setup_call.packages {
+        setuptools.insert("packages".to_string(), Value::from(packages));
+    }
+    if !setup_call.package_dir.is_empty() {
+        let package_dir: toml::Table = setup_call
+            .package_dir
+            .into_iter()
+            .map(|(package, path)| (package, Value::from(path)))
+            .collect();
+        setuptools.insert("package-dir".to_string(), Value::Table(package_dir));
+    }
+    if !setup_call.py_modules.is_empty() {
+        setuptools.insert("py-modules".to_string(), Value::from(setup_call.py_modules));
+    }
+    let source_paths = setuptools_source_paths(setuptools, base_dir);
+    warnings.extend(validate_source_paths(base_dir, &source_paths, config)?);
+
+    Ok(ProjectInfo {
+        name: setup_call.name,
+        dependencies: dependencies.sources.keys().cloned().collect(),
+        dependency_sources: dependencies.sources,
+        dependency_order: dependencies.order,
+        source_paths,
+        warnings,
+    })
+}
+
+/// Extract linter plugin packages from a `setup.cfg` or `.flake8` file.
+///
+/// This reads `require-plugins` from the `[flake8]` section, and `load-plugins` from
+/// any `[pylint.*]` section. Values may be separated by commas or continuation lines,
+/// and may carry version specifiers such as `flake8-bugbear>=22.1`.
+pub fn parse_tool_plugins(
+    config_path: &Path,
+    normalizer: &mut NameNormalizer,
+) -> Result<HashSet<String>> {
+    let content = fs::read_to_string(config_path)?;
+    let mut plugins = HashSet::new();
+    let mut section = String::new();
+    let mut in_plugin_option = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            continue;
+        }
+
+        let is_continuation = line.starts_with(char::is_whitespace);
+        let value = if is_continuation {
+            if !in_plugin_option {
+                continue;
+            }
+            trimmed
+        } else if let Some(name) = trimmed
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            section = name.trim().to_lowercase();
+            in_plugin_option = false;
+            continue;
+        } else {
+            let Some((key, value)) = trimmed.split_once(['=', ':']) else {
+                in_plugin_option = false;
+                continue;
+            };
+            let key = key.trim().replace('_', "-").to_lowercase();
+            in_plugin_option = match key.as_str() {
+                "require-plugins" => section == "flake8",
+                "load-plugins" => section == "pylint" || section.starts_with("pylint."),
+                _ => false,
+            };
+            if !in_plugin_option {
+                continue;
+            }
+            value
+        };
+
+        plugins.extend(
+            value
+                .split(',')
+                .map(str::trim)
+                .map(extract_package_name)
+                .filter(|plugin| !plugin.is_empty())
+                .map(|plugin| normalizer.normalize(plugin).to_string()),
+        );
+    }
+
+    Ok(plugins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    fn parse_toml(content: &str) -> Value {
+        toml::from_str(content).unwrap()
+    }
+
+    fn extract_dependencies(
+        toml_value: &Value,
+        config: &ExternalDependencyConfig,
+        warnings: &mut Vec<ParsingWarning>,
+    ) -> HashSet<String> {
+        extract_dependency_sources(toml_value, config, &mut NameNormalizer::default(), warnings)
+            .unwrap()
+            .sources
+            .into_keys()
+            .collect()
+    }
+
+    #[rstest]
+    #[case(vec![], &["Requests", "click"])]
+    #[case(vec!["test".to_string()], &["Requests", "click", "pytest-cov"])]
+    fn test_legacy_flit_metadata_dependencies(
+        #[case] include_dependency_groups: Vec<String>,
+        #[case] expected: &[&str],
+    ) {
+        let toml_value = parse_toml(
+            r#"
+            [tool.flit.metadata]
+            module = "legacy_flit"
+            requires = ["Requests >=2.6", "click"]
+
+            [tool.flit.metadata.requires-extra]
+            test = ["pytest-cov"]
+            "#,
+        );
+        let config = ExternalDependencyConfig {
+            include_dependency_groups,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            extract_dependencies(&toml_value, &config, &mut vec![]),
+            expected
+                .iter()
+                .map(|dep| normalize_package_name(dep))
+                .collect()
+        );
+    }
+
+    #[test]
+    fn test_hatch_env_dependencies_included_when_selected() {
+        let toml_value = parse_toml(
+            r#"
+            [project]
+            dependencies = ["requests"]
+
+            [tool.hatch.envs.test]
+            dependencies = ["pytest", "pytest-cov"]
+            "#,
+        );
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: vec!["test".to_string()],
+            ..Default::default()
+        };
+
+        let dependencies = extract_dependencies(&toml_value, &config, &mut vec![]);
+        assert_eq!(
+            dependencies,
+            HashSet::from([
+                "requests".to_string(),
+                "pytest".to_string(),
+                "pytest_cov".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_hatch_env_dependencies_excluded_when_not_selected() {
+        let toml_value = parse_toml(
+            r#"
+            [tool.hatch.envs.default]
+            dependencies = ["coverage"]
+
+            [tool.hatch.envs.test]
+            dependencies = ["pytest"]
+            "#,
+        );
+
+        let dependencies = extract_dependencies(
+            &toml_value,
+            &ExternalDependencyConfig::default(),
+            &mut vec![],
+        );
+        assert_eq!(dependencies, HashSet::from(["coverage".to_string()]));
+    }
+
+    #[test]
+    fn test_poetry_legacy_dev_dependencies() {
+        let toml_value = parse_toml(
+            r#"
+            [tool.poetry.dependencies]
+            python = "^3.9"
+            requests = "^2.0"
+
+            [tool.poetry.dev-dependencies]
+            pytest = "^7.0"
+            "#,
+        );
+        let mut warnings = vec![];
+
+        let dependencies = extract_dependencies(
+            &toml_value,
+            &ExternalDependencyConfig::default(),
+            &mut warnings,
+        );
+        assert_eq!(
+            dependencies,
+            HashSet::from(["requests".to_string(), "pytest".to_string()])
+        );
+        assert_eq!(warnings, vec![ParsingWarning::PoetryLegacyDevDependencies]);
+
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: vec![],
+            ..Default::default()
+        };
+        let dependencies = extract_dependencies(&toml_value, &config, &mut vec![]);
+        assert_eq!(dependencies, HashSet::from(["requests".to_string()]));
+    }
+
+    #[test]
+    fn test_project_name_falls_back_to_poetry() {
+        let toml_value = parse_toml(
+            r#"
+            [tool.poetry]
+            name = "poetry-pkg"
+            "#,
+        );
+        assert_eq!(
+            extract_project_name(&toml_value),
+            Some("poetry-pkg".to_string())
+        );
+    }
+
+    #[test]
+    fn test_project_name_prefers_pep621() {
+        let toml_value = parse_toml(
+            r#"
+            [project]
+            name = "pep621-pkg"
+
+            [tool.poetry]
+            name = "poetry-pkg"
+            "#,
+        );
+        assert_eq!(
+            extract_project_name(&toml_value),
+            Some("pep621-pkg".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dependency_group_string_include_matches_table_include() {
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: vec!["test".to_string()],
+            ..Default::default()
+        };
+        let table_include = parse_toml(
+            r#"
+            [dependency-groups]
+            coverage = ["coverage[toml]"]
+            test = ["pytest", {include-group = "coverage"}]
+            "#,
+        );
+        let string_include = parse_toml(
+            r#"
+            [dependency-groups]
+            coverage = ["coverage[toml]"]
+            test = ["pytest", ":coverage"]
+            "#,
+        );
+
+        let expected = HashSet::from(["pytest".to_string(), "coverage".to_string()]);
+        assert_eq!(
+            extract_dependencies(&table_include, &config, &mut vec![]),
+            expected
+        );
+        assert_eq!(
+            extract_dependencies(&string_include, &config, &mut vec![]),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(2, false)]
+    #[case(3, true)]
+    fn test_dependency_group_nesting_depth_limit(
+        #[case] max_dependency_group_depth: usize,
+        #[case] within_limit: bool,
+    ) {
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: vec!["a".to_string()],
+            max_dependency_group_depth,
+            ..Default::default()
+        };
+        let toml_value = parse_toml(
+            r#"
+            [dependency-groups]
+            a = ["pkg-a", ":b"]
+            b = ["pkg-b", ":c"]
+            c = ["pkg-c", ":d"]
+            d = ["pkg-d"]
+            "#,
+        );
+
+        let mut normalizer = NameNormalizer::default();
+        let result = extract_dependency_sources(&toml_value, &config, &mut normalizer, &mut vec![]);
+        if within_limit {
+            assert_eq!(result.unwrap().sources.len(), 4);
+        } else {
+            assert!(matches!(
+                result,
+                Err(error::ParsingError::GroupNestingTooDeep { group, depth: 3 }) if group == "d"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_dependency_group_package_string_is_dependency() {
+        let toml_value = parse_toml(
+            r#"
+            [dependency-groups]
+            dev = ["ruff>=0.5", "mypy"]
+            docs = ["mkdocs"]
+            "#,
+        );
+
+        let dependencies = extract_dependencies(
+            &toml_value,
+            &ExternalDependencyConfig::default(),
+            &mut vec![],
+        );
+        assert_eq!(
+            dependencies,
+            HashSet::from(["ruff".to_string(), "mypy".to_string()])
+        );
+    }
+
+    #[rstest]
+    #[case(&["dev"], &["ruff", "mypy", "core"])]
+    #[case(&["all"], &["ruff", "mypy", "core", "mkdocs", "pytest", "coverage"])]
+    #[case(&["all", "!docs"], &["ruff", "mypy", "core", "pytest", "coverage"])]
+    fn test_pdm_dev_dependency_groups(#[case] selection: &[&str], #[case] expected: &[&str]) {
+        let toml_value = parse_toml(
+            r#"
+            [tool.pdm.dev-dependencies]
+            dev = ["ruff>=0.5", "mypy", "-e file:///${PROJECT_ROOT}/libs/core#egg=core"]
+            Docs = ["mkdocs"]
+            test = ["pytest", "-e coverage @ file:///${PROJECT_ROOT}/vendor/coverage"]
+            "#,
+        );
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: selection.iter().map(|group| group.to_string()).collect(),
+            ..Default::default()
+        };
+
+        let mut normalizer = NameNormalizer::default();
+        let extracted =
+            extract_dependency_sources(&toml_value, &config, &mut normalizer, &mut vec![]).unwrap();
+        assert_eq!(
+            extracted.sources.keys().cloned().collect::<HashSet<_>>(),
+            expected.iter().map(|dep| dep.to_string()).collect()
+        );
+        assert_eq!(
+            extracted.sources["ruff"],
+            HashSet::from([DependencySource::Group("dev".to_string())])
+        );
+    }
+
+    #[rstest]
+    #[case(r#"dependencies = ["requests>=2"]"#, r#"dev = ["requests<2"]"#, true)]
+    #[case(
+        r#"dependencies = ["requests>=2,<3"]"#,
+        r#"dev = ["requests[socks]>=2.5"]"#,
+        false
+    )]
+    #[case(
+        r#"dependencies = ["requests>=2; python_version >= '3.8'"]"#,
+        r#"dev = ["requests<2; python_version < '3.8'"]"#,
+        false
+    )]
+    fn test_conflicting_specifiers(
+        #[case] project_dependencies: &str,
+        #[case] dev_group: &str,
+        #[case] conflicting: bool,
+    ) {
+        let toml_value = parse_toml(&format!(
+            "[project]\nname = \"pkg\"\n{project_dependencies}\n\n[dependency-groups]\n{dev_group}\n"
+        ));
+        let mut warnings = vec![];
+        extract_dependency_sources(
+            &toml_value,
+            &ExternalDependencyConfig::default(),
+            &mut NameNormalizer::default(),
+            &mut warnings,
+        )
+        .unwrap();
+
+        if conflicting {
+            assert_eq!(
+                warnings,
+                vec![ParsingWarning::ConflictingSpecifiers {
+                    package: "requests".to_string(),
+                    declarations: vec![
+                        "'>=2' (project dependencies)".to_string(),
+                        "'<2' (group 'dev')".to_string(),
+                    ],
+                }]
+            );
+        } else {
+            assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+        }
+    }
+
+    #[rstest]
+    #[case(r#"requests = ">=2""#, r#"requests = "<2""#, true)]
+    #[case(r#"requests = "^2.1""#, r#"requests = { version = "~3.0" }"#, true)]
+    #[case(r#"requests = "^2.1""#, r#"requests = { version = "~2.5" }"#, false)]
+    #[case(
+        r#"requests = ">=2""#,
+        r#"requests = { version = "<2", python = "<3.8" }"#,
+        false
+    )]
+    fn test_poetry_conflicting_specifiers(
+        #[case] project_dependency: &str,
+        #[case] dev_dependency: &str,
+        #[case] conflicting: bool,
+    ) {
+        let toml_value = parse_toml(&format!(
+            "[tool.poetry.dependencies]\npython = \"^3.9\"\n{project_dependency}\n\n[tool.poetry.group.dev.dependencies]\n{dev_dependency}\n"
+        ));
+        let mut warnings = vec![];
+        extract_dependency_sources(
+            &toml_value,
+            &ExternalDependencyConfig::default(),
+            &mut NameNormalizer::default(),
+            &mut warnings,
+        )
+        .unwrap();
+
+        assert_eq!(
+            warnings
+                .iter()
+                .any(|warning| matches!(warning, ParsingWarning::ConflictingSpecifiers { package, .. } if package == "requests")),
+            conflicting,
+            "unexpected warnings: {warnings:?}"
+        );
+    }
+
+    #[test]
+    fn test_strict_specifiers_and_detailed_parsing() {
+        let project = write_project(&[(
+            "pyproject.toml",
+            "[project]\nname = \"pkg\"\ndependencies = [\"requests>=2\", \"attrs\"]\n\n[dependency-groups]\ndev = [\"requests<2\"]\n",
+        )]);
+        let pyproject_path = project.path().join("pyproject.toml");
+
+        // By default the conflict is a warning, and every declaration is kept
+        let detailed =
+            parse_pyproject_toml_detailed(&pyproject_path, &ExternalDependencyConfig::default())
+                .unwrap();
+        assert_eq!(
+            detailed.project_info.dependency_order,
+            vec!["requests", "attrs"]
+        );
+        assert_eq!(
+            detailed.specifiers["requests"],
+            vec![
+                (DependencySource::Project, ">=2".to_string()),
+                (DependencySource::Group("dev".to_string()), "<2".to_string()),
+            ]
+        );
+        assert!(!detailed.specifiers.contains_key("attrs"));
+
+        let config = ExternalDependencyConfig {
+            strict_specifiers: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            parse_pyproject_toml(&pyproject_path, &config),
+            Err(error::ParsingError::ConflictingDependencySpecifiers { package, .. }) if package == "requests"
+        ));
+    }
+
+    #[test]
+    fn test_strict_specifiers_and_detailed_parsing_with_poetry() {
+        let project = write_project(&[(
+            "pyproject.toml",
+            "[tool.poetry]\nname = \"pkg\"\n\n[tool.poetry.dependencies]\npython = \"^3.9\"\nrequests = \"^2.1\"\n\n[tool.poetry.group.dev.dependencies]\nrequests = { version = \"<2\" }\n",
+        )]);
+        let pyproject_path = project.path().join("pyproject.toml");
+
+        let detailed =
+            parse_pyproject_toml_detailed(&pyproject_path, &ExternalDependencyConfig::default())
+                .unwrap();
+        assert_eq!(
+            detailed.specifiers["requests"],
+            vec![
+                (DependencySource::Project, ">=2.1,<3".to_string()),
+                (DependencySource::Group("dev".to_string()), "<2".to_string()),
+            ]
+        );
+
+        let config = ExternalDependencyConfig {
+            strict_specifiers: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            parse_pyproject_toml(&pyproject_path, &config),
+            Err(error::ParsingError::ConflictingDependencySpecifiers { package, .. }) if package == "requests"
+        ));
+    }
+
+    #[rstest]
+    #[case(r#"{ "" = "src" }"#, "app.api", "src/app/api")]
+    #[case(r#"{ "" = "src", app = "lib/app" }"#, "app.api", "lib/app/api")]
+    #[case(r#"{ "" = "src", "app.api" = "api" }"#, "app.api", "api")]
+    #[case("{}", "app.api", "app/api")]
+    fn test_setuptools_package_path(
+        #[case] package_dir: &str,
+        #[case] package_name: &str,
+        #[case] expected: &str,
+    ) {
+        let table: toml::Table = toml::from_str(&format!("package-dir = {package_dir}")).unwrap();
+        let package_dir = table["package-dir"].as_table();
+        assert_eq!(
+            setuptools_package_path(package_name, package_dir, Path::new("/project")),
+            PathBuf::from("/project").join(expected)
+        );
+    }
+
+    #[rstest]
+    #[case(
+        r#"where = ["src"]"#,
+        &["src/app", "src/tools"]
+    )]
+    #[case(
+        r#"where = ["src"]
+exclude = ["tools*"]"#,
+        &["src/app"]
+    )]
+    #[case(
+        r#"where = ["src"]
+include = ["app.*"]"#,
+        &["src/app"]
+    )]
+    #[case(
+        r#"where = ["src"]
+include = ["app.c*"]"#,
+        &["src/app"]
+    )]
+    #[case(
+        r#"where = ["src"]
+include = ["missing*"]"#,
+        &["src"]
+    )]
+    #[case(
+        r#"where = ["src", "plugins"]
+namespaces = false"#,
+        &["src/app", "plugins/extra"]
+    )]
+    fn test_setuptools_packages_find(#[case] find: &str, #[case] expected: &[&str]) {
+        let pyproject = format!("[tool.setuptools.packages.find]\n{find}\n");
+        let project = write_project(&[
+            ("pyproject.toml", &pyproject),
+            ("src/app/__init__.py", ""),
+            ("src/app/api/__init__.py", ""),
+            ("src/app/core/__init__.py", ""),
+            ("src/tools/cli.py", ""),
+            ("plugins/extra/__init__.py", ""),
+        ]);
+
+        let project_info = parse_pyproject_toml(
+            &project.path().join("pyproject.toml"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            project_info.source_paths,
+            expected
+                .iter()
+                .map(|path| project.path().join(path))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_explicit_external_dependencies_override_extraction() {
+        let pyproject = r#"
+            [project]
+            name = "overridden"
+            dynamic = ["dependencies"]
+
+            [tool.tach]
+            external_dependencies = ["requests", "pyyaml"]
+            "#;
+        let project_info = parse_pyproject_toml_str(
+            pyproject,
+            Path::new("."),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(project_info.dependency_order, vec!["requests", "pyyaml"]);
+        assert_eq!(
+            project_info.warnings,
+            vec![ParsingWarning::DependenciesOverridden {
+                pyproject_path: Path::new("./pyproject.toml").display().to_string()
+            }]
+        );
+
+        let project_info = parse_pyproject_toml_str(
+            r#"
+            [project]
+            name = "extracted"
+            dependencies = ["attrs"]
+            "#,
+            Path::new("."),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(project_info.dependency_order, vec!["attrs"]);
+        assert!(project_info.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_dynamic_dependencies_from_setuptools_files() {
+        let project = write_project(&[
+            (
+                "pyproject.toml",
+                r#"[project]
+name = "pkg"
+dynamic = ["dependencies", "optional-dependencies"]
+
+[tool.setuptools.dynamic]
+dependencies = { file = ["requirements.in", "missing.txt"] }
+optional-dependencies.dev = { file = "requirements-dev.txt" }
+"#,
+            ),
+            (
+                "requirements.in",
+                "requests>=2
+PyYAML
+",
+            ),
+            (
+                "requirements-dev.txt",
+                "pytest
+",
+            ),
+        ]);
+
+        let project_info = parse_pyproject_toml(
+            &project.path().join("pyproject.toml"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            project_info.dependency_order,
+            vec!["pyyaml", "requests", "pytest"]
+        );
+        assert_eq!(
+            project_info.dependency_sources["pytest"],
+            HashSet::from([DependencySource::Extra("dev".to_string())])
+        );
+        assert_eq!(
+            project_info.warnings,
+            vec![ParsingWarning::DynamicDependencyFileMissing {
+                path: project.path().join("missing.txt").display().to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_dependencies_from_setup_py() {
+        let project = write_project(&[
+            (
+                "pyproject.toml",
+                "[project]\nname = \"pkg\"\ndynamic = [\"dependencies\"]\n",
+            ),
+            (
+                "setup.py",
+                "from setuptools import setup\nsetup(install_requires=[\"click\"])\n",
+            ),
+        ]);
+
+        let project_info = parse_pyproject_toml(
+            &project.path().join("pyproject.toml"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(project_info.dependency_order, vec!["click"]);
+    }
+
+    #[test]
+    fn test_dynamic_dependencies_unresolved() {
+        let project = write_project(&[(
+            "pyproject.toml",
+            "[project]\nname = \"pkg\"\ndynamic = [\"dependencies\"]\n",
+        )]);
+
+        let result = parse_pyproject_toml(
+            &project.path().join("pyproject.toml"),
+            &ExternalDependencyConfig::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(error::ParsingError::DynamicDependenciesUnresolved { field, .. }) if field == "dependencies"
+        ));
+    }
+
+    #[rstest]
+    #[case("Zope.Interface")]
+    #[case("zope_interface")]
+    #[case("zope-interface")]
+    #[case("zope..interface")]
+    #[case("ZOPE._-INTERFACE")]
+    fn test_normalize_dotted_package_name(#[case] name: &str) {
+        assert_eq!(normalize_package_name(name), "zope_interface");
+    }
+
+    #[test]
+    fn test_name_normalizer_caches_names() {
+        let mut normalizer = NameNormalizer::default();
+        let first = normalizer.normalize("Zope.Interface");
+        assert_eq!(&*first, normalize_package_name("Zope.Interface"));
+        assert!(Arc::ptr_eq(&first, &normalizer.normalize("Zope.Interface")));
+    }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_excluded_deps_are_normalized(#[case] preserve_unknown_chars: bool) {
+        for excluded in EXCLUDED_DEPS {
+            assert_eq!(
+                normalize_package_name_with(excluded, preserve_unknown_chars),
+                excluded
+            );
+        }
+    }
+
+    #[test]
+    fn test_requirements_exclude_build_tooling() {
+        let dependencies = parse_requirements_str(
+            "requests\npoetry-core>=1.0\nPoetry\n",
+            Path::new("."),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(dependencies, HashSet::from(["requests".to_string()]));
+    }
+
+    #[rstest]
+    #[case(true, "torch+cu121")]
+    #[case(false, "torch_cu121")]
+    fn test_normalize_package_name_unknown_chars(
+        #[case] preserve_unknown_chars: bool,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            normalize_package_name_with("Torch+CU121", preserve_unknown_chars),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_expand_source_layout_template() {
+        let project_root = Path::new("/repo");
+        let member_roots = vec![
+            project_root.join("packages/core"),
+            project_root.join("packages/utils"),
+        ];
+        assert_eq!(
+            expand_source_layout_template("{member}/src", project_root, &member_roots),
+            vec![
+                PathBuf::from("/repo/packages/core/src"),
+                PathBuf::from("/repo/packages/utils/src")
+            ]
+        );
+        assert_eq!(
+            expand_source_layout_template("{member}/src/myorg/{name}", project_root, &member_roots),
+            vec![
+                PathBuf::from("/repo/packages/core/src/myorg/core"),
+                PathBuf::from("/repo/packages/utils/src/myorg/utils")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uv_workspace_members_and_local_sources() {
+        let project = write_project(&[
+            (
+                "pyproject.toml",
+                r#"[project]
+name = "monorepo"
+dependencies = ["core", "utils", "requests"]
+
+[tool.uv.sources]
+core = { workspace = true }
+utils = { path = "libs/utils", editable = true }
+requests = { index = "internal" }
+
+[tool.uv.workspace]
+members = ["packages/*"]
+exclude = ["packages/legacy"]
+"#,
+            ),
+            ("src/monorepo/__init__.py", ""),
+            (
+                "packages/core/pyproject.toml",
+                "[project]\nname = \"core\"\n",
+            ),
+            ("packages/core/src/core/__init__.py", ""),
+            (
+                "packages/cli/pyproject.toml",
+                "[project]\nname = \"cli\"\n\n[tool.maturin]\npython-source = \"python\"\n",
+            ),
+            (
+                "packages/legacy/pyproject.toml",
+                "[project]\nname = \"legacy\"\n",
+            ),
+            ("packages/notes/README.md", ""),
+        ]);
+
+        let project_info = parse_pyproject_toml(
+            &project.path().join("pyproject.toml"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(project_info.dependency_order, vec!["requests"]);
+        assert_eq!(
+            project_info.source_paths,
+            vec![
+                project.path().join("src"),
+                project.path().join("packages/cli/python"),
+                project.path().join("packages/core/src"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pdm_local_editable_is_named_after_its_directory() {
+        assert_eq!(
+            pdm_editable_name("-e file:///${PROJECT_ROOT}/packages/shared-utils/"),
+            Some("shared-utils".to_string())
+        );
+        assert_eq!(pdm_editable_name("--editable"), None);
+    }
+
+    fn write_project(files: &[(&str, &str)]) -> TempDir {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        for (name, content) in files {
+            let path = temp_dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_single_dependency_source_warns_when_both_present() {
+        let project = write_project(&[
+            (
+                "pyproject.toml",
+                "[project]\nname = \"pkg\"\ndependencies = [\"requests\"]\n",
+            ),
+            ("requirements.txt", "requests==2.0\n"),
+        ]);
+        let config = ExternalDependencyConfig {
+            single_dependency_source: true,
+            ..Default::default()
+        };
+
+        let project_info =
+            parse_pyproject_toml(&project.path().join("pyproject.toml"), &config).unwrap();
+        assert_eq!(project_info.warnings.len(), 1);
+        assert!(matches!(
+            project_info.warnings[0],
+            ParsingWarning::MultipleDependencySources { .. }
+        ));
+    }
+
+    #[test]
+    fn test_single_dependency_source_silent_with_one_source() {
+        let project = write_project(&[
+            (
+                "pyproject.toml",
+                "[project]\nname = \"pkg\"\ndependencies = [\"requests\"]\n",
+            ),
+            ("requirements.txt", "# no dependencies here\n"),
+        ]);
+        let config = ExternalDependencyConfig {
+            single_dependency_source: true,
+            ..Default::default()
+        };
+
+        let project_info =
+            parse_pyproject_toml(&project.path().join("pyproject.toml"), &config).unwrap();
+        assert!(project_info.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_requirements_txt_with_hash_continuations() {
+        let project = write_project(&[(
+            "requirements.txt",
+            r#"--index-url https://pypi.org/simple
+requests==2.31.0 \
+    --hash=sha256:aaaa \
+    --hash=sha256:bbbb
+    # via -r requirements.in
+idna==3.4 \
+    --hash=sha256:cccc
+"#,
+        )]);
+
+        let dependencies = parse_requirements_txt(
+            &project.path().join("requirements.txt"),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            dependencies,
+            HashSet::from(["requests".to_string(), "idna".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_source_paths_resolve_on_disk_case() {
+        let project = write_project(&[(
+            "pyproject.toml",
+            r#"
+            [tool.setuptools]
+            packages = ["mypackage"]
+            "#,
+        )]);
+        fs::create_dir(project.path().join("MyPackage")).unwrap();
+        let config = ExternalDependencyConfig {
+            case_insensitive_paths: Some(true),
+            ..Default::default()
+        };
+
+        let project_info =
+            parse_pyproject_toml(&project.path().join("pyproject.toml"), &config).unwrap();
+        assert_eq!(
+            project_info.source_paths,
+            vec![project.path().join("MyPackage")]
+        );
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    #[test]
+    fn test_source_paths_case_insensitive_by_default() {
+        let project = write_project(&[(
+            "pyproject.toml",
+            r#"
+            [tool.setuptools]
+            packages = ["mypackage"]
+            "#,
+        )]);
+        fs::create_dir(project.path().join("MyPackage")).unwrap();
+
+        let project_info = parse_pyproject_toml(
+            &project.path().join("pyproject.toml"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            project_info.source_paths,
+            vec![project.path().join("MyPackage")]
+        );
+    }
+
+    #[rstest]
+    #[case(&["requests"], &["requests", "Rich"], &["rich"], &[])]
+    #[case(&["requests", "rich"], &["requests"], &[], &["rich"])]
+    #[case(&["requests"], &["requests>=2"], &[], &[])]
+    fn test_diff_pyproject(
+        #[case] base_deps: &[&str],
+        #[case] head_deps: &[&str],
+        #[case] expected_added: &[&str],
+        #[case] expected_removed: &[&str],
+    ) {
+        let to_pyproject = |deps: &[&str]| {
+            format!(
+                "[project]\nname = \"example\"\ndependencies = [{}]\n",
+                deps.iter().map(|dep| format!("\"{dep}\"")).join(", ")
+            )
+        };
+        let base_content = to_pyproject(base_deps);
+        let head_content = to_pyproject(head_deps);
+        let project = write_project(&[
+            ("base.toml", base_content.as_str()),
+            ("head.toml", head_content.as_str()),
+        ]);
+
+        let (added, removed) = diff_pyproject(
+            &project.path().join("base.toml"),
+            &project.path().join("head.toml"),
+        )
+        .unwrap();
+        assert_eq!(added, expected_added);
+        assert_eq!(removed, expected_removed);
+    }
+
+    #[test]
+    fn test_parse_tool_plugins_from_setup_cfg() {
+        let project = write_project(&[(
+            "setup.cfg",
+            r#"[metadata]
+name = example
+
+[flake8]
+max-line-length = 88
+require-plugins =
+    flake8-bugbear>=22.1,
+    flake8_docstrings
+    flake8-pyi ~= 24.1, flake8-quotes
+
+[pylint.MASTER]
+load-plugins = pylint_django
+"#,
+        )]);
+
+        let plugins = parse_tool_plugins(
+            &project.path().join("setup.cfg"),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            plugins,
+            HashSet::from([
+                "flake8_bugbear".to_string(),
+                "flake8_docstrings".to_string(),
+                "flake8_pyi".to_string(),
+                "flake8_quotes".to_string(),
+                "pylint_django".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_maturin_project_extracts_selected_extras() {
+        let project = write_project(&[(
+            "pyproject.toml",
+            r#"
+            [project]
+            name = "mixed"
+            dependencies = ["numpy"]
+
+            [project.optional-dependencies]
+            plot = ["matplotlib>=3"]
+            docs = ["sphinx"]
+
+            [tool.maturin]
+            python-source = "python"
+            "#,
+        )]);
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: vec!["plot".to_string()],
+            ..Default::default()
+        };
+
+        let project_info =
+            parse_pyproject_toml(&project.path().join("pyproject.toml"), &config).unwrap();
+        assert_eq!(
+            project_info.source_paths,
+            vec![project.path().join("python")]
+        );
+        assert_eq!(
+            project_info.dependencies,
+            HashSet::from(["numpy".to_string(), "matplotlib".to_string()])
+        );
+        assert_eq!(
+            project_info.dependency_sources["matplotlib"],
+            HashSet::from([DependencySource::Extra("plot".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_parse_pyproject_toml_str() {
+        let project = write_project(&[]);
+        fs::create_dir(project.path().join("src")).unwrap();
+
+        let project_info = parse_pyproject_toml_str(
+            r#"
+            [project]
+            name = "example"
+            dependencies = ["requests>=2", "Rich"]
+            "#,
+            project.path(),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(project_info.name, Some("example".to_string()));
+        assert_eq!(
+            project_info.dependencies,
+            HashSet::from(["requests".to_string(), "rich".to_string()])
+        );
+        assert_eq!(project_info.source_paths, vec![project.path().join("src")]);
+    }
+
+    #[test]
+    fn test_parse_requirements_str() {
+        let dependencies = parse_requirements_str(
+            "# comment\nrequests==2.31.0\n-e .\nPyYAML>=6 ; python_version >= '3.8'\npython\n",
+            Path::new("."),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            dependencies,
+            HashSet::from(["requests".to_string(), "pyyaml".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_requirements_txt_follows_includes() {
+        let project = write_project(&[
+            (
+                "requirements.txt",
+                "-r requirements/base.txt\n--constraint=constraints.txt\nflask\n",
+            ),
+            ("requirements/base.txt", "requests\n-rshared.txt\n"),
+            ("requirements/shared.txt", "idna\n"),
+            ("constraints.txt", "urllib3<2\nflask<3\n"),
+        ]);
+
+        let requirements = parse_requirements(
+            &project.path().join("requirements.txt"),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            requirements.dependencies,
+            HashSet::from([
+                "flask".to_string(),
+                "requests".to_string(),
+                "idna".to_string()
+            ])
+        );
+        assert_eq!(
+            requirements.constraints,
+            HashSet::from(["urllib3".to_string()])
+        );
+    }
+
+    #[rstest]
+    #[case("git+https://github.com/org/pkg.git#egg=pkg", Some("pkg"))]
+    #[case(
+        "git+https://github.com/org/pkg.git@v1#egg=my_pkg&subdirectory=src",
+        Some("my_pkg")
+    )]
+    #[case(
+        "https://example.com/wheels/pkg_name-1.2.3-py3-none-any.whl",
+        Some("pkg_name")
+    )]
+    #[case(
+        "https://example.com/pkg_name-1.2.3-py3-none-any.whl#sha256=abcd",
+        Some("pkg_name")
+    )]
+    #[case("https://example.com/archive/main.tar.gz", None)]
+    fn test_extract_url_package_name(#[case] url: &str, #[case] expected: Option<&str>) {
+        assert_eq!(extract_url_package_name(url), expected);
+    }
+
+    #[test]
+    fn test_requirements_txt_editable_and_url_installs() {
+        let project = write_project(&[(
+            "requirements.txt",
+            "-e ./libs/foo\n\
+             --editable=libs/bar\n\
+             -e .\n\
+             -e git+https://github.com/org/vcs-pkg.git#egg=vcs_pkg\n\
+             git+https://github.com/org/other.git#egg=Other.Pkg\n\
+             https://example.com/wheels/wheel_pkg-1.2.3-py3-none-any.whl\n\
+             https://example.com/archive/main.tar.gz\n\
+             named @ https://example.com/archive/main.tar.gz\n",
+        )]);
+
+        let requirements = parse_requirements(
+            &project.path().join("requirements.txt"),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            requirements.dependencies,
+            HashSet::from([
+                "vcs_pkg".to_string(),
+                "other_pkg".to_string(),
+                "wheel_pkg".to_string(),
+                "named".to_string()
+            ])
+        );
+        assert_eq!(
+            requirements.editable_paths,
+            vec![
+                project.path().join("libs/foo"),
+                project.path().join("libs/bar"),
+                project.path().to_path_buf()
+            ]
+        );
+
+        let project_info = parse_project_root(project.path(), &ExternalDependencyConfig::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            project_info.source_paths,
+            vec![
+                project.path().to_path_buf(),
+                project.path().join("libs/foo"),
+                project.path().join("libs/bar")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_requirements_txt_include_cycle() {
+        let project = write_project(&[
+            ("requirements.txt", "requests\n-r dev.txt\n"),
+            ("dev.txt", "pytest\n-r requirements.txt\n"),
+        ]);
+
+        let result = parse_requirements_txt(
+            &project.path().join("requirements.txt"),
+            &mut NameNormalizer::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(error::ParsingError::RequirementsIncludeCycle { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(false, &["bad_a", "bad_b"])]
+    #[case(true, &["bad_a"])]
+    fn test_parse_workspace_failures(#[case] fail_fast: bool, #[case] expected: &[&str]) {
+        let project = write_project(&[
+            ("bad_a/pyproject.toml", "[project\n"),
+            ("good/pyproject.toml", "[project]\nname = \"good\"\n"),
+            ("bad_b/pyproject.toml", "not toml = \n"),
+        ]);
+        let member_roots: Vec<PathBuf> = ["bad_a", "good", "bad_b"]
+            .iter()
+            .map(|member| project.path().join(member))
+            .collect();
+
+        let error::WorkspaceParsingError(failures) = parse_workspace(
+            &member_roots,
+            &ExternalDependencyConfig::default(),
+            fail_fast,
+        )
+        .unwrap_err();
+        let failed_members: Vec<PathBuf> =
+            failures.into_iter().map(|failure| failure.member).collect();
+        assert_eq!(
+            failed_members,
+            expected
+                .iter()
+                .map(|member| project.path().join(member))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_setup_cfg() {
+        let project = write_project(&[(
+            "setup.cfg",
+            r#"[metadata]
+name = legacy-pkg
+
+[options]
+package_dir =
+    =src
+packages = find:
+install_requires =
+    requests>=2,<3
+    PyYAML; python_version >= "3.8"
+    # a comment
+
+[options.extras_require]
+docs = mkdocs
+cli =
+    click
+    rich
+
+[flake8]
+max-line-length = 120
+"#,
+        )]);
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: vec!["docs".to_string()],
+            ..Default::default()
+        };
+
+        let project_info = parse_project_root(project.path(), &config)
+            .unwrap()
+            .unwrap();
+        assert_eq!(project_info.name.as_deref(), Some("legacy-pkg"));
+        assert_eq!(
+            project_info.dependency_order,
+            vec!["requests", "pyyaml", "mkdocs"]
+        );
+        assert_eq!(project_info.source_paths, vec![project.path().join("src")]);
+    }
+
+    #[test]
+    fn test_setup_cfg_explicit_packages_use_package_dir() {
+        let project_info = parse_setup_cfg_str(
+            "[options]\npackages = app, app.api\npackage_dir =\n    =lib\n    app.api = api\n",
+            Path::new("/project"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            project_info.source_paths,
+            vec![
+                PathBuf::from("/project/lib/app"),
+                PathBuf::from("/project/api")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_dependency_array_is_flattened() {
+        let project = write_project(&[(
+            "pyproject.toml",
+            r#"[project]
+name = "generated"
+dependencies = [["requests>=2", "PyYAML"], "click"]
+"#,
+        )]);
+
+        let project_info = parse_pyproject_toml(
+            &project.path().join("pyproject.toml"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            project_info.dependency_order,
+            vec!["requests", "pyyaml", "click"]
+        );
+        assert_eq!(
+            project_info.warnings,
+            vec![ParsingWarning::NestedDependencyArray {
+                entry: r#"["requests>=2", "PyYAML"]"#.to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_setup_py() {
+        let project_info = parse_setup_py_str(
+            r#"
+from setuptools import setup
+
+with open("requirements-extra.txt") as f:
+    extra = f.read().splitlines()
+
+setup(
+    name="legacy-pkg",
+    install_requires=["requests>=2", 'pywin32; sys_platform == "win32"'] + extra,
+    packages=["app", "app.api"],
+    package_dir={"": "src"},
+)
+"#,
+            Path::new("/project/setup.py"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(project_info.name.as_deref(), Some("legacy-pkg"));
+        assert!(project_info.dependency_order.is_empty());
+        assert_eq!(
+            project_info.source_paths,
+            vec![
+                PathBuf::from("/project/src/app"),
+                PathBuf::from("/project/src/app/api")
+            ]
+        );
+        assert_eq!(
+            project_info.warnings,
+            vec![ParsingWarning::DynamicSetupArgument {
+                argument: "install_requires".to_string()
+            }]
+        );
+
+        let project_info = parse_setup_py_str(
+            r#"import setuptools
+setuptools.setup(name="pkg", install_requires=["requests>=2", "PyYAML"])"#,
+            Path::new("/project/setup.py"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(project_info.dependency_order, vec!["requests", "pyyaml"]);
+        assert!(project_info.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_parse_setup_py_without_setup_call() {
+        assert!(matches!(
+            parse_setup_py_str(
+                "from distutils.core import run_setup\n",
+                Path::new("/project/setup.py"),
+                &ExternalDependencyConfig::default(),
+            ),
+            Err(error::ParsingError::SetupCallNotFound { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(&["dev"], &["requests", "pytest"])]
+    #[case(&["all"], &["requests", "pytest", "mkdocs"])]
+    #[case(&[], &["requests"])]
+    fn test_poetry_group_dependencies(#[case] groups: &[&str], #[case] expected: &[&str]) {
+        let toml_value = parse_toml(
+            r#"
+            [tool.poetry.dependencies]
+            python = "^3.9"
+            requests = "^2.31"
+
+            [tool.poetry.group.dev.dependencies]
+            pytest = "^8.0"
+            poetry-core = "*"
+
+            [tool.poetry.group.docs.dependencies]
+            MkDocs = "*"
+            "#,
+        );
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: groups.iter().map(|g| g.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            extract_dependencies(&toml_value, &config, &mut Vec::new()),
+            expected.iter().map(|dep| dep.to_string()).collect()
+        );
+    }
+
+    #[test]
+    fn test_all_extra_expands_self_references() {
+        let toml_value = parse_toml(
+            r#"
+            [project]
+            name = "MyPkg"
+            dependencies = ["requests"]
+
+            [project.optional-dependencies]
+            docs = ["mkdocs"]
+            cli = ["click", "rich"]
+            all = ["mypkg[docs, cli]", "mypkg[all]"]
+            "#,
+        );
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: vec!["all".to_string()],
+            ..Default::default()
+        };
+
+        let sources = extract_dependency_sources(
+            &toml_value,
+            &config,
+            &mut NameNormalizer::default(),
+            &mut Vec::new(),
+        )
+        .unwrap()
+        .sources;
+        assert_eq!(
+            sources.keys().cloned().collect::<HashSet<String>>(),
+            HashSet::from([
+                "requests".to_string(),
+                "mkdocs".to_string(),
+                "click".to_string(),
+                "rich".to_string()
+            ])
+        );
+        assert!(sources["click"].contains(&DependencySource::Extra("all".to_string())));
+    }
+
+    #[test]
+    fn test_selected_extra_expands_own_extra_only() {
+        let toml_value = parse_toml(
+            r#"
+            [project]
+            name = "mypackage"
+
+            [project.optional-dependencies]
+            base = ["attrs"]
+            test = ["MyPackage[base]", "pytest"]
+            docs = ["sphinx"]
+            "#,
+        );
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: vec!["test".to_string()],
+            ..Default::default()
+        };
+
+        let sources = extract_dependency_sources(
+            &toml_value,
+            &config,
+            &mut NameNormalizer::default(),
+            &mut Vec::new(),
+        )
+        .unwrap()
+        .sources;
+        assert_eq!(
+            sources,
+            HashMap::from([
+                (
+                    "attrs".to_string(),
+                    HashSet::from([DependencySource::Extra("test".to_string())])
+                ),
+                (
+                    "pytest".to_string(),
+                    HashSet::from([DependencySource::Extra("test".to_string())])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_hatch_env_features_activate_extras() {
+        let toml_value = parse_toml(
+            r#"
+            [project]
+            dependencies = ["requests"]
+
+            [project.optional-dependencies]
+            docs = ["mkdocs"]
+            cli = ["click"]
+
+            [tool.hatch.envs.site]
+            features = ["docs"]
+            dependencies = ["mkdocs-material"]
+            "#,
+        );
+        let config = ExternalDependencyConfig {
+            include_dependency_groups: vec!["site".to_string()],
+            ..Default::default()
+        };
+
+        let sources = extract_dependency_sources(
+            &toml_value,
+            &config,
+            &mut NameNormalizer::default(),
+            &mut Vec::new(),
+        )
+        .unwrap()
+        .sources;
+        assert_eq!(
+            sources.keys().cloned().collect::<HashSet<String>>(),
+            HashSet::from([
+                "requests".to_string(),
+                "mkdocs".to_string(),
+                "mkdocs_material".to_string()
+            ])
+        );
+        assert_eq!(
+            sources["mkdocs"],
+            HashSet::from([DependencySource::Group("site".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_dependencies_filtered_by_target_environment() {
+        let toml_value = parse_toml(
+            r#"
+            [project]
+            dependencies = [
+                "requests",
+                "typing_extensions; python_version < '3.11'",
+                "pywin32 >= 306; sys_platform == 'win32'",
+                "uvloop; sys_platform != 'win32'",
+            ]
+
+            [tool.poetry.dev-dependencies]
+            colorama = { version = "*", markers = "sys_platform == 'win32'" }
+            pytest = "*"
+            "#,
+        );
+        let config = ExternalDependencyConfig {
+            target_environment: MarkerEnvironment {
+                python_version: Some("3.12".to_string()),
+                sys_platform: Some("linux".to_string()),
+                os_name: None,
+            },
+            ..Default::default()
+        };
+
+        let dependencies = extract_dependencies(&toml_value, &config, &mut Vec::new());
+        assert_eq!(
+            dependencies,
+            HashSet::from([
+                "requests".to_string(),
+                "uvloop".to_string(),
+                "pytest".to_string()
+            ])
+        );
+
+        let unfiltered = extract_dependencies(
+            &toml_value,
+            &ExternalDependencyConfig::default(),
+            &mut Vec::new(),
+        );
+        assert!(unfiltered.contains("typing_extensions"));
+        assert!(unfiltered.contains("pywin32"));
+        assert!(unfiltered.contains("colorama"));
+    }
+
+    #[rstest]
+    #[case(&["all", "!docs"], &["lint", "test", "test_integration"])]
+    #[case(&["test-*"], &["test_integration"])]
+    fn test_resolve_dependency_group_selection(
+        #[case] selection: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        let toml_value = parse_toml(
+            r#"
+            [dependency-groups]
+            docs = ["mkdocs"]
+            lint = ["ruff"]
+            test = ["pytest"]
+            test-integration = ["pytest-docker"]
+            "#,
+        );
+        let groups = toml_value["dependency-groups"].as_table().unwrap();
+        let selection: Vec<String> = selection.iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(
+            resolve_dependency_group_selection(groups, &selection),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_poetry_multiple_constraints_dependency() {
+        let toml_value = parse_toml(
+            r#"
+            [tool.poetry.dependencies]
+            python = "^3.7"
+            requests = [
+                {version = "1", python = "<3.8"},
+                {version = "2", python = ">=3.8"},
+            ]
+
+            [[tool.poetry.dependencies.numpy]]
+            version = "1.24"
+            python = "<3.9"
+
+            [[tool.poetry.dependencies.numpy]]
+            version = "2.0"
+            python = ">=3.9"
+            "#,
+        );
+
+        let dependencies = extract_dependencies(
+            &toml_value,
+            &ExternalDependencyConfig::default(),
+            &mut vec![],
+        );
+        assert_eq!(
+            dependencies,
+            HashSet::from(["requests".to_string(), "numpy".to_string()])
+        );
+    }
+
+    #[rstest]
+    #[case("src/pkg/module.py", Some("src"))]
+    #[case("src/pkg/nested/module.py", Some("src/pkg/nested"))]
+    #[case("tests/test_module.py", None)]
+    fn test_owning_source_path(#[case] file: &str, #[case] expected: Option<&str>) {
+        let project = write_project(&[]);
+        for dir in ["src/pkg/nested", "tests"] {
+            fs::create_dir_all(project.path().join(dir)).unwrap();
+        }
+        fs::write(project.path().join(file), "").unwrap();
+        let project_info = ProjectInfo::new(
+            None,
+            vec![],
+            vec![
+                project.path().join("src"),
+                project.path().join("src/pkg/nested"),
+            ],
+        );
+
+        assert_eq!(
+            project_info.owning_source_path(&project.path().join(file)),
+            expected.map(|root| project.path().join(root)).as_ref()
+        );
+    }
+
+    #[test]
+    fn test_empty_dependency_entries_are_skipped() {
+        let toml_value = parse_toml(
+            r#"
+            [project]
+            dependencies = ["requests", "", "   "]
+            "#,
+        );
+
+        let mut warnings = vec![];
+        let dependencies = extract_dependencies(
+            &toml_value,
+            &ExternalDependencyConfig::default(),
+            &mut warnings,
+        );
+        assert_eq!(dependencies, HashSet::from(["requests".to_string()]));
+        assert_eq!(
+            warnings,
+            vec![
+                ParsingWarning::EmptyDependency {
+                    entry: "".to_string()
+                },
+                ParsingWarning::EmptyDependency {
+                    entry: "   ".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tach_source_roots_take_precedence() {
+        let toml_value = parse_toml(
+            r#"
+            [tool.tach]
+            source_roots = ["lib", "apps/web"]
+
+            [tool.maturin]
+            python-source = "python"
+            "#,
+        );
+
+        assert_eq!(
+            extract_source_paths(&toml_value, Path::new("/project")),
+            vec![
+                PathBuf::from("/project/lib"),
+                PathBuf::from("/project/apps/web")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_source_paths_detected_without_tach_source_roots() {
+        let toml_value = parse_toml(
+            r#"
+            [tool.tach]
+            exact = true
+
+            [tool.maturin]
+            python-source = "python"
+            "#,
+        );
+
+        assert_eq!(
+            extract_source_paths(&toml_value, Path::new("/project")),
+            vec![PathBuf::from("/project/python")]
+        );
+    }
+
+    #[rstest]
+    #[case("[tool.flit.module]\nname = \"mypkg\"\n", "mypkg")]
+    #[case("[tool.flit.metadata]\nmodule = \"single\"\n", "single.py")]
+    #[case("[tool.flit.module]\nname = \"myorg.lib\"\n", "src/myorg/lib")]
+    fn test_flit_module_source_path(#[case] flit_config: &str, #[case] expected: &str) {
+        let project = write_project(&[
+            ("mypkg/__init__.py", ""),
+            ("single.py", ""),
+            ("src/myorg/lib/__init__.py", ""),
+        ]);
+
+        assert_eq!(
+            extract_source_paths(&parse_toml(flit_config), project.path()),
+            vec![project.path().join(expected)]
+        );
+    }
+
+    #[test]
+    fn test_maturin_python_source_and_packages() {
+        let toml_value = parse_toml(
+            r#"
+            [tool.maturin]
+            python-source = "python"
+            module-name = "mypkg._native"
+            python-packages = ["helpers", "plugins"]
+            "#,
+        );
+
+        assert_eq!(
+            extract_source_paths(&toml_value, Path::new("/project")),
+            vec![
+                PathBuf::from("/project/python"),
+                PathBuf::from("/project/python/helpers"),
+                PathBuf::from("/project/python/plugins")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_maturin_module_name_without_python_source() {
+        let project = write_project(&[("mypkg/__init__.py", "")]);
+        let toml_value = parse_toml("[tool.maturin]\nmodule-name = \"mypkg._native\"\n");
+
+        assert_eq!(
+            extract_source_paths(&toml_value, project.path()),
+            vec![project.path().join("mypkg")]
+        );
+    }
+
+    #[test]
+    fn test_poetry_packages_with_format_and_to() {
+        let toml_value = parse_toml(
+            r#"
+            [tool.poetry]
+            packages = [
+                { include = "mypkg", from = "src", to = "dist" },
+                { include = "wheel_only", format = "wheel" },
+                { include = "both", format = ["sdist", "wheel"] },
+                { include = "tests", format = "sdist" },
+                { include = "docs", format = ["sdist"] },
+            ]
+            "#,
+        );
+
+        assert_eq!(
+            extract_source_paths(&toml_value, Path::new("/project")),
+            vec![
+                PathBuf::from("/project/src/mypkg"),
+                PathBuf::from("/project/wheel_only"),
+                PathBuf::from("/project/both")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_poetry_package_resolves_beneath_to() {
+        let project = write_project(&[
+            (
+                "pyproject.toml",
+                r#"
+                [tool.poetry]
+                name = "relocated"
+                packages = [
+                    { include = "mypkg", from = "src", to = "dist" },
+                    { include = "toplevel" },
+                ]
+                "#,
+            ),
+            ("src/mypkg/__init__.py", ""),
+            ("src/mypkg/utils.py", ""),
+            ("toplevel/__init__.py", ""),
+        ]);
+
+        let project_info = parse_pyproject_toml(
+            &project.path().join("pyproject.toml"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            project_info.import_roots,
+            HashMap::from([(project.path().join("src/mypkg"), "dist".to_string())])
+        );
+        assert_eq!(
+            project_info.resolve_module("dist.mypkg.utils"),
+            Some(project.path().join("src/mypkg/utils.py"))
+        );
+        assert_eq!(project_info.resolve_module("mypkg.utils"), None);
+        assert_eq!(
+            project_info.resolve_module("toplevel"),
+            Some(project.path().join("toplevel"))
+        );
+    }
+
+    #[test]
+    fn test_scikit_build_core_source_paths() {
+        let toml_value = parse_toml(
+            r#"
+            [build-system]
+            requires = ["scikit-build-core"]
+            build-backend = "scikit_build_core.build"
+
+            [project]
+            name = "mypkg"
+            dependencies = ["numpy"]
+
+            [tool.scikit-build]
+            wheel.packages = ["src/mypkg"]
+            wheel.install-dir = "mypkg"
+            "#,
+        );
+
+        assert_eq!(
+            extract_source_paths(&toml_value, Path::new("/project")),
+            vec![PathBuf::from("/project/src/mypkg")]
+        );
+    }
+
+    #[test]
+    fn test_parse_uv_constraints() {
+        let project = write_project(&[(
+            "pyproject.toml",
+            r#"
+            [project]
+            name = "uv-project"
+            dependencies = ["requests"]
+
+            [tool.uv]
+            constraint-dependencies = ["urllib3<2"]
+            override-dependencies = ["Charset_Normalizer==3.3.2", " "]
+            "#,
+        )]);
+
+        assert_eq!(
+            parse_uv_constraints(
+                &project.path().join("pyproject.toml"),
+                &mut NameNormalizer::default(),
+            )
+            .unwrap(),
+            HashSet::from(["urllib3".to_string(), "charset_normalizer".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_validate_source_paths_flags_escaping_path() {
+        let project_root = Path::new("/repo/packages/app");
+        let source_paths = vec![project_root.join("src"), project_root.join("../other")];
+
+        assert_eq!(
+            validate_source_paths(
+                project_root,
+                &source_paths,
+                &ExternalDependencyConfig::default()
+            )
+            .unwrap(),
+            vec![ParsingWarning::SourcePathOutsideProject {
+                path: project_root.join("../other").display().to_string()
+            }]
+        );
+    }
+
+    #[rstest]
+    #[case(false, true)]
+    #[case(true, false)]
+    fn test_validate_source_paths_flags(
+        #[case] allow_external_source_paths: bool,
+        #[case] expect_error: bool,
+    ) {
+        let project_root = Path::new("/repo/packages/app");
+        let config = ExternalDependencyConfig {
+            allow_external_source_paths,
+            strict_source_paths: true,
+            ..Default::default()
+        };
+
+        assert!(
+            validate_source_paths(project_root, &[project_root.join("src/./pkg")], &config)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            validate_source_paths(project_root, &[PathBuf::from("/elsewhere")], &config).is_err(),
+            expect_error
+        );
+    }
+
+    #[rstest]
+    #[case("# pip install foo bar", &["foo", "bar"])]
+    #[case("pip install foo  # and maybe bar", &["foo"])]
+    #[case("RUN pip install --no-cache-dir -r requirements.txt 'Flask>=2' && pip install ./local", &["flask"])]
+    #[case("python -m pip install -i https://example.com/simple numpy; echo done", &["numpy"])]
+    #[case("# no install here", &[])]
+    fn test_parse_pip_install_mentions(#[case] content: &str, #[case] expected: &[&str]) {
+        assert_eq!(
+            parse_pip_install_mentions(content, &mut NameNormalizer::default()),
+            expected.iter().map(|pkg| pkg.to_string()).collect()
+        );
+    }
+
+    #[test]
+    fn test_dependency_order_follows_declaration() {
+        let project_info = parse_pyproject_toml_str(
+            r#"
+            [project]
+            name = "ordered"
+            dependencies = ["zope.interface", "Requests>=2", "attrs", "requests[socks]"]
+
+            [dependency-groups]
+            dev = ["pytest", "attrs"]
+            "#,
+            Path::new("."),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            project_info.dependency_order,
+            vec!["zope_interface", "requests", "attrs", "pytest"]
+        );
+        assert_eq!(
+            project_info.dependencies,
+            project_info.dependency_order.iter().cloned().collect()
+        );
+    }
+
+    #[test]
+    fn test_parse_dependency_origins_lists_every_file() {
+        let project = write_project(&[
+            (
+                "pyproject.toml",
+                "[project]\nname = \"drift\"\ndependencies = [\"requests\", \"rich\"]\n",
+            ),
+            ("requirements.txt", "requests==2.31.0\n"),
+            ("requirements-dev.txt", "pytest\nRequests\n"),
+        ]);
+
+        let origins =
+            parse_dependency_origins(project.path(), &ExternalDependencyConfig::default()).unwrap();
+
+        assert_eq!(
+            origins["requests"],
+            BTreeSet::from([
+                project.path().join("pyproject.toml"),
+                project.path().join("requirements-dev.txt"),
+                project.path().join("requirements.txt"),
+            ])
+        );
+        assert_eq!(
+            origins["pytest"],
+            BTreeSet::from([project.path().join("requirements-dev.txt")])
+        );
+        assert_eq!(
+            origins["rich"],
+            BTreeSet::from([project.path().join("pyproject.toml")])
+        );
+    }
+
+    #[rstest]
+    #[case(false, &["requests", "my_lib", "django"])]
+    #[case(true, &["requests", "my_lib", "django", "pytest"])]
+    fn test_parse_pipfile(#[case] include_dev: bool, #[case] expected: &[&str]) {
+        let project = write_project(&[(
+            "Pipfile",
+            r#"
+            [[source]]
+            url = "https://pypi.org/simple"
+            name = "pypi"
+
+            [packages]
+            Requests = "*"
+            mylib = {git = "https://github.com/example/my-lib.git#egg=My-Lib", ref = "main"}
+            django = {git = "https://github.com/django/django.git", ref = "stable/5.0.x"}
+
+            [dev-packages]
+            pytest = ">=8"
+            "#,
+        )]);
+
+        assert_eq!(
+            parse_pipfile(
+                &project.path().join("Pipfile"),
+                include_dev,
+                &mut NameNormalizer::default(),
+            )
+            .unwrap(),
+            expected.iter().map(|pkg| pkg.to_string()).collect()
+        );
+    }
+
+    #[rstest]
+    #[case(false, &["requests", "urllib3"])]
+    #[case(true, &["requests", "urllib3", "pytest"])]
+    fn test_parse_pipfile_lock(#[case] include_dev: bool, #[case] expected: &[&str]) {
+        let project = write_project(&[(
+            "Pipfile.lock",
+            r#"{
+                "_meta": {"hash": {"sha256": "abc"}},
+                "default": {
+                    "requests": {"version": "==2.31.0"},
+                    "urllib3": {"version": "==2.2.1"}
+                },
+                "develop": {
+                    "pytest": {"version": "==8.1.1"}
+                }
+            }"#,
+        )]);
+
+        assert_eq!(
+            parse_pipfile_lock(
+                &project.path().join("Pipfile.lock"),
+                include_dev,
+                &mut NameNormalizer::default(),
+            )
+            .unwrap(),
+            expected.iter().map(|pkg| pkg.to_string()).collect()
+        );
+    }
+
+    #[test]
+    fn test_single_module_source_path_is_resolvable() {
+        let project = write_project(&[
+            (
+                "pyproject.toml",
+                "[project]\nname = \"single\"\n\n[tool.setuptools]\npy-modules = [\"mymod\"]\npackages = [\"mypkg\"]\n",
+            ),
+            ("mymod.py", "VALUE = 1\n"),
+        ]);
+        fs::create_dir(project.path().join("mypkg")).unwrap();
+        fs::write(project.path().join("mypkg/__init__.py"), "").unwrap();
+        fs::write(project.path().join("mypkg/utils.py"), "").unwrap();
+
+        let project_info = parse_pyproject_toml(
+            &project.path().join("pyproject.toml"),
+            &ExternalDependencyConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            project_info.source_roots(),
+            vec![
+                SourceRoot::Directory(project.path().join("mypkg")),
+                SourceRoot::SingleModule {
+                    module: "mymod".to_string(),
+                    path: project.path().join("mymod.py"),
+                },
+            ]
+        );
+        assert_eq!(
+            project_info.resolve_module("mymod"),
+            Some(project.path().join("mymod.py"))
+        );
+        assert_eq!(
+            project_info.resolve_module("mypkg"),
+            Some(project.path().join("mypkg"))
+        );
+        assert_eq!(
+            project_info.resolve_module("mypkg.utils"),
+            Some(project.path().join("mypkg/utils.py"))
+        );
+        assert_eq!(project_info.resolve_module("mymod.missing"), None);
+    }
+
+    #[test]
+    fn test_parse_conda_lock_unified_layout() {
+        let project = write_project(&[(
+            "conda-lock.yml",
+            r#"version: 1
+metadata:
+  platforms:
+  - linux-64
+package:
+- name: numpy
+  version: 1.26.4
+  manager: conda
+  platform: linux-64
+  dependencies:
+    python: '>=3.11'
+  hash:
+    md5: abc123
+- name: Scikit-Learn
+  version: 1.4.0
+  manager: pip
+  platform: linux-64
+  dependencies: {}
+"#,
+        )]);
+
+        let dependencies = parse_conda_lock(
+            &project.path().join("conda-lock.yml"),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            dependencies,
+            HashSet::from(["numpy".to_string(), "scikit_learn".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_conda_environment() {
+        let project = write_project(&[(
+            "environment.yml",
+            r#"name: analysis
+channels:
+  - conda-forge
+dependencies:
+  - python=3.11
+  - numpy=1.26
+  - conda-forge::pandas>=2
+  - "scikit-learn 1.4.*"  # pinned for the models
+  - pip
+  - pip:
+    - requests>=2
+    - -r requirements-extra.txt
+    - Flask[async]
+  - matplotlib
+variables:
+  - not-a-dependency
+"#,
+        )]);
+
+        let dependencies = parse_conda_environment(
+            &project.path().join("environment.yml"),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            dependencies,
+            HashSet::from([
+                "numpy".to_string(),
+                "pandas".to_string(),
+                "scikit_learn".to_string(),
+                "requests".to_string(),
+                "flask".to_string(),
+                "matplotlib".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_conda_lock_per_platform_layout() {
+        let project = write_project(&[(
+            "conda-lock.yml",
+            r#"version: 1
+package:
+  linux-64:
+    - name: numpy
+      version: 1.26.4
+    - name: "pandas"
+      version: 2.2.0
+  osx-arm64:
+    - name: numpy
+      version: 1.26.4
+"#,
+        )]);
+
+        let dependencies = parse_conda_lock(
+            &project.path().join("conda-lock.yml"),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            dependencies,
+            HashSet::from(["numpy".to_string(), "pandas".to_string()])
+        );
+    }
 }
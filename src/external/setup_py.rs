@@ -0,0 +1,187 @@
+use ruff_python_ast::visitor::{Visitor, walk_expr};
+use ruff_python_ast::{Expr, ExprCall, Mod};
+
+use crate::python::error::ParsingError;
+use crate::python::parsing::parse_python_source;
+
+/// The literal arguments of the `setup(...)` call in a `setup.py`
+#[derive(Debug, Default, PartialEq)]
+pub struct SetupCall {
+    pub name: Option<String>,
+    pub install_requires: Vec<String>,
+    /// Explicitly listed packages, or `None` when they are discovered (e.g. with `find_packages()`)
+    pub packages: Option<Vec<String>>,
+    pub package_dir: Vec<(String, String)>,
+    pub py_modules: Vec<String>,
+    /// Arguments which are built dynamically, and so could not be read statically
+    pub dynamic_arguments: Vec<String>,
+}
+
+fn string_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::StringLiteral(string) => Some(string.value.to_string()),
+        _ => None,
+    }
+}
+
+/// The literal strings of a list or tuple, or `None` if any element is not a literal
+fn string_list(expr: &Expr) -> Option<Vec<String>> {
+    let elements = match expr {
+        Expr::List(list) => &list.elts,
+        Expr::Tuple(tuple) => &tuple.elts,
+        _ => return None,
+    };
+    elements.iter().map(string_literal).collect()
+}
+
+fn is_package_discovery(expr: &Expr) -> bool {
+    let Expr::Call(call) = expr else {
+        return false;
+    };
+    let function_name = match &*call.func {
+        Expr::Name(name) => name.id.as_str(),
+        Expr::Attribute(attribute) => attribute.attr.as_str(),
+        _ => return false,
+    };
+    matches!(function_name, "find_packages" | "find_namespace_packages")
+}
+
+fn is_setup_function(expr: &Expr) -> bool {
+    match expr {
+        Expr::Name(name) => name.id.as_str() == "setup",
+        // e.g. `setuptools.setup(...)`
+        Expr::Attribute(attribute) => attribute.attr.as_str() == "setup",
+        _ => false,
+    }
+}
+
+impl SetupCall {
+    fn from_call(call: &ExprCall) -> Self {
+        let mut setup_call = Self::default();
+        for keyword in &call.arguments.keywords {
+            let Some(argument) = keyword.arg.as_deref() else {
+                // `setup(**kwargs)`
+                setup_call.dynamic_arguments.push("**kwargs".to_string());
+                continue;
+            };
+            let value = &keyword.value;
+            let is_literal = match argument {
+                "name" => string_literal(value)
+                    .map(|name| setup_call.name = Some(name))
+                    .is_some(),
+                "install_requires" => string_list(value)
+                    .or_else(|| {
+                        // A single string may hold one requirement per line
+                        string_literal(value).map(|value| {
+                            value
+                                .lines()
+                                .map(str::trim)
+                                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                                .map(str::to_string)
+                                .collect()
+                        })
+                    })
+                    .map(|requirements| setup_call.install_requires = requirements)
+                    .is_some(),
+                "packages" if is_package_discovery(value) => true,
+                "packages" => string_list(value)
+                    .map(|packages| setup_call.packages = Some(packages))
+                    .is_some(),
+                "package_dir" => match value {
+                    Expr::Dict(dict) => dict
+                        .items
+                        .iter()
+                        .map(|item| {
+                            Some((
+                                string_literal(item.key.as_ref()?)?,
+                                string_literal(&item.value)?,
+                            ))
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .map(|package_dir| setup_call.package_dir = package_dir)
+                        .is_some(),
+                    _ => false,
+                },
+                "py_modules" => string_list(value)
+                    .map(|py_modules| setup_call.py_modules = py_modules)
+                    .is_some(),
+                _ => true,
+            };
+            if !is_literal {
+                setup_call.dynamic_arguments.push(argument.to_string());
+            }
+        }
+        setup_call
+    }
+}
+
+#[derive(Default)]
+struct SetupCallVisitor {
+    setup_call: Option<SetupCall>,
+}
+
+impl Visitor<'_> for SetupCallVisitor {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if self.setup_call.is_some() {
+            return;
+        }
+        if let Expr::Call(call) = expr
+            && is_setup_function(&call.func)
+        {
+            self.setup_call = Some(SetupCall::from_call(call));
+            return;
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Find the first `setup(...)` call in `setup.py` source and read its literal arguments.
+/// Returns `Ok(None)` when the source contains no `setup(...)` call.
+pub fn find_setup_call(source: &str) -> Result<Option<SetupCall>, ParsingError> {
+    let Mod::Module(module) = parse_python_source(source)? else {
+        return Err(ParsingError::InvalidSyntax);
+    };
+    let mut visitor = SetupCallVisitor::default();
+    visitor.visit_body(&module.body);
+    Ok(visitor.setup_call)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_setup_call() {
+        let source = r#"
+import os
+from setuptools import setup, find_packages
+
+if __name__ == "__main__":
+    setup(
+        name="legacy-pkg",
+        install_requires=["requests>=2", "PyYAML"],
+        extras_require={"docs": ["mkdocs"]},
+        packages=find_packages(where="src"),
+        package_dir={"": "src"},
+        long_description=open("README.md").read(),
+        py_modules=[m for m in os.listdir(".")],
+    )
+"#;
+        assert_eq!(
+            find_setup_call(source).unwrap(),
+            Some(SetupCall {
+                name: Some("legacy-pkg".to_string()),
+                install_requires: vec!["requests>=2".to_string(), "PyYAML".to_string()],
+                packages: None,
+                package_dir: vec![("".to_string(), "src".to_string())],
+                py_modules: vec![],
+                dynamic_arguments: vec!["py_modules".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_setup_call_without_setup() {
+        assert_eq!(find_setup_call("print('hello')\n").unwrap(), None);
+    }
+}
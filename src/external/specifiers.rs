@@ -0,0 +1,234 @@
+use std::cmp::Ordering;
+
+use super::markers::{compare_versions, version_components};
+
+/// One end of the range of versions allowed by a set of specifiers
+#[derive(Debug, Clone)]
+struct Bound {
+    version: Vec<u64>,
+    inclusive: bool,
+}
+
+/// The range of versions allowed by a set of specifiers. Unbounded ends are `None`.
+#[derive(Debug, Default)]
+struct VersionRange {
+    lower: Option<Bound>,
+    upper: Option<Bound>,
+}
+
+/// The version which follows every version starting with `prefix`, e.g. `1.3` for `1.2`
+fn next_prefix(prefix: &[u64]) -> Vec<u64> {
+    let mut next = prefix.to_vec();
+    if let Some(last) = next.last_mut() {
+        *last += 1;
+    }
+    next
+}
+
+impl VersionRange {
+    fn raise_lower(&mut self, version: Vec<u64>, inclusive: bool) {
+        let is_tighter = self.lower.as_ref().is_none_or(|lower| {
+            match compare_versions(&version, &lower.version) {
+                Ordering::Greater => true,
+                Ordering::Equal => lower.inclusive && !inclusive,
+                Ordering::Less => false,
+            }
+        });
+        if is_tighter {
+            self.lower = Some(Bound { version, inclusive });
+        }
+    }
+
+    fn reduce_upper(&mut self, version: Vec<u64>, inclusive: bool) {
+        let is_tighter = self.upper.as_ref().is_none_or(|upper| {
+            match compare_versions(&version, &upper.version) {
+                Ordering::Less => true,
+                Ordering::Equal => upper.inclusive && !inclusive,
+                Ordering::Greater => false,
+            }
+        });
+        if is_tighter {
+            self.upper = Some(Bound { version, inclusive });
+        }
+    }
+
+    /// Narrow the range by a single clause, such as `>=2.0` or `==1.4.*`.
+    /// Clauses which do not bound the range (such as `!=`) or cannot be parsed are ignored.
+    fn apply(&mut self, clause: &str) {
+        let clause = clause.trim();
+        let version_start = clause
+            .find(|c: char| !"<>=!~".contains(c))
+            .unwrap_or(clause.len());
+        let (operator, version) = clause.split_at(version_start);
+        let version = version.trim();
+        if let Some(prefix) = version.strip_suffix(".*") {
+            let prefix = version_components(prefix);
+            if operator == "==" && !prefix.is_empty() {
+                self.reduce_upper(next_prefix(&prefix), false);
+                self.raise_lower(prefix, true);
+            }
+            return;
+        }
+        let components = version_components(version);
+        if components.is_empty() {
+            return;
+        }
+        match operator {
+            ">=" => self.raise_lower(components, true),
+            ">" => self.raise_lower(components, false),
+            "<=" => self.reduce_upper(components, true),
+            "<" => self.reduce_upper(components, false),
+            "==" => {
+                self.raise_lower(components.clone(), true);
+                self.reduce_upper(components, true);
+            }
+            "~=" if components.len() >= 2 => {
+                // Compatible release: at least the version, with the same prefix up to its last component
+                let prefix = &components[..components.len() - 1];
+                self.reduce_upper(next_prefix(prefix), false);
+                self.raise_lower(components, true);
+            }
+            _ => {}
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        let (Some(lower), Some(upper)) = (&self.lower, &self.upper) else {
+            return false;
+        };
+        match compare_versions(&lower.version, &upper.version) {
+            Ordering::Greater => true,
+            Ordering::Equal => !(lower.inclusive && upper.inclusive),
+            Ordering::Less => false,
+        }
+    }
+}
+
+/// The version specifier of a PEP 508 requirement (without its marker), such as `>=2,<3`
+/// for `requests[socks] (>=2,<3)`. Returns `None` for unversioned and direct URL requirements.
+pub fn requirement_specifier(requirement: &str) -> Option<&str> {
+    let requirement = requirement.trim();
+    let name_end = requirement
+        .find(|c: char| !(c.is_alphanumeric() || "-_.".contains(c)))
+        .unwrap_or(requirement.len());
+    let mut rest = requirement[name_end..].trim_start();
+    if rest.starts_with('[') {
+        rest = rest[rest.find(']')? + 1..].trim_start();
+    }
+    if rest.starts_with('@') {
+        return None;
+    }
+    let specifier = rest.trim_start_matches('(').trim_end_matches(')').trim();
+    (!specifier.is_empty()).then_some(specifier)
+}
+
+/// Translate a Poetry version constraint, such as `^1.2` or `~1.2.3`, into a PEP 440 specifier.
+/// Caret constraints allow changes which keep the leftmost non-zero component, tilde constraints
+/// allow patch-level changes, and bare versions are exact. Returns `None` for unconstrained (`*`)
+/// and union (`||`) constraints, which have no single-range equivalent.
+pub fn poetry_constraint_specifier(constraint: &str) -> Option<String> {
+    let constraint = constraint.trim();
+    if constraint.is_empty() || constraint == "*" || constraint.contains("||") {
+        return None;
+    }
+    let clauses: Option<Vec<String>> = constraint
+        .split(',')
+        .map(str::trim)
+        .filter(|clause| !clause.is_empty() && *clause != "*")
+        .map(poetry_clause_specifier)
+        .collect();
+    let clauses = clauses?;
+    (!clauses.is_empty()).then(|| clauses.join(","))
+}
+
+fn poetry_clause_specifier(clause: &str) -> Option<String> {
+    if let Some(version) = clause.strip_prefix('^') {
+        let components = version_components(version.trim());
+        if components.is_empty() {
+            return None;
+        }
+        // Everything before the leftmost non-zero component is fixed, along with that component
+        let fixed = components
+            .iter()
+            .position(|component| *component != 0)
+            .unwrap_or(components.len() - 1);
+        let upper = next_prefix(&components[..=fixed]);
+        return Some(format!(">={},<{}", version.trim(), join_version(&upper)));
+    }
+    if let Some(version) = clause
+        .strip_prefix('~')
+        .filter(|_| !clause.starts_with("~="))
+    {
+        let components = version_components(version.trim());
+        if components.is_empty() {
+            return None;
+        }
+        // `~1` allows minor changes, while more precise versions only allow patch changes
+        let upper = next_prefix(&components[..components.len().min(2)]);
+        return Some(format!(">={},<{}", version.trim(), join_version(&upper)));
+    }
+    if clause.starts_with(|c: char| c.is_ascii_digit()) {
+        return Some(format!("=={clause}"));
+    }
+    Some(clause.to_string())
+}
+
+fn join_version(components: &[u64]) -> String {
+    components
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// Whether no single version satisfies every one of the given specifiers, e.g. `>=2` and `<2`
+pub fn specifiers_conflict(specifiers: &[&str]) -> bool {
+    let mut range = VersionRange::default();
+    for clause in specifiers.iter().flat_map(|specifier| specifier.split(',')) {
+        range.apply(clause);
+    }
+    range.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("requests>=2", Some(">=2"))]
+    #[case("requests[socks] (>=2,<3)", Some(">=2,<3"))]
+    #[case("Foo.Bar ~= 1.4", Some("~= 1.4"))]
+    #[case("requests", None)]
+    #[case("pkg @ https://example.com/pkg-1.0-py3-none-any.whl", None)]
+    fn test_requirement_specifier(#[case] requirement: &str, #[case] expected: Option<&str>) {
+        assert_eq!(requirement_specifier(requirement), expected);
+    }
+
+    #[rstest]
+    #[case("^1.2.3", Some(">=1.2.3,<2"))]
+    #[case("^0.2.3", Some(">=0.2.3,<0.3"))]
+    #[case("^0.0.3", Some(">=0.0.3,<0.0.4"))]
+    #[case("~1.2.3", Some(">=1.2.3,<1.3"))]
+    #[case("~1", Some(">=1,<2"))]
+    #[case("~=1.2", Some("~=1.2"))]
+    #[case(">=2, <3", Some(">=2,<3"))]
+    #[case("1.4.2", Some("==1.4.2"))]
+    #[case("*", None)]
+    #[case("^1 || ^2", None)]
+    fn test_poetry_constraint_specifier(#[case] constraint: &str, #[case] expected: Option<&str>) {
+        assert_eq!(poetry_constraint_specifier(constraint).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case(&[">=2", "<2"], true)]
+    #[case(&[">=2", "<=2"], false)]
+    #[case(&[">2", "<=2"], true)]
+    #[case(&[">=2,<3", ">=2.5"], false)]
+    #[case(&["==1.4.*", ">=1.5"], true)]
+    #[case(&["~=2.2", "<3"], false)]
+    #[case(&["~=2.2", ">=3"], true)]
+    fn test_specifiers_conflict(#[case] specifiers: &[&str], #[case] expected: bool) {
+        assert_eq!(specifiers_conflict(specifiers), expected);
+    }
+}
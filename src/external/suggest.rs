@@ -0,0 +1,69 @@
+/// The Levenshtein distance between two names, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution_cost = usize::from(a_char != *b_char);
+            current[j + 1] = (previous[j] + substitution_cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b_chars.len()]
+}
+
+/// Find the candidate closest to `name` within `max_distance` edits, to suggest as a likely typo.
+/// Names shorter than `min_length` never receive suggestions, since nearly any other short
+/// name is within a couple of edits. Ties are broken alphabetically.
+pub fn suggest_similar_name<'a, I>(
+    name: &str,
+    candidates: I,
+    max_distance: usize,
+    min_length: usize,
+) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    if name.chars().count() < min_length {
+        return None;
+    }
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate.as_str()))
+        .filter(|(distance, _)| *distance > 0 && *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("reqeusts", 2, 4, Some("requests"))]
+    #[case("requets", 2, 4, Some("requests"))]
+    #[case("rqsts", 2, 4, None)]
+    #[case("rqsts", 3, 4, Some("requests"))]
+    #[case("ujsn", 2, 5, None)]
+    #[case("ujsn", 2, 4, Some("ujson"))]
+    fn test_suggest_similar_name(
+        #[case] name: &str,
+        #[case] max_distance: usize,
+        #[case] min_length: usize,
+        #[case] expected: Option<&str>,
+    ) {
+        let candidates = vec!["requests".to_string(), "ujson".to_string()];
+        assert_eq!(
+            suggest_similar_name(name, &candidates, max_distance, min_length),
+            expected
+        );
+    }
+}
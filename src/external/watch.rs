@@ -0,0 +1,175 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use super::parsing::{ProjectInfo, parse_project_root};
+use crate::config::ExternalDependencyConfig;
+
+/// The manifest files which are watched for changes
+const WATCHED_FILES: [&str; 2] = ["pyproject.toml", "requirements.txt"];
+
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// How often the watched files are read
+    pub poll_interval: Duration,
+    /// How long the content must remain unchanged before a change is emitted
+    pub debounce: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(100),
+            debounce: Duration::from_millis(300),
+        }
+    }
+}
+
+/// A fingerprint of the content of the watched files, so that touching a file
+/// without changing it does not count as a change.
+fn content_fingerprint(project_root: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for file_name in WATCHED_FILES {
+        fs::read(project_root.join(file_name))
+            .ok()
+            .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Watches a project's dependency manifests on a background thread,
+/// invoking a callback with freshly parsed `ProjectInfo` after each debounced content change.
+/// The watcher stops when dropped.
+pub struct ProjectWatcher {
+    stopped: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ProjectWatcher {
+    pub fn spawn<F>(
+        project_root: PathBuf,
+        config: ExternalDependencyConfig,
+        options: WatchOptions,
+        mut callback: F,
+    ) -> Self
+    where
+        F: FnMut(ProjectInfo) + Send + 'static,
+    {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = Arc::clone(&stopped);
+        let handle = thread::spawn(move || {
+            let mut emitted = content_fingerprint(&project_root);
+            let mut pending: Option<(u64, Instant)> = None;
+
+            while !thread_stopped.load(Ordering::Relaxed) {
+                thread::sleep(options.poll_interval);
+                let current = content_fingerprint(&project_root);
+
+                pending = match pending {
+                    _ if current == emitted => None,
+                    Some((fingerprint, since)) if fingerprint == current => {
+                        if since.elapsed() < options.debounce {
+                            Some((fingerprint, since))
+                        } else {
+                            emitted = current;
+                            // Manifests which fail to parse are skipped until they are fixed
+                            if let Ok(Some(project_info)) =
+                                parse_project_root(&project_root, &config)
+                            {
+                                callback(project_info);
+                            }
+                            None
+                        }
+                    }
+                    // The content changed again, so restart the debounce
+                    _ => Some((current, Instant::now())),
+                };
+            }
+        });
+
+        Self {
+            stopped,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ProjectWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::fs::File;
+    use std::sync::Mutex;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_watcher_emits_once_after_debounce() {
+        let project = TempDir::with_prefix("tach-test").unwrap();
+        let pyproject_path = project.path().join("pyproject.toml");
+        fs::write(
+            &pyproject_path,
+            "[project]\ndependencies = [\"requests\"]\n",
+        )
+        .unwrap();
+
+        let emitted = Arc::new(Mutex::new(Vec::new()));
+        let callback_emitted = Arc::clone(&emitted);
+        let mut watcher = ProjectWatcher::spawn(
+            project.path().to_path_buf(),
+            ExternalDependencyConfig::default(),
+            WatchOptions {
+                poll_interval: Duration::from_millis(10),
+                debounce: Duration::from_millis(50),
+            },
+            move |project_info| {
+                callback_emitted
+                    .lock()
+                    .unwrap()
+                    .push(project_info.dependencies);
+            },
+        );
+
+        // Consecutive writes within the debounce window are emitted together
+        fs::write(&pyproject_path, "[project]\ndependencies = [\"rich\"]\n").unwrap();
+        fs::write(
+            &pyproject_path,
+            "[project]\ndependencies = [\"requests\", \"rich\"]\n",
+        )
+        .unwrap();
+        thread::sleep(Duration::from_millis(500));
+
+        // Touching the file without changing its content is not a change
+        File::options()
+            .write(true)
+            .open(&pyproject_path)
+            .unwrap()
+            .set_modified(SystemTime::now())
+            .unwrap();
+        thread::sleep(Duration::from_millis(200));
+        watcher.stop();
+
+        assert_eq!(
+            *emitted.lock().unwrap(),
+            vec![HashSet::from(["requests".to_string(), "rich".to_string()])]
+        );
+    }
+}
@@ -241,9 +241,9 @@ pub fn is_hidden(entry: &ignore::DirEntry) -> bool {
         .unwrap_or(false)
 }
 
-fn is_pyfile(entry: &ignore::DirEntry) -> bool {
-    match entry.path().extension() {
-        Some(ext) => ext == "py",
+fn is_source_file(entry: &ignore::DirEntry, source_extensions: &[String]) -> bool {
+    match entry.path().extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => source_extensions.iter().any(|source_ext| source_ext == ext),
         None => false,
     }
 }
@@ -299,6 +299,7 @@ pub struct FSWalker {
     _project_root: PathBuf,
     overrides: ignore::overrides::Override,
     walk_builder: ignore::WalkBuilder,
+    source_extensions: Vec<String>,
 }
 
 impl FSWalker {
@@ -328,9 +329,19 @@ impl FSWalker {
             _project_root: project_root.as_ref().to_path_buf(),
             overrides,
             walk_builder,
+            source_extensions: vec!["py".to_string()],
         })
     }
 
+    /// Set the file extensions which are considered Python source (default: 'py')
+    pub fn with_source_extensions(mut self, source_extensions: &[String]) -> Self {
+        self.source_extensions = source_extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.').to_string())
+            .collect();
+        self
+    }
+
     pub fn empty<P: AsRef<Path>>(project_root: P) -> Self {
         Self::try_new(project_root, &[], RespectGitIgnore::False).unwrap()
     }
@@ -377,9 +388,11 @@ impl FSWalker {
 
     pub fn walk_pyfiles(&self, root: &str) -> impl Iterator<Item = PathBuf> + use<> {
         let prefix = root.to_string();
+        let source_extensions = self.source_extensions.clone();
         self.walk_non_excluded_paths(root)
-            .filter(|entry| {
-                entry.file_type().map(|t| t.is_file()).unwrap_or(false) && is_pyfile(entry)
+            .filter(move |entry| {
+                entry.file_type().map(|t| t.is_file()).unwrap_or(false)
+                    && is_source_file(entry, &source_extensions)
             })
             .map(move |entry| relative_to(entry.path(), &prefix).unwrap())
     }
@@ -454,6 +467,7 @@ mod tests {
     use super::*;
     use crate::tests::fixtures::tests_dir;
     use rstest::rstest;
+    use tempfile::TempDir;
 
     #[rstest]
     #[case(&["."], "__init__.py", ".")]
@@ -480,4 +494,26 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn test_walk_pyfiles_source_extensions() {
+        let temp_dir = TempDir::with_prefix("tach-test").unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("module.py"), "").unwrap();
+        fs::write(root.join("stub_only.pyi"), "").unwrap();
+        let root_str = root.display().to_string();
+
+        let walker = FSWalker::empty(root);
+        let files: Vec<PathBuf> = walker.walk_pyfiles(&root_str).collect();
+        assert_eq!(files, vec![PathBuf::from("module.py")]);
+
+        let walker =
+            FSWalker::empty(root).with_source_extensions(&["py".to_string(), "pyi".to_string()]);
+        let mut files: Vec<PathBuf> = walker.walk_pyfiles(&root_str).collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![PathBuf::from("module.py"), PathBuf::from("stub_only.pyi")]
+        );
+    }
 }
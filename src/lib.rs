@@ -20,7 +20,10 @@ pub mod resolvers;
 pub mod tests;
 use crate::config::RespectGitIgnore;
 use commands::{check, report, server, sync, test};
-use diagnostics::serialize_diagnostics_json;
+use diagnostics::{
+    rule_exit_code_py, serialize_check_output_json, serialize_diagnostics_json,
+    serialize_rule_summary_json,
+};
 use pyo3::{prelude::*, types::PyTuple};
 use std::path::PathBuf;
 
@@ -239,35 +242,90 @@ fn create_dependency_report(
     )
 }
 
+/// Describe the active Python interpreter by its version and, with `include_distributions`,
+/// its installed distributions, which affect import resolution
+fn interpreter_description(py: Python<'_>, include_distributions: bool) -> PyResult<String> {
+    let version = py.version_info();
+    let version = format!("{}.{}.{}", version.major, version.minor, version.patch);
+    if !include_distributions {
+        return Ok(version);
+    }
+    let mut distributions = py
+        .import("importlib.metadata")?
+        .call_method0("distributions")?
+        .try_iter()?
+        .map(|distribution| {
+            let distribution = distribution?;
+            let name: Option<String> = distribution
+                .getattr("metadata")?
+                .call_method1("get", ("Name",))?
+                .extract()?;
+            let version: Option<String> = distribution.getattr("version")?.extract()?;
+            Ok(format!(
+                "{}=={}",
+                name.unwrap_or_default(),
+                version.unwrap_or_default()
+            ))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    distributions.sort();
+    Ok(format!("{version};{}", distributions.join(";")))
+}
+
 #[pyfunction]
+#[allow(clippy::too_many_arguments)]
 fn create_computation_cache_key(
+    py: Python<'_>,
     project_root: PathBuf,
     source_roots: Vec<PathBuf>,
+    source_extensions: Vec<String>,
     action: String,
-    py_interpreter_version: String,
+    interpreter_sensitive: bool,
+    interpreter_distributions: bool,
     file_dependencies: Vec<String>,
     env_dependencies: Vec<String>,
     backend: String,
     respect_gitignore: RespectGitIgnore,
-) -> String {
-    cache::create_computation_cache_key(
+    rules: config::RulesConfig,
+    irrelevant_rules: Vec<String>,
+) -> PyResult<String> {
+    let interpreter = if interpreter_sensitive {
+        Some(interpreter_description(py, interpreter_distributions)?)
+    } else {
+        None
+    };
+    Ok(cache::create_computation_cache_key(
         &project_root,
         &source_roots,
+        &source_extensions,
         action,
-        py_interpreter_version,
+        interpreter,
         file_dependencies,
         env_dependencies,
         backend,
         respect_gitignore,
-    )
+        &rules,
+        &irrelevant_rules,
+    ))
+}
+
+#[pyfunction]
+fn resolve_cache_dir(project_root: PathBuf) -> PathBuf {
+    cache::resolve_cache_dir(&project_root)
+}
+
+#[pyfunction]
+fn prepare_cache_dir(project_root: PathBuf) -> PathBuf {
+    cache::prepare_cache_dir(&project_root)
 }
 
 #[pyfunction]
 fn check_computation_cache(
     project_root: PathBuf,
     cache_key: String,
+    cache_config: config::CacheConfig,
 ) -> cache::Result<Option<cache::ComputationCacheValue>> {
-    cache::check_computation_cache(&project_root, cache_key)
+    cache::check_computation_cache(&project_root, cache_key, &cache_config)
 }
 
 #[pyfunction]
@@ -275,8 +333,9 @@ fn update_computation_cache(
     project_root: PathBuf,
     cache_key: String,
     value: cache::ComputationCacheValue,
+    cache_config: config::CacheConfig,
 ) -> cache::Result<Option<cache::ComputationCacheValue>> {
-    cache::update_computation_cache(&project_root, cache_key, value)
+    cache::update_computation_cache(&project_root, cache_key, value, &cache_config)
 }
 
 #[pyfunction]
@@ -336,6 +395,7 @@ fn extension(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<config::DependencyConfig>()?;
     m.add_class::<config::MapConfig>()?;
     m.add_class::<diagnostics::Diagnostic>()?;
+    m.add_class::<external::parsing::ProjectInfo>()?;
     m.add_class::<dep_map::PyDependentMap>()?;
     m.add_class::<dep_map::PyDirection>()?;
     m.add_class::<test::TachPytestPluginHandler>()?;
@@ -346,6 +406,8 @@ fn extension(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(check_external_dependencies, m)?)?;
     m.add_function(wrap_pyfunction!(create_dependency_report, m)?)?;
     m.add_function(wrap_pyfunction!(create_computation_cache_key, m)?)?;
+    m.add_function(wrap_pyfunction!(resolve_cache_dir, m)?)?;
+    m.add_function(wrap_pyfunction!(prepare_cache_dir, m)?)?;
     m.add_function(wrap_pyfunction!(check_computation_cache, m)?)?;
     m.add_function(wrap_pyfunction!(update_computation_cache, m)?)?;
     m.add_function(wrap_pyfunction!(dump_project_config_to_toml, m)?)?;
@@ -356,5 +418,9 @@ fn extension(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(run_server, m)?)?;
     m.add_function(wrap_pyfunction!(serialize_modules_json, m)?)?;
     m.add_function(wrap_pyfunction!(serialize_diagnostics_json, m)?)?;
+    m.add_function(wrap_pyfunction!(serialize_check_output_json, m)?)?;
+    m.add_function(wrap_pyfunction!(serialize_rule_summary_json, m)?)?;
+    m.add_function(wrap_pyfunction!(rule_exit_code_py, m)?)?;
+    m.add_function(wrap_pyfunction!(config::schema::config_json_schema_py, m)?)?;
     Ok(())
 }
@@ -58,6 +58,7 @@ impl From<Severity> for lsp_types::DiagnosticSeverity {
         match severity {
             Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
             Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+            Severity::Info => lsp_types::DiagnosticSeverity::INFORMATION,
         }
     }
 }
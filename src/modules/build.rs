@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use crate::{
-    config::{ModuleConfig, RootModuleTreatment},
+    config::{ModuleConfig, RootModuleTreatment, RuleSetting},
     filesystem,
     resolvers::{ModuleResolver, glob},
 };
@@ -9,14 +9,14 @@ use crate::{
 use super::{
     ModuleTree, ModuleTreeError,
     validation::{
-        find_duplicate_modules, find_modules_with_cycles, find_visibility_violations,
+        find_dependency_cycles, find_duplicate_modules, find_visibility_violations,
         validate_root_module_treatment,
     },
 };
 
 pub struct ModuleTreeBuilder<'a> {
     resolver: ModuleResolver<'a>,
-    forbid_circular_dependencies: bool,
+    circular_dependencies: RuleSetting,
     root_module_treatment: RootModuleTreatment,
 }
 
@@ -24,12 +24,12 @@ impl<'a> ModuleTreeBuilder<'a> {
     pub fn new(
         source_roots: &'a [PathBuf],
         file_walker: &'a filesystem::FSWalker,
-        forbid_circular_dependencies: bool,
+        circular_dependencies: RuleSetting,
         root_module_treatment: RootModuleTreatment,
     ) -> Self {
         Self {
             resolver: ModuleResolver::new(source_roots, file_walker),
-            forbid_circular_dependencies,
+            circular_dependencies,
             root_module_treatment,
         }
     }
@@ -83,13 +83,12 @@ impl<'a> ModuleTreeBuilder<'a> {
         // Check for root module treatment errors
         validate_root_module_treatment(self.root_module_treatment, &modules)?;
 
-        // Check for circular dependencies if forbidden
-        if self.forbid_circular_dependencies {
-            let module_paths = find_modules_with_cycles(&modules);
-            if !module_paths.is_empty() {
-                return Err(ModuleTreeError::CircularDependency(
-                    module_paths.iter().map(|s| s.to_string()).collect(),
-                ));
+        // Check for circular dependencies if they are errors.
+        // Callers report cycles themselves when they are only warnings.
+        if self.circular_dependencies.is_error() {
+            let cycles = find_dependency_cycles(&modules);
+            if !cycles.is_empty() {
+                return Err(ModuleTreeError::CircularDependency(cycles));
             }
         }
 
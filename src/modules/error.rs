@@ -21,7 +21,7 @@ pub enum ModuleTreeError {
     )]
     VisibilityViolation(Vec<VisibilityErrorInfo>),
     #[error("Circular dependency detected: {0:?}")]
-    CircularDependency(Vec<String>),
+    CircularDependency(Vec<Vec<String>>),
     #[error("Root module violation: {0:?}")]
     RootModuleViolation(String),
     #[error("Parsing Error while building module tree.\n{0}")]
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::RandomState;
 
 use crate::config::ModuleConfig;
@@ -85,7 +85,9 @@ pub fn find_visibility_violations(
     Ok(results)
 }
 
-pub fn find_modules_with_cycles(modules: &[ModuleConfig]) -> Vec<&String> {
+fn build_dependency_graph(
+    modules: &[ModuleConfig],
+) -> DiGraphMap<&String, Option<()>, RandomState> {
     let mut graph: DiGraphMap<&String, Option<()>, RandomState> = DiGraphMap::new();
 
     // Add nodes
@@ -100,6 +102,12 @@ pub fn find_modules_with_cycles(modules: &[ModuleConfig]) -> Vec<&String> {
         }
     }
 
+    graph
+}
+
+pub fn find_modules_with_cycles(modules: &[ModuleConfig]) -> Vec<&String> {
+    let graph = build_dependency_graph(modules);
+
     // Find strongly connected components (SCCs)
     let sccs = kosaraju_scc(&graph);
 
@@ -114,6 +122,58 @@ pub fn find_modules_with_cycles(modules: &[ModuleConfig]) -> Vec<&String> {
     modules_with_cycles
 }
 
+/// Find one dependency cycle within each group of mutually dependent modules.
+/// Each cycle is the path of module paths from its first module back to itself, e.g. `[a, b, c, a]`.
+/// The shortest such path is reported, starting from the first of the cycle's modules in configuration order.
+pub fn find_dependency_cycles(modules: &[ModuleConfig]) -> Vec<Vec<String>> {
+    let graph = build_dependency_graph(modules);
+
+    let mut cycles = Vec::new();
+    for scc in kosaraju_scc(&graph) {
+        if scc.len() < 2 {
+            continue;
+        }
+        let members: HashSet<&String> = scc.iter().copied().collect();
+        let Some(start) = modules
+            .iter()
+            .map(|module| &module.path)
+            .find(|path| members.contains(path))
+        else {
+            continue;
+        };
+
+        // Breadth-first search within the component for the shortest path back to the start
+        let mut previous: HashMap<&String, &String> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        let mut last = None;
+        while let Some(current) = queue.pop_front() {
+            if graph.contains_edge(current, start) {
+                last = Some(current);
+                break;
+            }
+            for next in graph.neighbors(current) {
+                if members.contains(next) && next != start && !previous.contains_key(next) {
+                    previous.insert(next, current);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let Some(mut current) = last else {
+            continue;
+        };
+        let mut cycle = vec![start.clone(), current.clone()];
+        while let Some(&before) = previous.get(current) {
+            cycle.push(before.clone());
+            current = before;
+        }
+        cycle.reverse();
+        cycles.push(cycle);
+    }
+
+    cycles
+}
+
 pub fn validate_root_module_treatment(
     root_module_treatment: RootModuleTreatment,
     modules: &[ModuleConfig],
@@ -183,6 +243,7 @@ pub fn validate_root_module_treatment(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::DependencyConfig;
     use crate::{parsing::config::parse_project_config, tests::fixtures::example_dir};
     use rstest::rstest;
     use std::path::PathBuf;
@@ -205,4 +266,26 @@ mod tests {
         let module_paths = find_modules_with_cycles(&modules);
         assert_eq!(module_paths, ["domain_one", "domain_two", "domain_three"]);
     }
+
+    #[test]
+    fn test_find_dependency_cycles_reports_full_path() {
+        let module = |path: &str, dependencies: &[&str]| {
+            ModuleConfig::from_path_and_dependencies(
+                path,
+                Some(
+                    dependencies
+                        .iter()
+                        .map(|dependency| DependencyConfig::from_path(dependency))
+                        .collect(),
+                ),
+            )
+        };
+        let modules = [
+            module("a", &["b", "d"]),
+            module("b", &["c"]),
+            module("c", &["a"]),
+            module("d", &[]),
+        ];
+        assert_eq!(find_dependency_cycles(&modules), [["a", "b", "c", "a"]]);
+    }
 }
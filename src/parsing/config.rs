@@ -152,7 +152,8 @@ pub fn parse_domain_config<P: AsRef<Path>>(
 pub fn add_domain_configs<P: AsRef<Path>>(config: &mut ProjectConfig, root_dir: P) -> Result<()> {
     let root_dir = root_dir.as_ref().to_path_buf();
     let file_walker =
-        filesystem::FSWalker::try_new(&root_dir, &config.exclude, config.respect_gitignore)?;
+        filesystem::FSWalker::try_new(&root_dir, &config.exclude, config.respect_gitignore)?
+            .with_source_extensions(&config.source_extensions);
     let source_root_resolver = SourceRootResolver::new(&root_dir, &file_walker);
     let source_roots = source_root_resolver.resolve(&config.source_roots)?;
     let mut domain_configs = source_roots
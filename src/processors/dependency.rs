@@ -200,22 +200,25 @@ impl<'a> FileProcessor<'a, ProjectFile<'a>> for ExternalDependencyExtractor<'a>
         )?
         .into_iter()
         .filter_map(|import| {
-            let package_resolution = self
-                .package_resolver
-                .resolve_module_path(&import.module_path);
-            match package_resolution {
-                PackageResolution::Found {
-                    package: resolved_package,
-                    ..
-                } => {
-                    if resolved_package.root != package.root {
-                        return Some(Dependency::Import(import));
-                    }
-                }
-                PackageResolution::NotFound => {
-                    return Some(Dependency::Import(import));
-                }
-                PackageResolution::Excluded => (),
+            // First-party prefixes are internal regardless of whether a source root provides them
+            let is_external = !self
+                .project_config
+                .external
+                .is_first_party(&import.module_path)
+                && !package.is_editable_module(&import.module_path)
+                && match self
+                    .package_resolver
+                    .resolve_module_path(&import.module_path)
+                {
+                    PackageResolution::Found {
+                        package: resolved_package,
+                        ..
+                    } => resolved_package.root != package.root,
+                    PackageResolution::NotFound => true,
+                    PackageResolution::Excluded => false,
+                };
+            if is_external {
+                return Some(Dependency::Import(import));
             }
 
             // Remove directives that match irrelevant imports
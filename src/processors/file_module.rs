@@ -79,6 +79,15 @@ impl<'a> FileModule<'a> {
     pub fn declared_dependencies(&self) -> &HashSet<String> {
         &self.package.dependencies
     }
+
+    /// Whether the distribution is declared, or otherwise known to be available to the package
+    pub fn is_dependency_available(&self, distribution_name: &str) -> bool {
+        self.package.dependencies.contains(distribution_name)
+            || self
+                .package
+                .available_dependencies
+                .contains(distribution_name)
+    }
 }
 
 impl<'a> AsRef<FileModule<'a>> for FileModule<'a> {
@@ -4,5 +4,7 @@ pub mod package;
 pub mod source_root;
 
 pub use module::{ModuleGlob, ModuleResolver, ModuleResolverError};
-pub use package::{Package, PackageResolution, PackageResolutionError, PackageResolver};
+pub use package::{
+    DependencyStats, Package, PackageResolution, PackageResolutionError, PackageResolver,
+};
 pub use source_root::{SourceRootResolver, SourceRootResolverError};
@@ -1,9 +1,15 @@
-use std::collections::{HashMap, HashSet};
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-use crate::external::error::ParsingError;
-use crate::external::parsing;
+use crate::config::ExternalDependencyConfig;
+use crate::config::external::is_module_under_prefix;
+use crate::external::error::{ParsingError, ParsingWarning};
+use crate::external::parsing::{
+    self, DependencySource, DependencySources, NameNormalizer, normalize_package_name,
+};
 use crate::filesystem;
 
 #[derive(Error, Debug)]
@@ -14,7 +20,9 @@ pub enum PackageResolutionError {
     Parsing(#[from] ParsingError),
     #[error("Source root '{0}' does not appear to be within project root.")]
     InvalidSourceRoot(String),
-    #[error("Packages defined with setup.py ('{0}') are not supported. ")]
+    #[error(
+        "Packages defined with setup.py ('{0}') are only supported through a 'setup(...)' call."
+    )]
     SetupPyNotSupported(String),
     #[error("Package root not found for path: '{0}'")]
     PackageRootNotFound(String),
@@ -30,15 +38,34 @@ fn is_setup_py_package_root<P: AsRef<Path>>(directory: P) -> bool {
     directory.as_ref().join("setup.py").exists()
 }
 
+fn is_setup_cfg_package_root<P: AsRef<Path>>(directory: P) -> bool {
+    parsing::is_setuptools_setup_cfg(&directory.as_ref().join("setup.cfg"))
+}
+
 fn is_requirements_txt_package_root<P: AsRef<Path>>(directory: P) -> bool {
     directory.as_ref().join("requirements.txt").exists()
 }
 
+fn is_pipfile_package_root<P: AsRef<Path>>(directory: P) -> bool {
+    directory.as_ref().join("Pipfile").exists()
+}
+
+fn conda_environment_path<P: AsRef<Path>>(directory: P) -> Option<PathBuf> {
+    ["environment.yml", "environment.yaml"]
+        .into_iter()
+        .map(|file_name| directory.as_ref().join(file_name))
+        .find(|path| path.is_file())
+}
+
 fn get_package_root<P: AsRef<Path>>(directory: P) -> Option<PackageRoot> {
     if is_pyproject_toml_package_root(directory.as_ref()) {
         return Some(PackageRoot::Pyproject(directory.as_ref().to_path_buf()));
     }
 
+    if is_setup_cfg_package_root(directory.as_ref()) {
+        return Some(PackageRoot::SetupCfg(directory.as_ref().to_path_buf()));
+    }
+
     if is_setup_py_package_root(directory.as_ref()) {
         return Some(PackageRoot::SetupPy(directory.as_ref().to_path_buf()));
     }
@@ -49,6 +76,14 @@ fn get_package_root<P: AsRef<Path>>(directory: P) -> Option<PackageRoot> {
         ));
     }
 
+    if is_pipfile_package_root(directory.as_ref()) {
+        return Some(PackageRoot::Pipfile(directory.as_ref().to_path_buf()));
+    }
+
+    if let Some(environment_path) = conda_environment_path(directory.as_ref()) {
+        return Some(PackageRoot::CondaEnvironment(environment_path));
+    }
+
     None
 }
 
@@ -56,8 +91,12 @@ fn get_package_root<P: AsRef<Path>>(directory: P) -> Option<PackageRoot> {
 #[derive(Debug)]
 enum PackageRoot {
     Pyproject(PathBuf),
+    SetupCfg(PathBuf),
     SetupPy(PathBuf),
     RequirementsTxt(PathBuf),
+    Pipfile(PathBuf),
+    /// The path to the `environment.yml` itself, since its extension may vary
+    CondaEnvironment(PathBuf),
     Empty(PathBuf),
 }
 
@@ -92,55 +131,343 @@ fn find_package_root<P1: AsRef<Path>, P2: AsRef<Path>>(
     Ok(PackageRoot::Empty(current_dir))
 }
 
+fn is_regular_package<P: AsRef<Path>>(directory: P) -> bool {
+    let directory = directory.as_ref();
+    directory.join("__init__.py").exists() || directory.join("__init__.pyi").exists()
+}
+
+/// Find top-level directories in the source roots which are implicit namespace packages,
+/// meaning they have no '__init__.py' of their own but contain regular packages.
+fn detect_implicit_namespaces(source_roots: &[PathBuf]) -> Vec<String> {
+    let mut namespaces: Vec<String> = source_roots
+        .iter()
+        .filter_map(|source_root| std::fs::read_dir(source_root).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && !is_regular_package(path))
+        .filter(|path| {
+            std::fs::read_dir(path).is_ok_and(|children| {
+                children
+                    .filter_map(|child| child.ok())
+                    .any(|child| child.path().is_dir() && is_regular_package(child.path()))
+            })
+        })
+        .filter_map(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.to_string())
+        })
+        .collect();
+    namespaces.sort();
+    namespaces.dedup();
+    namespaces
+}
+
+/// The directories which may hold the modules of local projects installed with `-e`,
+/// for both flat and 'src' layouts. The package itself is already covered by its source roots.
+fn editable_roots(package_root: &Path, editable_paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    editable_paths
+        .into_iter()
+        .filter(|path| path != package_root)
+        .flat_map(|path| [path.join("src"), path])
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Counts describing the dependencies declared by a package, for reporting
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct DependencyStats {
+    /// Distinct dependencies declared across every origin
+    pub declared: usize,
+    /// Dependencies declared by the main dependency list
+    pub project: usize,
+    /// Dependencies declared by each dependency group, keyed by normalized group name
+    pub groups: BTreeMap<String, usize>,
+    /// Dependencies declared by each extra, keyed by normalized extra name
+    pub extras: BTreeMap<String, usize>,
+    /// Declared dependencies which are excluded from checks
+    pub excluded: usize,
+    /// Declared dependencies which are the target of a rename
+    pub renamed: usize,
+}
+
 #[derive(Debug)]
 pub struct Package {
     pub name: Option<String>,
     pub root: PathBuf,
     pub source_roots: Vec<PathBuf>,
     pub dependencies: HashSet<String>,
+    pub dependency_sources: DependencySources,
+    /// Packages known to be available (e.g. linter plugins), which are not direct dependencies
+    pub available_dependencies: HashSet<String>,
+    /// Packages pinned by a lockfile, which may only be transitively available
+    pub locked_dependencies: HashSet<String>,
+    /// Directories of local projects installed with `-e`, whose modules are first-party
+    pub editable_roots: Vec<PathBuf>,
+    pub warnings: Vec<ParsingWarning>,
 }
 
-impl TryFrom<PackageRoot> for Package {
-    type Error = PackageResolutionError;
+impl Package {
+    fn try_from_package_root(
+        package_root: PackageRoot,
+        external_config: &ExternalDependencyConfig,
+        normalizer: &mut NameNormalizer,
+    ) -> Result<Self> {
+        let mut package =
+            Self::try_from_package_root_manifest(package_root, external_config, normalizer)?;
+        if external_config.conda_lock {
+            let lock_path = package.root.join("conda-lock.yml");
+            if lock_path.is_file() {
+                package
+                    .locked_dependencies
+                    .extend(parsing::parse_conda_lock(&lock_path, normalizer)?);
+            }
+        }
+        if external_config.uv_support {
+            let pyproject_path = package.root.join("pyproject.toml");
+            if pyproject_path.is_file() {
+                package
+                    .available_dependencies
+                    .extend(parsing::parse_uv_constraints(&pyproject_path, normalizer)?);
+            }
+        }
+        for pip_install_file in &external_config.pip_install_files {
+            let file_path = package.root.join(pip_install_file);
+            if file_path.is_file() {
+                let content = std::fs::read_to_string(&file_path).map_err(ParsingError::from)?;
+                package
+                    .available_dependencies
+                    .extend(parsing::parse_pip_install_mentions(&content, normalizer));
+            }
+        }
+        if external_config.tool_plugins {
+            for config_file in ["setup.cfg", ".flake8"] {
+                let config_path = package.root.join(config_file);
+                if config_path.is_file() {
+                    package
+                        .available_dependencies
+                        .extend(parsing::parse_tool_plugins(&config_path, normalizer)?);
+                }
+            }
+        }
+        Ok(package)
+    }
 
-    fn try_from(value: PackageRoot) -> std::result::Result<Self, Self::Error> {
-        match value {
+    fn try_from_package_root_manifest(
+        package_root: PackageRoot,
+        external_config: &ExternalDependencyConfig,
+        normalizer: &mut NameNormalizer,
+    ) -> Result<Self> {
+        match package_root {
             PackageRoot::Pyproject(path) => {
-                let project_info = parsing::parse_pyproject_toml(&path.join("pyproject.toml"))?;
+                let project_info = parsing::parse_pyproject_toml_with(
+                    &path.join("pyproject.toml"),
+                    external_config,
+                    normalizer,
+                )?;
+
+                Ok(Self {
+                    name: project_info.name,
+                    root: path,
+                    source_roots: vec![],
+                    dependencies: project_info.dependencies,
+                    dependency_sources: project_info.dependency_sources,
+                    available_dependencies: HashSet::new(),
+                    locked_dependencies: HashSet::new(),
+                    editable_roots: vec![],
+                    warnings: project_info.warnings,
+                })
+            }
+            PackageRoot::SetupCfg(path) => {
+                let project_info = parsing::parse_setup_cfg_with(
+                    &path.join("setup.cfg"),
+                    external_config,
+                    normalizer,
+                )?;
+
+                Ok(Self {
+                    name: project_info.name,
+                    root: path,
+                    source_roots: vec![],
+                    dependencies: project_info.dependencies,
+                    dependency_sources: project_info.dependency_sources,
+                    available_dependencies: HashSet::new(),
+                    locked_dependencies: HashSet::new(),
+                    editable_roots: vec![],
+                    warnings: project_info.warnings,
+                })
+            }
+            PackageRoot::SetupPy(path) => {
+                let project_info = match parsing::parse_setup_py_with(
+                    &path.join("setup.py"),
+                    external_config,
+                    normalizer,
+                ) {
+                    Ok(project_info) => project_info,
+                    Err(ParsingError::SetupCallNotFound { .. }) => {
+                        return Err(PackageResolutionError::SetupPyNotSupported(
+                            path.display().to_string(),
+                        ));
+                    }
+                    Err(err) => return Err(err.into()),
+                };
 
                 Ok(Self {
                     name: project_info.name,
                     root: path,
                     source_roots: vec![],
                     dependencies: project_info.dependencies,
+                    dependency_sources: project_info.dependency_sources,
+                    available_dependencies: HashSet::new(),
+                    locked_dependencies: HashSet::new(),
+                    editable_roots: vec![],
+                    warnings: project_info.warnings,
                 })
             }
-            PackageRoot::SetupPy(path) => Err(PackageResolutionError::SetupPyNotSupported(
-                path.display().to_string(),
-            )),
             PackageRoot::RequirementsTxt(path) => {
-                let dependencies = parsing::parse_requirements_txt(&path.join("requirements.txt"))?;
+                let requirements =
+                    parsing::parse_requirements(&path.join("requirements.txt"), normalizer)?;
+
+                Ok(Self {
+                    name: None,
+                    root: path,
+                    source_roots: vec![],
+                    dependencies: requirements.dependencies,
+                    dependency_sources: DependencySources::new(),
+                    // Constrained packages are pinned, like uv constraints, without being declared
+                    available_dependencies: requirements.constraints,
+                    locked_dependencies: HashSet::new(),
+                    editable_roots: editable_roots(&path, requirements.editable_paths),
+                    warnings: vec![],
+                })
+            }
+            PackageRoot::Pipfile(path) => {
+                let include_dev = external_config.pipfile_include_dev;
+                let dependencies =
+                    parsing::parse_pipfile(&path.join("Pipfile"), include_dev, normalizer)?;
+                let lock_path = path.join("Pipfile.lock");
+                let locked_dependencies = if lock_path.is_file() {
+                    parsing::parse_pipfile_lock(&lock_path, include_dev, normalizer)?
+                } else {
+                    HashSet::new()
+                };
 
                 Ok(Self {
                     name: None,
                     root: path,
                     source_roots: vec![],
                     dependencies,
+                    dependency_sources: DependencySources::new(),
+                    available_dependencies: HashSet::new(),
+                    locked_dependencies,
+                    editable_roots: vec![],
+                    warnings: vec![],
+                })
+            }
+            PackageRoot::CondaEnvironment(environment_path) => {
+                let dependencies = parsing::parse_conda_environment(&environment_path, normalizer)?;
+
+                Ok(Self {
+                    name: None,
+                    root: environment_path.parent().unwrap().to_path_buf(),
+                    source_roots: vec![],
+                    dependencies,
+                    dependency_sources: DependencySources::new(),
+                    available_dependencies: HashSet::new(),
+                    locked_dependencies: HashSet::new(),
+                    editable_roots: vec![],
+                    warnings: vec![],
                 })
             }
             PackageRoot::Empty(path) => Ok(Self::empty(path)),
         }
     }
-}
 
-impl Package {
     pub fn empty<P: AsRef<Path>>(root: P) -> Self {
         Self {
             name: None,
             root: root.as_ref().to_path_buf(),
             source_roots: vec![],
             dependencies: HashSet::new(),
+            dependency_sources: DependencySources::new(),
+            available_dependencies: HashSet::new(),
+            locked_dependencies: HashSet::new(),
+            editable_roots: vec![],
+            warnings: vec![],
+        }
+    }
+
+    /// Whether the dependency is declared only by the given groups, and not by the main
+    /// dependency list or any other group. Dependencies without known sources are not.
+    pub fn is_dependency_only_from_groups(&self, dependency: &str, groups: &[String]) -> bool {
+        self.dependency_sources
+            .get(dependency)
+            .is_some_and(|sources| {
+                !sources.is_empty()
+                    && sources.iter().all(|source| match source {
+                        DependencySource::Group(group) | DependencySource::Extra(group) => groups
+                            .iter()
+                            .any(|exempt| &normalize_package_name(exempt) == group),
+                        DependencySource::Project => false,
+                    })
+            })
+    }
+
+    /// The members of each dependency group (or extra) declared by this package,
+    /// keyed and sorted by normalized group name
+    pub fn dependency_groups(&self) -> BTreeMap<&str, BTreeSet<&str>> {
+        let mut groups: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for (dependency, sources) in &self.dependency_sources {
+            for source in sources {
+                if let DependencySource::Group(group) | DependencySource::Extra(group) = source {
+                    groups.entry(group).or_default().insert(dependency);
+                }
+            }
+        }
+        groups
+    }
+
+    /// Count this package's dependencies by origin, along with how many are excluded
+    /// or renamed. `renames` maps modules to distributions, as in [`ExternalDependencyConfig::rename_map`].
+    /// A dependency declared by several origins is counted once for each of them.
+    pub fn stats(
+        &self,
+        excluded: &HashSet<String>,
+        renames: &HashMap<String, String>,
+    ) -> DependencyStats {
+        let excluded: HashSet<String> = excluded
+            .iter()
+            .map(|name| normalize_package_name(name))
+            .collect();
+        let renamed: HashSet<String> = renames
+            .values()
+            .map(|name| normalize_package_name(name))
+            .collect();
+        let mut stats = DependencyStats {
+            declared: self.dependencies.len(),
+            excluded: self.dependencies.intersection(&excluded).count(),
+            renamed: self.dependencies.intersection(&renamed).count(),
+            ..Default::default()
+        };
+        for source in self.dependency_sources.values().flatten() {
+            match source {
+                DependencySource::Project => stats.project += 1,
+                DependencySource::Group(group) => {
+                    *stats.groups.entry(group.clone()).or_default() += 1
+                }
+                DependencySource::Extra(extra) => {
+                    *stats.extras.entry(extra.clone()).or_default() += 1
+                }
+            }
         }
+        stats
+    }
+
+    /// Whether the module is provided by a local project which this package installs with `-e`
+    pub fn is_editable_module(&self, module_path: &str) -> bool {
+        !self.editable_roots.is_empty()
+            && filesystem::module_to_file_path(&self.editable_roots, module_path, true).is_some()
     }
 
     fn set_source_roots(&mut self, source_roots: Vec<PathBuf>) {
@@ -163,6 +490,8 @@ pub struct PackageResolver<'a> {
     source_roots: &'a [PathBuf],
     file_walker: &'a filesystem::FSWalker,
     package_for_source_root: HashMap<PathBuf, Package>,
+    namespace_packages: Vec<String>,
+    preserve_unknown_chars: bool,
 }
 
 impl<'a> PackageResolver<'a> {
@@ -170,12 +499,16 @@ impl<'a> PackageResolver<'a> {
         project_root: &'a PathBuf,
         source_roots: &'a [PathBuf],
         file_walker: &'a filesystem::FSWalker,
+        external_config: &ExternalDependencyConfig,
     ) -> Result<Self> {
+        // Packages of a workspace tend to declare the same dependencies, so they share one normalizer
+        let mut normalizer = external_config.name_normalizer();
         let package_for_source_root = source_roots
             .iter()
             .map(|source_root| {
                 let package_root = find_package_root(project_root, source_root)?;
-                let mut package: Package = package_root.try_into()?;
+                let mut package =
+                    Package::try_from_package_root(package_root, external_config, &mut normalizer)?;
                 package.set_source_roots(source_roots.to_vec());
                 Ok((source_root.clone(), package))
             })
@@ -184,9 +517,26 @@ impl<'a> PackageResolver<'a> {
             source_roots,
             file_walker,
             package_for_source_root,
+            namespace_packages: external_config
+                .namespace_packages
+                .iter()
+                .cloned()
+                .chain(
+                    external_config
+                        .detect_namespace_packages
+                        .then(|| detect_implicit_namespaces(source_roots))
+                        .unwrap_or_default(),
+                )
+                .collect(),
+            preserve_unknown_chars: external_config.preserve_unknown_chars,
         })
     }
 
+    /// Normalize a distribution name the same way as the dependencies of resolved packages
+    pub fn normalize_package_name(&self, name: &str) -> String {
+        parsing::normalize_package_name_with(name, self.preserve_unknown_chars)
+    }
+
     pub fn get_package_for_source_root<P: AsRef<Path>>(
         &'a self,
         source_root: P,
@@ -194,6 +544,13 @@ impl<'a> PackageResolver<'a> {
         self.package_for_source_root.get(source_root.as_ref())
     }
 
+    /// Iterate over each resolved package once, even if it owns multiple source roots
+    pub fn packages(&self) -> impl Iterator<Item = &Package> {
+        self.package_for_source_root
+            .values()
+            .unique_by(|package| &package.root)
+    }
+
     pub fn get_package_by_package_root(&self, package_root: &PathBuf) -> Option<&Package> {
         self.package_for_source_root
             .values()
@@ -223,10 +580,24 @@ impl<'a> PackageResolver<'a> {
         }
     }
 
+    fn is_in_namespace_package(&self, module_path: &str) -> bool {
+        self.namespace_packages
+            .iter()
+            .any(|namespace| is_module_under_prefix(module_path, namespace))
+    }
+
     pub fn resolve_module_path(&self, module_path: &str) -> PackageResolution {
-        if let Some(resolved_module) =
+        // Subpackages of a namespace package may live in any source root,
+        // so look for the module itself in every root before falling back to
+        // a member of the namespace's '__init__.py' in an earlier root.
+        let resolved_module = if self.is_in_namespace_package(module_path) {
+            filesystem::module_to_file_path(self.source_roots, module_path, false)
+                .or_else(|| filesystem::module_to_file_path(self.source_roots, module_path, true))
+        } else {
             filesystem::module_to_file_path(self.source_roots, module_path, true)
-        {
+        };
+
+        if let Some(resolved_module) = resolved_module {
             if self
                 .file_walker
                 .is_path_excluded(&resolved_module.file_path, false)
@@ -246,3 +617,194 @@ impl<'a> PackageResolver<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RespectGitIgnore;
+    use rstest::rstest;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Two members which share the 'myorg' namespace, with 'myorg.utils' only provided
+    /// by the second member. The first member ships a pkgutil-style 'myorg/__init__.py',
+    /// while the second ships an implicit namespace package unless `pkgutil_everywhere` is set.
+    fn namespace_project(pkgutil_everywhere: bool) -> TempDir {
+        let project = TempDir::with_prefix("tach-test").unwrap();
+        for (member, module) in [("core", "core_one"), ("utils", "utils")] {
+            let package_dir = project.path().join(member).join("src/myorg");
+            fs::create_dir_all(package_dir.join(module)).unwrap();
+            fs::write(
+                project.path().join(member).join("pyproject.toml"),
+                format!("[project]\nname = \"myorg-{member}\"\n"),
+            )
+            .unwrap();
+            if member == "core" || pkgutil_everywhere {
+                fs::write(
+                    package_dir.join("__init__.py"),
+                    "__path__ = __import__('pkgutil').extend_path(__path__, __name__)\n",
+                )
+                .unwrap();
+            }
+            fs::write(package_dir.join(module).join("__init__.py"), "").unwrap();
+        }
+        fs::write(
+            project.path().join("core/src/myorg/core_one/__init__.py"),
+            "from myorg.utils import helper\n",
+        )
+        .unwrap();
+        project
+    }
+
+    fn resolve_source_root(
+        project: &TempDir,
+        external_config: &ExternalDependencyConfig,
+        module_path: &str,
+    ) -> Option<PathBuf> {
+        let project_root = project.path().to_path_buf();
+        let source_roots = vec![
+            project_root.join("core/src"),
+            project_root.join("utils/src"),
+        ];
+        let file_walker =
+            filesystem::FSWalker::try_new(&project_root, &[], RespectGitIgnore::False).unwrap();
+        let package_resolver =
+            PackageResolver::try_new(&project_root, &source_roots, &file_walker, external_config)
+                .unwrap();
+        match package_resolver.resolve_module_path(module_path) {
+            PackageResolution::Found { source_root, .. } => Some(source_root),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_namespace_package_resolves_across_source_roots() {
+        let project = namespace_project(true);
+        let external_config = ExternalDependencyConfig {
+            namespace_packages: vec!["myorg".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            resolve_source_root(&project, &external_config, "myorg.utils"),
+            Some(project.path().join("utils/src"))
+        );
+    }
+
+    #[rstest]
+    #[case(true, "utils/src")]
+    #[case(false, "core/src")]
+    fn test_detect_implicit_namespace_packages(
+        #[case] detect_namespace_packages: bool,
+        #[case] expected_source_root: &str,
+    ) {
+        let project = namespace_project(false);
+        let external_config = ExternalDependencyConfig {
+            detect_namespace_packages,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            resolve_source_root(&project, &external_config, "myorg.utils"),
+            Some(project.path().join(expected_source_root))
+        );
+    }
+
+    #[test]
+    fn test_dependency_stats() {
+        let project = TempDir::with_prefix("tach-test").unwrap();
+        fs::write(
+            project.path().join("pyproject.toml"),
+            r#"[project]
+name = "dashboard"
+dependencies = ["requests", "PyYAML", "attrs"]
+
+[dependency-groups]
+dev = ["pytest", "attrs"]
+"#,
+        )
+        .unwrap();
+
+        let package = Package::try_from_package_root(
+            PackageRoot::Pyproject(project.path().to_path_buf()),
+            &ExternalDependencyConfig::default(),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+        let stats = package.stats(
+            &HashSet::from(["pytest".to_string()]),
+            &HashMap::from([("yaml".to_string(), "PyYAML".to_string())]),
+        );
+
+        assert_eq!(
+            stats,
+            DependencyStats {
+                declared: 4,
+                project: 3,
+                groups: BTreeMap::from([("dev".to_string(), 2)]),
+                extras: BTreeMap::new(),
+                excluded: 1,
+                renamed: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_uv_constraints_are_available_but_not_declared() {
+        let project = TempDir::with_prefix("tach-test").unwrap();
+        fs::write(
+            project.path().join("pyproject.toml"),
+            "[project]\nname = \"uv-project\"\ndependencies = [\"requests\"]\n\n[tool.uv]\nconstraint-dependencies = [\"urllib3<2\"]\n",
+        )
+        .unwrap();
+        let external_config = ExternalDependencyConfig {
+            uv_support: true,
+            ..Default::default()
+        };
+
+        let package = Package::try_from_package_root(
+            PackageRoot::Pyproject(project.path().to_path_buf()),
+            &external_config,
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+
+        assert!(package.available_dependencies.contains("urllib3"));
+        assert!(!package.dependencies.contains("urllib3"));
+        assert!(package.dependencies.contains("requests"));
+    }
+
+    #[test]
+    fn test_requirements_editable_paths_provide_modules() {
+        let project = TempDir::new().unwrap();
+        fs::write(
+            project.path().join("requirements.txt"),
+            "-e .\n-e ./libs/flat\n-e ./libs/layered\n-e ./libs/missing\n",
+        )
+        .unwrap();
+        fs::create_dir_all(project.path().join("libs/flat/flat")).unwrap();
+        fs::write(project.path().join("libs/flat/flat/__init__.py"), "").unwrap();
+        fs::create_dir_all(project.path().join("libs/layered/src")).unwrap();
+        fs::write(project.path().join("libs/layered/src/layered.py"), "").unwrap();
+
+        let package = Package::try_from_package_root(
+            PackageRoot::RequirementsTxt(project.path().to_path_buf()),
+            &ExternalDependencyConfig::default(),
+            &mut NameNormalizer::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            package.editable_roots,
+            vec![
+                project.path().join("libs/flat"),
+                project.path().join("libs/layered/src"),
+                project.path().join("libs/layered"),
+            ]
+        );
+        assert!(package.is_editable_module("flat.api"));
+        assert!(package.is_editable_module("layered"));
+        assert!(!package.is_editable_module("missing"));
+        assert!(package.dependencies.is_empty());
+    }
+}